@@ -6,7 +6,7 @@ use crate::ember::message::Incoming;
 use crate::parameters::messaging::handler::IncomingMessage;
 
 /// A complete incoming APS message with an owned, unrestricted payload.
-#[derive(Debug)]
+#[derive(Clone, Debug)]
 pub struct DefragmentedMessage {
     typ: u8,
     aps_frame: ApsFrame,
@@ -1,7 +1,17 @@
 //! The AES encryption algorithm.
+//!
+//! Besides the hash context exchanged with the NCP, this module implements the
+//! AES-MMO hash and the keyed hash of the Zigbee specification on the host, so
+//! that keys can be derived without NCP round trips.
 
 use le_stream::{FromLeStream, ToLeStream};
 
+pub use self::keyed_hash::{hashed_link_key, key_load_key, key_transport_key, keyed_hash};
+pub use self::mmo_hash::{MmoHash, mmo_hash};
+
+mod keyed_hash;
+mod mmo_hash;
+
 /// The hash context for an ongoing hash operation.
 #[derive(Clone, Debug, Eq, PartialEq, FromLeStream, ToLeStream)]
 pub struct MmoHashContext {
@@ -0,0 +1,129 @@
+use silizium::zigbee::security::man::Key;
+
+use super::MmoHash;
+use crate::ember::Eui64;
+
+const INNER_PAD: u8 = 0x36;
+const OUTER_PAD: u8 = 0x5C;
+const KEY_TRANSPORT: u8 = 0x00;
+const KEY_LOAD: u8 = 0x02;
+
+/// Returns the keyed hash of `message` for message authentication.
+///
+/// This is the HMAC construction over the AES-MMO hash from section B.1.4 of
+/// the Zigbee specification.
+#[must_use]
+pub fn keyed_hash(key: &Key, message: &[u8]) -> Key {
+    let mut inner = MmoHash::new();
+    inner.update(&pad(key, INNER_PAD));
+    inner.update(message);
+
+    let mut outer = MmoHash::new();
+    outer.update(&pad(key, OUTER_PAD));
+    outer.update(&inner.finalize());
+    outer.finalize()
+}
+
+/// Returns the key-transport key derived from a link key.
+///
+/// Devices use it to protect network keys transported with the link key.
+#[must_use]
+pub fn key_transport_key(link_key: &Key) -> Key {
+    keyed_hash(link_key, &[KEY_TRANSPORT])
+}
+
+/// Returns the key-load key derived from a link key.
+///
+/// Devices use it to protect link keys transported with the link key.
+#[must_use]
+pub fn key_load_key(link_key: &Key) -> Key {
+    keyed_hash(link_key, &[KEY_LOAD])
+}
+
+/// Returns the link key a trust center with a hashed link key shares with a device.
+///
+/// With [`TRUST_CENTER_USES_HASHED_LINK_KEY`](crate::ember::security::initial::Bitmask::TRUST_CENTER_USES_HASHED_LINK_KEY)
+/// set, the trust center derives each device's link key as the keyed hash of
+/// the device's EUI64 in little-endian byte order under the trust center link
+/// key.
+#[must_use]
+pub fn hashed_link_key(tc_link_key: &Key, eui64: Eui64) -> Key {
+    let mut eui64 = eui64.into_array();
+    eui64.reverse();
+    keyed_hash(tc_link_key, &eui64)
+}
+
+fn pad(key: &Key, pad: u8) -> Key {
+    key.map(|byte| byte ^ pad)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{hashed_link_key, key_load_key, key_transport_key, keyed_hash};
+    use crate::ember::Eui64;
+
+    /// Link key of the install code from Silicon Labs AN1089.
+    const LINK_KEY: [u8; 16] = [
+        0x66, 0xB6, 0x90, 0x09, 0x81, 0xE1, 0xEE, 0x3C, 0xA4, 0x20, 0x6B, 0x6B, 0x86, 0x1C, 0x02,
+        0xBB,
+    ];
+    const TC_LINK_KEY: [u8; 16] = [
+        0x40, 0x41, 0x42, 0x43, 0x44, 0x45, 0x46, 0x47, 0x48, 0x49, 0x4A, 0x4B, 0x4C, 0x4D, 0x4E,
+        0x4F,
+    ];
+
+    #[test]
+    fn derives_key_transport_key() {
+        assert_eq!(key_transport_key(&LINK_KEY), keyed_hash(&LINK_KEY, &[0x00]));
+        assert_eq!(
+            key_transport_key(&LINK_KEY),
+            [
+                0x3C, 0x6C, 0xCA, 0x89, 0x77, 0xEB, 0x18, 0x9E, 0xFD, 0x16, 0x14, 0xC3, 0xE7, 0xF7,
+                0x59, 0x89,
+            ]
+        );
+    }
+
+    #[test]
+    fn derives_key_load_key() {
+        assert_eq!(key_load_key(&LINK_KEY), keyed_hash(&LINK_KEY, &[0x02]));
+        assert_eq!(
+            key_load_key(&LINK_KEY),
+            [
+                0x11, 0x77, 0xA0, 0xEE, 0x26, 0x00, 0xD0, 0x02, 0x0D, 0xBA, 0x82, 0xD7, 0x04, 0x9F,
+                0x53, 0xBC,
+            ]
+        );
+    }
+
+    #[test]
+    fn derives_hashed_link_key_from_little_endian_eui64() {
+        let eui64 = Eui64::new(0x00, 0x0B, 0x57, 0xFF, 0xFE, 0x12, 0x34, 0x56);
+        assert_eq!(
+            hashed_link_key(&TC_LINK_KEY, eui64),
+            keyed_hash(
+                &TC_LINK_KEY,
+                &[0x56, 0x34, 0x12, 0xFE, 0xFF, 0x57, 0x0B, 0x00]
+            )
+        );
+        assert_eq!(
+            hashed_link_key(&TC_LINK_KEY, eui64),
+            [
+                0x3F, 0x29, 0xC4, 0x16, 0x38, 0xF1, 0xB7, 0x8F, 0x98, 0x09, 0x52, 0x50, 0xF5, 0x5A,
+                0xFC, 0xBB,
+            ]
+        );
+    }
+
+    #[test]
+    fn keyed_hash_matches_specification() {
+        // Test vector from section C.6.1 of the Zigbee specification.
+        assert_eq!(
+            keyed_hash(&TC_LINK_KEY, &[0xC0]),
+            [
+                0x45, 0x12, 0x80, 0x7B, 0xF9, 0x4C, 0xB3, 0x40, 0x0F, 0x0E, 0x2C, 0x25, 0xFB, 0x76,
+                0xE9, 0x99,
+            ]
+        );
+    }
+}
@@ -0,0 +1,190 @@
+use aes::Aes128;
+use aes::cipher::{BlockCipherEncrypt, KeyInit};
+
+const BLOCK_SIZE: usize = 16;
+/// Messages of this many bytes or more encode their length in 32 instead of 16 bits.
+const LONG_MESSAGE_LENGTH: u64 = 1 << 13;
+const SHORT_LENGTH_OFFSET: usize = BLOCK_SIZE - 2;
+const LONG_LENGTH_OFFSET: usize = BLOCK_SIZE - 6;
+const END_OF_MESSAGE: u8 = 0x80;
+
+/// Incremental AES-MMO hash.
+///
+/// The Matyas–Meyer–Oseas construction uses AES-128 with the previous hash
+/// value as the key and XORs the encrypted block with the message block. The
+/// message is padded as specified in section B.6 of the Zigbee specification,
+/// so that the result matches
+/// [`TrustCenter::aes_mmo_hash`](crate::TrustCenter::aes_mmo_hash) on the NCP.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct MmoHash {
+    result: [u8; BLOCK_SIZE],
+    block: [u8; BLOCK_SIZE],
+    buffered: usize,
+    length: u64,
+}
+
+impl MmoHash {
+    /// Creates a new hash.
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            result: [0; BLOCK_SIZE],
+            block: [0; BLOCK_SIZE],
+            buffered: 0,
+            length: 0,
+        }
+    }
+
+    /// Hashes the given data.
+    pub fn update(&mut self, data: &[u8]) {
+        self.length += data.len() as u64;
+
+        for &byte in data {
+            self.push(byte);
+        }
+    }
+
+    /// Pads the message and returns the hash value.
+    #[must_use]
+    pub fn finalize(mut self) -> [u8; BLOCK_SIZE] {
+        let bits = self.length * 8;
+        self.push(END_OF_MESSAGE);
+
+        if self.length < LONG_MESSAGE_LENGTH {
+            self.pad(SHORT_LENGTH_OFFSET);
+            #[expect(clippy::cast_possible_truncation)]
+            for byte in (bits as u16).to_be_bytes() {
+                self.push(byte);
+            }
+        } else {
+            self.pad(LONG_LENGTH_OFFSET);
+            #[expect(clippy::cast_possible_truncation)]
+            for byte in (bits as u32).to_be_bytes().into_iter().chain([0, 0]) {
+                self.push(byte);
+            }
+        }
+
+        self.result
+    }
+
+    /// Appends zeros until the current block has `offset` bytes.
+    fn pad(&mut self, offset: usize) {
+        while self.buffered != offset {
+            self.push(0);
+        }
+    }
+
+    fn push(&mut self, byte: u8) {
+        self.block[self.buffered] = byte;
+        self.buffered += 1;
+
+        if self.buffered == BLOCK_SIZE {
+            self.compress();
+            self.buffered = 0;
+        }
+    }
+
+    fn compress(&mut self) {
+        let mut block = self.block.into();
+        Aes128::new(&self.result.into()).encrypt_block(&mut block);
+
+        for ((result, encrypted), plain) in self.result.iter_mut().zip(block).zip(self.block) {
+            *result = encrypted ^ plain;
+        }
+    }
+}
+
+/// Returns the AES-MMO hash of `data`.
+#[must_use]
+pub fn mmo_hash(data: &[u8]) -> [u8; BLOCK_SIZE] {
+    let mut hash = MmoHash::new();
+    hash.update(data);
+    hash.finalize()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{MmoHash, mmo_hash};
+
+    /// Returns a message of `length` bytes counting up from zero.
+    fn counting(length: usize) -> Vec<u8> {
+        (0..=u8::MAX).cycle().take(length).collect()
+    }
+
+    #[test]
+    fn hashes_single_byte() {
+        // Test vector from section C.5.1 of the Zigbee specification.
+        assert_eq!(
+            mmo_hash(&[0xC0]),
+            [
+                0xAE, 0x3A, 0x10, 0x2A, 0x28, 0xD4, 0x3E, 0xE0, 0xD4, 0xA0, 0x9E, 0x22, 0x78, 0x8B,
+                0x20, 0x6C,
+            ]
+        );
+    }
+
+    #[test]
+    fn hashes_full_block() {
+        // Test vector from section C.5.2 of the Zigbee specification.
+        let message: Vec<u8> = (0xC0..=0xCF).collect();
+        assert_eq!(
+            mmo_hash(&message),
+            [
+                0xA7, 0x97, 0x7E, 0x88, 0xBC, 0x0B, 0x61, 0xE8, 0x21, 0x08, 0x27, 0x10, 0x9A, 0x22,
+                0x8F, 0x2D,
+            ]
+        );
+    }
+
+    #[test]
+    fn hashes_longest_short_message() {
+        // Test vector from section C.5.3 of the Zigbee specification.
+        assert_eq!(
+            mmo_hash(&counting(8191)),
+            [
+                0x24, 0xEC, 0x2F, 0xE7, 0x5B, 0xBF, 0xFC, 0xB3, 0x47, 0x89, 0xBC, 0x06, 0x10, 0xE7,
+                0xF1, 0x65,
+            ]
+        );
+    }
+
+    #[test]
+    fn pads_long_message_with_length_trailer() {
+        // At 8192 bytes, the bit length no longer fits into 16 bits.
+        assert_eq!(
+            mmo_hash(&counting(8192)),
+            [
+                0xDC, 0x6B, 0x06, 0x87, 0xF0, 0x9F, 0x86, 0x07, 0x13, 0x1C, 0x17, 0x0B, 0x3B, 0xD3,
+                0x15, 0x91,
+            ]
+        );
+    }
+
+    #[test]
+    fn hashes_incrementally() {
+        let message = counting(100);
+        let mut hash = MmoHash::new();
+
+        for chunk in message.chunks(7) {
+            hash.update(chunk);
+        }
+
+        assert_eq!(hash.finalize(), mmo_hash(&message));
+    }
+
+    #[test]
+    fn hashes_install_code() {
+        // Install code and link key from Silicon Labs AN1089, as derived by the NCP.
+        let install_code = [
+            0x83, 0xFE, 0xD3, 0x40, 0x7A, 0x93, 0x97, 0x23, 0xA5, 0xC6, 0x39, 0xB2, 0x69, 0x16,
+            0xD5, 0x05, 0xC3, 0xB5,
+        ];
+        assert_eq!(
+            mmo_hash(&install_code),
+            [
+                0x66, 0xB6, 0x90, 0x09, 0x81, 0xE1, 0xEE, 0x3C, 0xA4, 0x20, 0x6B, 0x6B, 0x86, 0x1C,
+                0x02, 0xBB,
+            ]
+        );
+    }
+}
@@ -38,10 +38,53 @@ impl Parameters {
             max_hops,
         })
     }
+
+    /// Returns the concentrator type.
+    ///
+    /// # Errors
+    ///
+    /// Returns the raw value if it is not a valid [`Type`].
+    pub const fn concentrator_type(&self) -> Result<Type, u16> {
+        match self.concentrator_type {
+            low_ram if low_ram == Type::LowRam as u16 => Ok(Type::LowRam),
+            high_ram if high_ram == Type::HighRam as u16 => Ok(Type::HighRam),
+            other => Err(other),
+        }
+    }
+
+    /// Returns the minimum time between two many-to-one route requests.
+    #[must_use]
+    pub const fn min_time(&self) -> Duration {
+        Duration::from_secs(self.min_time as u64)
+    }
+
+    /// Returns the maximum time between two many-to-one route requests.
+    #[must_use]
+    pub const fn max_time(&self) -> Duration {
+        Duration::from_secs(self.max_time as u64)
+    }
+
+    /// Returns the number of route errors that trigger a many-to-one route request.
+    #[must_use]
+    pub const fn route_error_threshold(&self) -> u8 {
+        self.route_error_threshold
+    }
+
+    /// Returns the number of delivery failures that trigger a many-to-one route request.
+    #[must_use]
+    pub const fn delivery_failure_threshold(&self) -> u8 {
+        self.delivery_failure_threshold
+    }
+
+    /// Returns the maximum number of hops of a many-to-one route request.
+    #[must_use]
+    pub const fn max_hops(&self) -> u8 {
+        self.max_hops
+    }
 }
 
 /// Ember concentrator type.
-#[derive(Clone, Debug, Eq, PartialEq)]
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
 #[repr(u16)]
 pub enum Type {
     /// A concentrator with insufficient memory to store source routes for the entire network.
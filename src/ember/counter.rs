@@ -3,8 +3,10 @@
 use num_derive::FromPrimitive;
 use num_traits::FromPrimitive;
 
+use crate::ember::constants::COUNTER_TYPE_COUNT;
+
 /// Ember counter type
-#[derive(Debug, Clone, Copy, Ord, PartialOrd, Eq, PartialEq, FromPrimitive)]
+#[derive(Debug, Clone, Copy, Ord, PartialOrd, Eq, Hash, PartialEq, FromPrimitive)]
 #[repr(u8)]
 pub enum Type {
     /// The MAC received a broadcast.
@@ -113,3 +115,55 @@ impl TryFrom<u8> for Type {
         Self::from_u8(value).ok_or(value)
     }
 }
+
+/// Ember counter values indexed by their [`Type`].
+///
+/// Convert the raw arrays returned by
+/// [`Utilities::read_counters`](crate::Utilities::read_counters) and
+/// [`Utilities::read_and_clear_counters`](crate::Utilities::read_and_clear_counters)
+/// into `Counters` to access them by type.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct Counters([u16; COUNTER_TYPE_COUNT]);
+
+impl Counters {
+    /// Returns the value of the given counter.
+    #[must_use]
+    pub const fn get(&self, typ: Type) -> u16 {
+        self.0[typ as usize]
+    }
+
+    /// Returns the values of all counter types.
+    pub fn iter(&self) -> impl Iterator<Item = (Type, u16)> {
+        self.0
+            .iter()
+            .zip(0..)
+            .filter_map(|(&value, index)| match Type::try_from(index) {
+                Ok(Type::TypeCount) | Err(_) => None,
+                Ok(typ) => Some((typ, value)),
+            })
+    }
+
+    /// Returns the raw counter values.
+    #[must_use]
+    pub const fn as_array(&self) -> &[u16; COUNTER_TYPE_COUNT] {
+        &self.0
+    }
+}
+
+impl Default for Counters {
+    fn default() -> Self {
+        Self([0; COUNTER_TYPE_COUNT])
+    }
+}
+
+impl From<[u16; COUNTER_TYPE_COUNT]> for Counters {
+    fn from(values: [u16; COUNTER_TYPE_COUNT]) -> Self {
+        Self(values)
+    }
+}
+
+impl From<Counters> for [u16; COUNTER_TYPE_COUNT] {
+    fn from(counters: Counters) -> Self {
+        counters.0
+    }
+}
@@ -85,6 +85,10 @@ pub enum Error {
     /// NCP startup was requested without any application endpoints.
     #[error("No endpoints provided.")]
     NoEndpoints,
+
+    /// The network address of a device could not be resolved.
+    #[error("Network address of {0} not resolved.")]
+    AddressNotResolved(ember::Eui64),
 }
 
 impl From<Result<ezsp::Status, u8>> for Error {
@@ -189,6 +193,10 @@ impl From<Error> for io::Error {
                 Self::new(ErrorKind::WouldBlock, "Transaction queue full")
             }
             Error::NoEndpoints => Self::new(ErrorKind::InvalidInput, "No endpoints configured"),
+            Error::AddressNotResolved(ieee_address) => Self::new(
+                ErrorKind::NotFound,
+                format!("Network address of {ieee_address} not resolved"),
+            ),
         }
     }
 }
@@ -2,6 +2,8 @@ use core::num::TryFromIntError;
 use std::io;
 use std::io::ErrorKind;
 
+use crate::ember::Eui64;
+
 /// Invalid values.
 #[derive(Clone, Copy, Debug, Eq, PartialEq, thiserror::Error)]
 pub enum ValueError {
@@ -36,19 +38,89 @@ pub enum ValueError {
     /// Indicates that some expected payload was missing.
     #[error("Missing payload")]
     MissingPayload,
+
+    /// A reportable change does not match the data type of its attribute.
+    #[error("Invalid reportable change for attribute: {0:#06X}")]
+    ReportableChange(u16),
+
+    /// A manufacturing token has an unexpected length.
+    #[error("Invalid length of manufacturing token {token:#04X}: {length}")]
+    MfgTokenLength {
+        /// The manufacturing token ID.
+        token: u8,
+        /// The length of the token read from the NCP.
+        length: usize,
+    },
+
+    /// An EUI64 is not a valid unicast device address.
+    #[error("Invalid EUI64: {0}")]
+    InvalidEui64(Eui64),
+
+    /// The NCP's custom EUI64 token has already been written.
+    #[error("Custom EUI64 already set: {0}")]
+    CustomEui64AlreadySet(Eui64),
+
+    /// The custom EUI64 token read back after writing differs from the written value.
+    #[error("Custom EUI64 verification failed: {0:?}")]
+    CustomEui64Mismatch(Option<Eui64>),
+
+    /// The NCP does not hold a network key with the expected sequence number.
+    #[error("Network key sequence number mismatch: expected {expected}, got {actual:?}")]
+    NetworkKeySequenceNumber {
+        /// The expected sequence number.
+        expected: u8,
+        /// The sequence number reported by the NCP, if the key is set.
+        actual: Option<u8>,
+    },
+
+    /// An install code does not have a valid length including its CRC.
+    #[error("Invalid install code length: {0}")]
+    InstallCodeLength(usize),
+
+    /// The CRC of an install code does not match the code.
+    #[error("Invalid install code CRC: expected {expected:#06X}, got {actual:#06X}")]
+    InstallCodeCrc {
+        /// The CRC computed from the code.
+        expected: u16,
+        /// The CRC following the code.
+        actual: u16,
+    },
+
+    /// A binding of the given type cannot be created on a device.
+    #[error("Invalid binding type: {0:#04X}")]
+    BindingType(u8),
+
+    /// A token element read back after restoring it differs from the written data.
+    #[error("Token verification failed: {key:#010X}[{index}]")]
+    TokenVerification {
+        /// The NVM3 key of the token.
+        key: u32,
+        /// The index of the element.
+        index: u8,
+    },
 }
 
 impl From<ValueError> for io::Error {
     fn from(error: ValueError) -> Self {
         let kind = match error {
-            ValueError::InvalidRouteRadius(_) => ErrorKind::InvalidInput,
+            ValueError::InvalidRouteRadius(_)
+            | ValueError::ReportableChange(_)
+            | ValueError::InvalidEui64(_)
+            | ValueError::InstallCodeLength(_)
+            | ValueError::InstallCodeCrc { .. }
+            | ValueError::BindingType(_) => ErrorKind::InvalidInput,
+            ValueError::CustomEui64AlreadySet(_) => ErrorKind::AlreadyExists,
             ValueError::InvalidFrameId(_)
             | ValueError::EmberDutyCycleState(_)
             | ValueError::EmberNetworkStatus(_)
             | ValueError::EmberNodeType(_)
             | ValueError::DecisionId(_)
             | ValueError::EntropySource(_)
-            | ValueError::MissingPayload => ErrorKind::InvalidData,
+            | ValueError::MissingPayload
+            | ValueError::MfgTokenLength { .. }
+            | ValueError::CustomEui64Mismatch(_)
+            | ValueError::NetworkKeySequenceNumber { .. }
+            | ValueError::TokenVerification { .. } => ErrorKind::InvalidData,
         };
 
         Self::new(kind, error)
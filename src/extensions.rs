@@ -5,8 +5,10 @@ use std::fmt::Display;
 
 pub use self::configuration_ext::ConfigurationExt;
 pub use self::policy_ext::PolicyExt;
+pub use self::tables_ext::{TableWalk, TablesExt};
 mod configuration_ext;
 mod policy_ext;
+mod tables_ext;
 
 /// Extension trait for converting a type into a displayable form.
 pub trait Displayable {
@@ -0,0 +1,465 @@
+use std::collections::BTreeMap;
+use std::ops::Range;
+use std::pin::Pin;
+
+use log::{trace, warn};
+
+use crate::ember::{Eui64, NodeId, binding, child, multicast, neighbor, route};
+use crate::error::Status;
+use crate::ezsp::config;
+use crate::parameters::networking::get_source_route_table_entry;
+use crate::parameters::security::export_link_key_by_index;
+use crate::{Binding, Configuration, Error, Messaging, Networking, Security};
+
+/// Reads the slot at an index, returning `None` if it is empty.
+type ReadSlot<T, E> =
+    for<'t> fn(
+        &'t mut T,
+        u8,
+    ) -> Pin<Box<dyn Future<Output = Result<Option<E>, Error>> + Send + 't>>;
+
+/// Extension trait for reading the NCP's tables.
+///
+/// Each method reads the size the NCP reports for its table and returns a
+/// [`TableWalk`], which lazily reads the slots up to that size and yields the
+/// entries in use together with their table index. Errors other than the
+/// status the NCP reports for an empty slot end the walk.
+pub trait TablesExt {
+    /// Walks the active entries of the neighbor table.
+    ///
+    /// The table is read up to [`Networking::neighbor_count`].
+    fn neighbor_table(
+        &mut self,
+    ) -> impl Future<Output = Result<TableWalk<'_, Self, neighbor::TableEntry>, Error>> + Send;
+
+    /// Walks the entries of the child table.
+    ///
+    /// The table is read up to [`config::Id::MaxEndDeviceChildren`] until the
+    /// child count reported by [`Networking::get_parent_child_parameters`] is
+    /// reached. Empty slots, reported as [`ember::Status::NotJoined`](crate::ember::Status::NotJoined),
+    /// are skipped.
+    fn child_table(
+        &mut self,
+    ) -> impl Future<Output = Result<TableWalk<'_, Self, child::Data>, Error>> + Send;
+
+    /// Walks the used entries of the route table.
+    ///
+    /// The table is read up to [`config::Id::RouteTableSize`].
+    fn route_table(
+        &mut self,
+    ) -> impl Future<Output = Result<TableWalk<'_, Self, route::TableEntry>, Error>> + Send;
+
+    /// Walks the entries of the source route table.
+    ///
+    /// The table is read up to [`Networking::get_source_route_table_filled_size`].
+    fn source_route_table(
+        &mut self,
+    ) -> impl Future<
+        Output = Result<TableWalk<'_, Self, get_source_route_table_entry::Entry>, Error>,
+    > + Send;
+
+    /// Walks the active entries of the binding table.
+    ///
+    /// The table is read up to [`config::Id::BindingTableSize`].
+    fn binding_table(
+        &mut self,
+    ) -> impl Future<Output = Result<TableWalk<'_, Self, binding::TableEntry>, Error>> + Send;
+
+    /// Walks the IEEE and network addresses of the active address table entries.
+    ///
+    /// The table is read up to [`config::Id::AddressTableSize`].
+    fn address_table(
+        &mut self,
+    ) -> impl Future<Output = Result<TableWalk<'_, Self, (Eui64, NodeId)>, Error>> + Send;
+
+    /// Walks the used entries of the multicast table.
+    ///
+    /// The table is read up to [`config::Id::MulticastTableSize`]. Entries
+    /// without an endpoint are unused.
+    fn multicast_table(
+        &mut self,
+    ) -> impl Future<Output = Result<TableWalk<'_, Self, multicast::TableEntry>, Error>> + Send;
+
+    /// Walks the link keys of the key table.
+    ///
+    /// The table is read up to [`config::Id::KeyTableSize`]. Empty slots,
+    /// reported as [`silizium::Status::NotFound`], are skipped.
+    fn key_table(
+        &mut self,
+    ) -> impl Future<Output = Result<TableWalk<'_, Self, export_link_key_by_index::Payload>, Error>> + Send;
+}
+
+impl<T> TablesExt for T
+where
+    T: Binding + Configuration + Messaging + Networking + Security + Send,
+{
+    async fn neighbor_table(&mut self) -> Result<TableWalk<'_, Self, neighbor::TableEntry>, Error> {
+        let slots = Slots::new(self.neighbor_count().await?);
+        Ok(TableWalk::new(self, slots, |transport, index| {
+            Box::pin(async move { transport.get_neighbor(index).await.map(Some) })
+        }))
+    }
+
+    async fn child_table(&mut self) -> Result<TableWalk<'_, Self, child::Data>, Error> {
+        let children = self.get_parent_child_parameters().await?.child_count();
+        let size = table_size(self, config::Id::MaxEndDeviceChildren).await?;
+        Ok(TableWalk::new(
+            self,
+            Slots::limited(size, children),
+            |transport, index| {
+                Box::pin(async move {
+                    empty_slot(transport.get_child_data(index).await, is_empty_child_slot)
+                })
+            },
+        ))
+    }
+
+    async fn route_table(&mut self) -> Result<TableWalk<'_, Self, route::TableEntry>, Error> {
+        let slots = Slots::new(table_size(self, config::Id::RouteTableSize).await?);
+        Ok(TableWalk::new(self, slots, |transport, index| {
+            Box::pin(async move {
+                let entry = transport.get_route_table_entry(index).await?;
+                Ok((entry.status() != Ok(route::Status::Unused)).then_some(entry))
+            })
+        }))
+    }
+
+    async fn source_route_table(
+        &mut self,
+    ) -> Result<TableWalk<'_, Self, get_source_route_table_entry::Entry>, Error> {
+        let slots = Slots::new(self.get_source_route_table_filled_size().await?);
+        Ok(TableWalk::new(self, slots, |transport, index| {
+            Box::pin(async move {
+                transport
+                    .get_source_route_table_entry(index)
+                    .await
+                    .map(Some)
+            })
+        }))
+    }
+
+    async fn binding_table(&mut self) -> Result<TableWalk<'_, Self, binding::TableEntry>, Error> {
+        let slots = Slots::new(table_size(self, config::Id::BindingTableSize).await?);
+        Ok(TableWalk::new(self, slots, |transport, index| {
+            Box::pin(async move {
+                let entry = Binding::get(transport, index).await?;
+                Ok((entry.typ() != Ok(binding::Type::Unused)).then_some(entry))
+            })
+        }))
+    }
+
+    async fn address_table(&mut self) -> Result<TableWalk<'_, Self, (Eui64, NodeId)>, Error> {
+        let slots = Slots::new(table_size(self, config::Id::AddressTableSize).await?);
+        Ok(TableWalk::new(self, slots, |transport, index| {
+            Box::pin(async move {
+                if !transport.address_table_entry_is_active(index).await? {
+                    return Ok(None);
+                }
+
+                let eui64 = transport.get_address_table_remote_eui64(index).await?;
+                let node_id = transport.get_address_table_remote_node_id(index).await?;
+                Ok(Some((eui64, node_id)))
+            })
+        }))
+    }
+
+    async fn multicast_table(
+        &mut self,
+    ) -> Result<TableWalk<'_, Self, multicast::TableEntry>, Error> {
+        let slots = Slots::new(table_size(self, config::Id::MulticastTableSize).await?);
+        Ok(TableWalk::new(self, slots, |transport, index| {
+            Box::pin(async move {
+                let entry = transport.get_multicast_table_entry(index).await?;
+                Ok((entry.endpoint() != 0).then_some(entry))
+            })
+        }))
+    }
+
+    async fn key_table(
+        &mut self,
+    ) -> Result<TableWalk<'_, Self, export_link_key_by_index::Payload>, Error> {
+        let slots = Slots::new(table_size(self, config::Id::KeyTableSize).await?);
+        Ok(TableWalk::new(self, slots, |transport, index| {
+            Box::pin(async move {
+                empty_slot(
+                    transport.export_link_key_by_index(index).await,
+                    is_empty_key_slot,
+                )
+            })
+        }))
+    }
+}
+
+/// A lazy walk over the used entries of an NCP table.
+///
+/// Obtained from the methods of [`TablesExt`]. Each call to
+/// [`next`](Self::next) reads slots until it finds the next entry in use, so
+/// a walk can be stopped early without reading the rest of the table.
+pub struct TableWalk<'t, T, E>
+where
+    T: ?Sized,
+{
+    transport: &'t mut T,
+    slots: Slots,
+    read: ReadSlot<T, E>,
+}
+
+impl<'t, T, E> TableWalk<'t, T, E>
+where
+    T: ?Sized,
+{
+    const fn new(transport: &'t mut T, slots: Slots, read: ReadSlot<T, E>) -> Self {
+        Self {
+            transport,
+            slots,
+            read,
+        }
+    }
+
+    /// Returns the next entry in use together with its table index.
+    ///
+    /// Returns `None` when the walk is complete. After an error, the walk ends.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`Error`] if reading a slot fails with a status other than
+    /// the one reported for an empty slot.
+    pub async fn next(&mut self) -> Option<Result<(u8, E), Error>> {
+        while let Some(index) = self.slots.next_index() {
+            match (self.read)(self.transport, index).await {
+                Ok(Some(entry)) => {
+                    self.slots.record_used();
+                    return Some(Ok((index, entry)));
+                }
+                Ok(None) => trace!("Table entry {index} is empty."),
+                Err(error) => {
+                    self.slots.stop();
+                    return Some(Err(error));
+                }
+            }
+        }
+
+        None
+    }
+
+    /// Reads the remaining entries in use, keyed by their table index.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`Error`] if reading a slot fails with a status other than
+    /// the one reported for an empty slot.
+    pub async fn collect(mut self) -> Result<BTreeMap<u8, E>, Error> {
+        let mut entries = BTreeMap::new();
+
+        while let Some(entry) = self.next().await {
+            let (index, entry) = entry?;
+            entries.insert(index, entry);
+        }
+
+        Ok(entries)
+    }
+}
+
+impl<T, E> std::fmt::Debug for TableWalk<'_, T, E>
+where
+    T: ?Sized,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("TableWalk")
+            .field("slots", &self.slots)
+            .finish_non_exhaustive()
+    }
+}
+
+/// The slots of a table that remain to be read.
+#[derive(Debug)]
+struct Slots {
+    indexes: Range<u8>,
+    remaining: Option<u8>,
+}
+
+impl Slots {
+    /// Walks all `size` slots.
+    const fn new(size: u8) -> Self {
+        Self {
+            indexes: 0..size,
+            remaining: None,
+        }
+    }
+
+    /// Walks the `size` slots until `used` entries were found.
+    const fn limited(size: u8, used: u8) -> Self {
+        Self {
+            indexes: 0..size,
+            remaining: Some(used),
+        }
+    }
+
+    /// Returns the index of the next slot to read, if any.
+    fn next_index(&mut self) -> Option<u8> {
+        if self.remaining == Some(0) {
+            return None;
+        }
+
+        self.indexes.next()
+    }
+
+    /// Records that the slot just read is in use.
+    const fn record_used(&mut self) {
+        if let Some(remaining) = &mut self.remaining {
+            *remaining = remaining.saturating_sub(1);
+        }
+    }
+
+    /// Ends the walk.
+    const fn stop(&mut self) {
+        self.remaining = Some(0);
+    }
+}
+
+/// Returns the configured size of a table, limited to the index range.
+///
+/// Slots beyond index 254 cannot be addressed by the EZSP table commands, so
+/// a larger size is logged and capped.
+async fn table_size<T>(transport: &mut T, id: config::Id) -> Result<u8, Error>
+where
+    T: Configuration + Send,
+{
+    let size = transport.get_configuration_value(id).await?;
+    Ok(u8::try_from(size).unwrap_or_else(|_| {
+        warn!(
+            "Size {size} of {id:?} exceeds the addressable slots. Reading {} slots.",
+            u8::MAX
+        );
+        u8::MAX
+    }))
+}
+
+/// Maps the error reported for an empty slot to `None`.
+fn empty_slot<E>(
+    result: Result<E, Error>,
+    is_empty: fn(&Error) -> bool,
+) -> Result<Option<E>, Error> {
+    match result {
+        Ok(entry) => Ok(Some(entry)),
+        Err(error) if is_empty(&error) => Ok(None),
+        Err(error) => Err(error),
+    }
+}
+
+const fn is_empty_child_slot(error: &Error) -> bool {
+    matches!(
+        error,
+        Error::Status(Status::Ember(Ok(crate::ember::Status::NotJoined)))
+    )
+}
+
+const fn is_empty_key_slot(error: &Error) -> bool {
+    matches!(
+        error,
+        Error::Status(Status::Sl(Ok(silizium::Status::NotFound)))
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use std::pin::pin;
+    use std::task::{Context, Poll, Waker};
+
+    use super::{Slots, TableWalk, empty_slot, is_empty_child_slot, is_empty_key_slot};
+    use crate::Error;
+    use crate::ember::Status;
+
+    fn walk(mut table: Slots, used: &[u8]) -> Vec<u8> {
+        let mut read = Vec::new();
+
+        while let Some(index) = table.next_index() {
+            read.push(index);
+
+            if used.contains(&index) {
+                table.record_used();
+            }
+        }
+
+        read
+    }
+
+    #[test]
+    fn stops_without_children() {
+        assert!(walk(Slots::limited(6, 0), &[]).is_empty());
+    }
+
+    #[test]
+    fn stops_after_the_last_child() {
+        assert_eq!(walk(Slots::limited(6, 2), &[1, 3]), [0, 1, 2, 3]);
+    }
+
+    /// Completes a future that never waits.
+    fn ready<F>(future: F) -> F::Output
+    where
+        F: Future,
+    {
+        let mut context = Context::from_waker(Waker::noop());
+
+        match pin!(future).poll(&mut context) {
+            Poll::Ready(output) => output,
+            Poll::Pending => panic!("future should be ready"),
+        }
+    }
+
+    type TestSlots = Vec<Result<Option<u8>, Status>>;
+
+    fn table(slots: &mut TestSlots) -> TableWalk<'_, TestSlots, u8> {
+        let size = u8::try_from(slots.len()).expect("test table fits");
+        TableWalk::new(slots, Slots::new(size), |slots, index| {
+            let slot = slots[usize::from(index)];
+            Box::pin(async move { slot.map_err(|status| Ok(status).into()) })
+        })
+    }
+
+    #[test]
+    fn skips_inactive_slots() {
+        let mut slots = vec![Ok(None), Ok(Some(1)), Ok(None), Ok(Some(3))];
+        let entries = ready(table(&mut slots).collect()).expect("table should be read");
+        assert_eq!(entries.into_keys().collect::<Vec<_>>(), [1, 3]);
+    }
+
+    #[test]
+    fn yields_entries_lazily() {
+        let mut slots = vec![Ok(Some(0)), Err(Status::ErrFatal), Ok(Some(2))];
+        let mut table = table(&mut slots);
+        assert!(matches!(ready(table.next()), Some(Ok((0, 0)))));
+        assert!(matches!(ready(table.next()), Some(Err(_))));
+        assert!(ready(table.next()).is_none());
+    }
+
+    #[test]
+    fn reads_up_to_the_filled_size() {
+        assert_eq!(walk(Slots::new(3), &[0, 1, 2]), [0, 1, 2]);
+        assert!(walk(Slots::new(0), &[]).is_empty());
+    }
+
+    #[test]
+    fn stops_after_an_error() {
+        let mut table = Slots::new(4);
+        assert_eq!(table.next_index(), Some(0));
+        table.stop();
+        assert_eq!(table.next_index(), None);
+    }
+
+    #[test]
+    fn propagates_errors_of_used_slots() {
+        let empty_child: Result<u8, Error> = Err(Ok(Status::NotJoined).into());
+        assert!(matches!(
+            empty_slot(empty_child, is_empty_child_slot),
+            Ok(None)
+        ));
+
+        let empty_key: Result<u8, Error> = Err(Ok(silizium::Status::NotFound).into());
+        assert!(matches!(empty_slot(empty_key, is_empty_key_slot), Ok(None)));
+
+        let failed: Result<u8, Error> = Err(Ok(Status::ErrFatal).into());
+        assert!(empty_slot(failed, is_empty_child_slot).is_err());
+        assert!(matches!(empty_slot(Ok(7), is_empty_key_slot), Ok(Some(7))));
+    }
+}
@@ -1,11 +1,19 @@
 //! Manufacturing tokens.
 
+pub use cbke_data::CbkeData;
+pub use installation_code::InstallationCode;
 pub use mfg::Mfg;
+pub use mfg_string::MfgString;
 use num_traits::FromPrimitive;
 pub use stack::Stack;
+pub use token::Token;
 
+mod cbke_data;
+mod installation_code;
 mod mfg;
+mod mfg_string;
 mod stack;
+mod token;
 
 /// Manufacturing token IDs used by `ezspGetMfgToken()`.
 #[derive(Debug, Clone, Copy, Eq, PartialEq)]
@@ -0,0 +1,73 @@
+use std::fmt::{self, Display, Formatter};
+
+use le_stream::{FromLeStream, ToLeStream};
+
+use crate::ember::{CertificateData, PrivateKeyData, PublicKeyData};
+
+/// Preinstalled Certificate Based Key Exchange (CBKE) data.
+///
+/// This is the layout of the [`CbkeData`](super::Mfg::CbkeData) token for the
+/// 163k1 curve. The [`Display`] implementation omits the private key.
+#[derive(Clone, Debug, Eq, Hash, PartialEq, FromLeStream, ToLeStream)]
+pub struct CbkeData {
+    certificate: CertificateData,
+    ca_public_key: PublicKeyData,
+    private_key: PrivateKeyData,
+    flags: u8,
+}
+
+impl CbkeData {
+    /// Creates new CBKE data.
+    #[must_use]
+    pub const fn new(
+        certificate: CertificateData,
+        ca_public_key: PublicKeyData,
+        private_key: PrivateKeyData,
+        flags: u8,
+    ) -> Self {
+        Self {
+            certificate,
+            ca_public_key,
+            private_key,
+            flags,
+        }
+    }
+
+    /// Returns the device's implicit certificate.
+    #[must_use]
+    pub const fn certificate(&self) -> &CertificateData {
+        &self.certificate
+    }
+
+    /// Returns the public key of the certificate authority.
+    #[must_use]
+    pub const fn ca_public_key(&self) -> &PublicKeyData {
+        &self.ca_public_key
+    }
+
+    /// Returns the device's private key.
+    #[must_use]
+    pub const fn private_key(&self) -> &PrivateKeyData {
+        &self.private_key
+    }
+
+    /// Returns the flags.
+    #[must_use]
+    pub const fn flags(&self) -> u8 {
+        self.flags
+    }
+}
+
+impl Display for CbkeData {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "certificate ")?;
+        write_hex(f, &self.certificate)?;
+        write!(f, ", CA public key ")?;
+        write_hex(f, &self.ca_public_key)?;
+        write!(f, ", flags {:#04X}", self.flags)
+    }
+}
+
+fn write_hex(f: &mut Formatter<'_>, bytes: &[u8]) -> fmt::Result {
+    bytes.iter().try_for_each(|byte| write!(f, "{byte:02X}"))
+}
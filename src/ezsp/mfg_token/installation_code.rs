@@ -0,0 +1,74 @@
+use le_stream::{FromLeStream, ToLeStream};
+
+use crate::{InstallCode, ValueError};
+
+const CODE_LENGTHS: [usize; 4] = [6, 8, 12, 16];
+const CODE_LENGTH_SHIFT: u8 = 1;
+const CODE_LENGTH_MASK: u16 = 0b11;
+
+/// The content of the [`InstallationCode`](super::Mfg::InstallationCode) token.
+///
+/// The token stores the code padded to 16 bytes, its CRC, and flags whose
+/// bits 1 and 2 select the code length of 6, 8, 12 or 16 bytes.
+#[derive(Clone, Debug, Eq, Hash, PartialEq, FromLeStream, ToLeStream)]
+pub struct InstallationCode {
+    flags: u16,
+    value: [u8; 16],
+    crc: u16,
+}
+
+impl InstallationCode {
+    /// Returns the flags.
+    #[must_use]
+    pub const fn flags(&self) -> u16 {
+        self.flags
+    }
+
+    /// Returns the padded code.
+    #[must_use]
+    pub const fn value(&self) -> &[u8; 16] {
+        &self.value
+    }
+
+    /// Returns the CRC of the code.
+    #[must_use]
+    pub const fn crc(&self) -> u16 {
+        self.crc
+    }
+
+    /// Returns the code length selected by the flags.
+    #[must_use]
+    pub fn code_length(&self) -> usize {
+        CODE_LENGTHS[usize::from((self.flags >> CODE_LENGTH_SHIFT) & CODE_LENGTH_MASK)]
+    }
+
+    /// Returns the validated install code.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`ValueError`] if the CRC does not match the code.
+    pub fn install_code(&self) -> Result<InstallCode, ValueError> {
+        let mut bytes = self.value[..self.code_length()].to_vec();
+        bytes.extend_from_slice(&self.crc.to_le_bytes());
+        InstallCode::try_from(bytes.as_slice())
+    }
+}
+
+impl From<&InstallCode> for InstallationCode {
+    fn from(install_code: &InstallCode) -> Self {
+        let code = install_code.code();
+        let mut value = [0; 16];
+        value[..code.len()].copy_from_slice(code);
+        let index = CODE_LENGTHS
+            .iter()
+            .position(|&length| length == code.len())
+            .and_then(|index| u16::try_from(index).ok())
+            .unwrap_or(CODE_LENGTH_MASK);
+
+        Self {
+            flags: index << CODE_LENGTH_SHIFT,
+            value,
+            crc: install_code.crc(),
+        }
+    }
+}
@@ -1,7 +1,8 @@
+use enum_iterator::Sequence;
 use num_derive::FromPrimitive;
 
 /// Manufacturing token IDs pertaining to the manufacturer.
-#[derive(Debug, Clone, Copy, Eq, PartialEq, FromPrimitive)]
+#[derive(Debug, Clone, Copy, Eq, PartialEq, FromPrimitive, Sequence)]
 #[repr(u8)]
 pub enum Mfg {
     /// Custom version (2 bytes).
@@ -0,0 +1,53 @@
+use std::fmt::{self, Display, Formatter};
+
+const LENGTH: usize = 16;
+const PADDING: [u8; 2] = [0x00, 0xFF];
+
+/// A 16 byte text token, such as the manufacturing string or the board name.
+///
+/// The text is padded with zero or erased bytes.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct MfgString([u8; LENGTH]);
+
+impl MfgString {
+    /// Creates a zero-padded text token.
+    ///
+    /// Returns `None` if `text` exceeds 16 bytes.
+    #[must_use]
+    pub fn new(text: &str) -> Option<Self> {
+        let mut bytes = [0; LENGTH];
+        bytes
+            .get_mut(..text.len())?
+            .copy_from_slice(text.as_bytes());
+        Some(Self(bytes))
+    }
+
+    /// Returns the raw token bytes.
+    #[must_use]
+    pub const fn as_bytes(&self) -> &[u8; LENGTH] {
+        &self.0
+    }
+
+    /// Returns the text without its padding.
+    #[must_use]
+    pub fn text(&self) -> String {
+        let end = self
+            .0
+            .iter()
+            .position(|byte| PADDING.contains(byte))
+            .unwrap_or(LENGTH);
+        String::from_utf8_lossy(&self.0[..end]).into_owned()
+    }
+}
+
+impl Display for MfgString {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        self.text().fmt(f)
+    }
+}
+
+impl From<[u8; LENGTH]> for MfgString {
+    fn from(bytes: [u8; LENGTH]) -> Self {
+        Self(bytes)
+    }
+}
@@ -1,7 +1,8 @@
+use enum_iterator::Sequence;
 use num_derive::FromPrimitive;
 
 /// Manufacturing token IDs pertaining to the stack.
-#[derive(Debug, Clone, Copy, Eq, PartialEq, FromPrimitive)]
+#[derive(Debug, Clone, Copy, Eq, PartialEq, FromPrimitive, Sequence)]
 #[repr(u8)]
 pub enum Stack {
     /// Radio calibration data (64 bytes).
@@ -0,0 +1,230 @@
+use std::fmt::{self, Display, Formatter};
+use std::iter::once;
+
+use le_stream::{FromLeStream, ToLeStream};
+
+use super::{CbkeData, Id, InstallationCode, Mfg, MfgString, Stack};
+use crate::ValueError;
+use crate::ember::Eui64;
+use crate::types::ByteSizedVec;
+
+/// Value of an erased token byte.
+const ERASED: u8 = 0xFF;
+
+/// A decoded manufacturing token.
+///
+/// Decode the bytes returned by
+/// [`Utilities::get_mfg_token`](crate::Utilities::get_mfg_token) with
+/// [`decode`](Self::decode) and encode a token for
+/// [`Utilities::set_mfg_token`](crate::Utilities::set_mfg_token) with
+/// [`to_bytes`](Self::to_bytes). The [`Display`] implementation omits secret
+/// keys.
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub enum Token {
+    /// Custom version.
+    CustomVersion(u16),
+    /// Manufacturing string.
+    String(MfgString),
+    /// Board name.
+    BoardName(MfgString),
+    /// Manufacturer ID.
+    ManufId(u16),
+    /// Radio configuration bitmask.
+    PhyConfig(u16),
+    /// Bootload AES key.
+    BootloadAesKey([u8; 16]),
+    /// ASH configuration values.
+    AshConfig([u16; 20]),
+    /// EZSP storage.
+    EzspStorage([u8; 8]),
+    /// Radio calibration data of the 16 channels.
+    CalData([u8; 64]),
+    /// Certificate Based Key Exchange (CBKE) data.
+    CbkeData(CbkeData),
+    /// Installation code.
+    InstallationCode(InstallationCode),
+    /// Radio channel filter calibration data.
+    CalFilter(u8),
+    /// Custom EUI64 MAC address.
+    CustomEui64(Eui64),
+    /// Crystal tuning value.
+    CTune(u16),
+}
+
+impl Token {
+    /// Decodes the bytes of the token `id`.
+    ///
+    /// Returns `None` if the token is erased.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`ValueError::MfgTokenLength`] if `bytes` does not have the
+    /// token's [`length`](Self::length).
+    pub fn decode(id: Id, bytes: &[u8]) -> Result<Option<Self>, ValueError> {
+        let invalid = || ValueError::MfgTokenLength {
+            token: id.into(),
+            length: bytes.len(),
+        };
+
+        if bytes.len() != Self::length(id) {
+            return Err(invalid());
+        }
+
+        if bytes.iter().all(|&byte| byte == ERASED) {
+            return Ok(None);
+        }
+
+        let stream = bytes.iter().copied();
+        match id {
+            Id::Mfg(Mfg::CustomVersion) => u16::from_le_stream(stream).map(Self::CustomVersion),
+            Id::Mfg(Mfg::String) => {
+                <[u8; 16]>::from_le_stream(stream).map(|bytes| Self::String(bytes.into()))
+            }
+            Id::Mfg(Mfg::BoardName) => {
+                <[u8; 16]>::from_le_stream(stream).map(|bytes| Self::BoardName(bytes.into()))
+            }
+            Id::Mfg(Mfg::ManufId) => u16::from_le_stream(stream).map(Self::ManufId),
+            Id::Mfg(Mfg::PhyConfig) => u16::from_le_stream(stream).map(Self::PhyConfig),
+            Id::Mfg(Mfg::BootloadAesKey) => {
+                FromLeStream::from_le_stream(stream).map(Self::BootloadAesKey)
+            }
+            Id::Mfg(Mfg::AshConfig) => FromLeStream::from_le_stream(stream).map(Self::AshConfig),
+            Id::Mfg(Mfg::EzspStorage) => {
+                FromLeStream::from_le_stream(stream).map(Self::EzspStorage)
+            }
+            Id::Mfg(Mfg::CbkeData) => CbkeData::from_le_stream(stream).map(Self::CbkeData),
+            Id::Mfg(Mfg::InstallationCode) => {
+                InstallationCode::from_le_stream(stream).map(Self::InstallationCode)
+            }
+            Id::Mfg(Mfg::CustomEui64) => Eui64::from_le_stream(stream).map(Self::CustomEui64),
+            Id::Mfg(Mfg::CTune) => u16::from_le_stream(stream).map(Self::CTune),
+            Id::Stack(Stack::CalData) => FromLeStream::from_le_stream(stream).map(Self::CalData),
+            Id::Stack(Stack::CalFilter) => u8::from_le_stream(stream).map(Self::CalFilter),
+        }
+        .map(Some)
+        .ok_or_else(invalid)
+    }
+
+    /// Returns the length of the token `id` in bytes.
+    #[must_use]
+    pub const fn length(id: Id) -> usize {
+        match id {
+            Id::Stack(Stack::CalFilter) => 1,
+            Id::Mfg(Mfg::CustomVersion | Mfg::ManufId | Mfg::PhyConfig | Mfg::CTune) => 2,
+            Id::Mfg(Mfg::EzspStorage | Mfg::CustomEui64) => 8,
+            Id::Mfg(Mfg::String | Mfg::BoardName | Mfg::BootloadAesKey) => 16,
+            Id::Mfg(Mfg::InstallationCode) => 20,
+            Id::Mfg(Mfg::AshConfig) => 40,
+            Id::Stack(Stack::CalData) => 64,
+            Id::Mfg(Mfg::CbkeData) => 92,
+        }
+    }
+
+    /// Returns the ID of the token.
+    #[must_use]
+    pub const fn id(&self) -> Id {
+        match self {
+            Self::CustomVersion(_) => Id::Mfg(Mfg::CustomVersion),
+            Self::String(_) => Id::Mfg(Mfg::String),
+            Self::BoardName(_) => Id::Mfg(Mfg::BoardName),
+            Self::ManufId(_) => Id::Mfg(Mfg::ManufId),
+            Self::PhyConfig(_) => Id::Mfg(Mfg::PhyConfig),
+            Self::BootloadAesKey(_) => Id::Mfg(Mfg::BootloadAesKey),
+            Self::AshConfig(_) => Id::Mfg(Mfg::AshConfig),
+            Self::EzspStorage(_) => Id::Mfg(Mfg::EzspStorage),
+            Self::CalData(_) => Id::Stack(Stack::CalData),
+            Self::CbkeData(_) => Id::Mfg(Mfg::CbkeData),
+            Self::InstallationCode(_) => Id::Mfg(Mfg::InstallationCode),
+            Self::CalFilter(_) => Id::Stack(Stack::CalFilter),
+            Self::CustomEui64(_) => Id::Mfg(Mfg::CustomEui64),
+            Self::CTune(_) => Id::Mfg(Mfg::CTune),
+        }
+    }
+
+    /// Encodes the token.
+    #[must_use]
+    pub fn to_bytes(&self) -> ByteSizedVec<u8> {
+        match self {
+            Self::CustomVersion(value)
+            | Self::ManufId(value)
+            | Self::PhyConfig(value)
+            | Self::CTune(value) => value.to_le_stream().collect(),
+            Self::String(string) | Self::BoardName(string) => {
+                string.as_bytes().iter().copied().collect()
+            }
+            Self::BootloadAesKey(key) => key.iter().copied().collect(),
+            Self::AshConfig(config) => config.to_le_stream().collect(),
+            Self::EzspStorage(storage) => storage.iter().copied().collect(),
+            Self::CalData(data) => data.iter().copied().collect(),
+            Self::CbkeData(data) => data.clone().to_le_stream().collect(),
+            Self::InstallationCode(code) => code.clone().to_le_stream().collect(),
+            Self::CalFilter(filter) => once(*filter).collect(),
+            Self::CustomEui64(eui64) => eui64.to_le_stream().collect(),
+        }
+    }
+}
+
+impl Display for Token {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::CustomVersion(value)
+            | Self::ManufId(value)
+            | Self::PhyConfig(value)
+            | Self::CTune(value) => write!(f, "{value:#06X}"),
+            Self::String(string) | Self::BoardName(string) => write!(f, "{string:?}"),
+            Self::BootloadAesKey(_) => write!(f, "set"),
+            Self::AshConfig(config) => config.iter().enumerate().try_for_each(|(index, value)| {
+                if index > 0 {
+                    write!(f, " ")?;
+                }
+
+                write!(f, "{value:04X}")
+            }),
+            Self::EzspStorage(bytes) => write_hex(f, bytes),
+            Self::CalData(bytes) => write_hex(f, bytes),
+            Self::CbkeData(data) => data.fmt(f),
+            Self::InstallationCode(code) => {
+                write_hex(f, &code.value()[..code.code_length()])?;
+                write!(f, " CRC {:#06X}", code.crc())?;
+
+                if code.install_code().is_err() {
+                    write!(f, " (invalid)")?;
+                }
+
+                Ok(())
+            }
+            Self::CalFilter(filter) => write!(f, "{filter:#04X}"),
+            Self::CustomEui64(eui64) => eui64.fmt(f),
+        }
+    }
+}
+
+fn write_hex(f: &mut Formatter<'_>, bytes: &[u8]) -> fmt::Result {
+    bytes.iter().try_for_each(|byte| write!(f, "{byte:02X}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Token;
+    use crate::ezsp::mfg_token::{Id, Mfg, MfgString};
+
+    #[test]
+    fn decodes_and_encodes_tokens() {
+        let mut bytes = *b"EZSP-NCP\0\0\0\0\0\0\0\0";
+        let token = Token::decode(Id::Mfg(Mfg::BoardName), &bytes).expect("valid length");
+        assert_eq!(token, Some(Token::BoardName(bytes.into())));
+        assert_eq!(MfgString::from(bytes).text(), "EZSP-NCP");
+        assert_eq!(token.expect("not erased").to_bytes().as_slice(), &bytes);
+
+        bytes.fill(0xFF);
+        assert_eq!(
+            Token::decode(Id::Mfg(Mfg::BoardName), &bytes).ok(),
+            Some(None)
+        );
+        assert_eq!(
+            Token::decode(Id::Mfg(Mfg::CTune), &[0x34, 0x12]).ok(),
+            Some(Some(Token::CTune(0x1234)))
+        );
+        assert!(Token::decode(Id::Mfg(Mfg::CTune), &[0x34]).is_err());
+    }
+}
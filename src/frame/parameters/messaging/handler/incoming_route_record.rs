@@ -8,7 +8,7 @@ crate::frame::parameters::handler!(
         source_eui: Eui64,
         last_hop_lqi: u8,
         last_hop_rssi: i8,
-        relays: ByteSizedVec<u8>,
+        relays: ByteSizedVec<NodeId>,
     },
     impl {
         impl Handler {
@@ -36,9 +36,12 @@ crate::frame::parameters::handler!(
                 self.last_hop_rssi
             }
 
-            /// Returns the relays.
+            /// Returns the network addresses of the relays.
+            ///
+            /// The relay count of the callback counts 16 bit network addresses,
+            /// so the relays are decoded as such rather than as bytes.
             #[must_use]
-            pub fn relays(&self) -> &[u8] {
+            pub fn relays(&self) -> &[NodeId] {
                 self.relays.as_ref()
             }
         }
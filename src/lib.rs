@@ -16,7 +16,8 @@
 //! [`Builder`] orchestrates actor startup, protocol negotiation, stack
 //! configuration, network restoration or formation, endpoint registration,
 //! and callback translation. The resulting [`Ncp`] adds higher-level scan and
-//! APS messaging workflows.
+//! APS messaging workflows, as well as services such as the OTA upgrade
+//! [`OtaServer`] that answer device requests on the application's behalf.
 //!
 //! This crate does not supply an `ASHv2` implementation. An external `ASHv2`
 //! adapter implements [`Transmit`] and [`Receive`], passes both halves to
@@ -39,14 +40,25 @@ pub use self::communicate::Communicate;
 pub use self::constants::{MAX_HEADER_SIZE, MIN_NON_LEGACY_VERSION};
 pub use self::defragmentation::{Defragmented, DefragmentedMessage, Defragmenter};
 pub use self::error::{Decode, Error, Status, ValueError};
-pub use self::extensions::{ConfigurationExt, Displayable, PolicyExt};
+pub use self::extensions::{ConfigurationExt, Displayable, PolicyExt, TableWalk, TablesExt};
 pub use self::frame::{
     Callback, CallbackType, Command, Commands, Extended, FormatVersion, Frame, Header, HighByte,
     Legacy, LowByte, Parameters, Parsable, Response, SleepMode, parameters,
 };
 pub use self::ncp::{
-    BuildResult, Builder, Endpoint, EventHandler, InitializationParameters, MulticastOptions, Ncp,
-    NetworkCredentials, Scans, StackResponse, Startup,
+    AddressTable, AddressTableEntry, AttributeAddress, AttributeCache, BackupDevice, BackupLinkKey,
+    BindingManager, BuildResult, Builder, CachedAttribute, ChannelMigration, ChannelQuality,
+    ChannelSurvey, CounterSampler, CounterSnapshot, Device, DeviceDescription, DeviceRegistry,
+    DeviceStorage, Endpoint, EventHandler, FileStorage, GroupFileStorage, GroupManager,
+    GroupMembership, GroupStorage, IasZoneEvent, IasZoneResponder, InitializationParameters,
+    InstallCode, InterviewEvent, InterviewStep, Interviewer, JoinPolicy, JoinPolicyEvent,
+    JoinRejection, JoinRules, JoinWindow, JoinWindowEvent, JoinWindowTarget, KeyRotation,
+    KeyRotationEvent, LinkKey, LinkKeyManager, MfgProfile, MulticastOptions, Ncp, NcpIdentity,
+    NetworkBackup, NetworkCredentials, NvmToken, OtaEvent, OtaServer, PanIdConflictEvent,
+    PanIdResolution, ReportingEvent, ReportingManager, RouteRefresh, RouteRefreshEvent, Scans,
+    SequentialZoneIds, SourceRoute, SourceRouteTable, StackResponse, Startup, TokenBackup,
+    TokenRestore, Topology, TopologyLink, TopologyNode, TopologyRoute, WriteOnceAcknowledgement,
+    ZoneIdAllocator,
 };
 pub use self::types::SourceRouteDiscoveryMode;
 
@@ -65,6 +77,8 @@ pub mod ezsp;
 mod frame;
 mod ncp;
 mod types;
+pub mod zcl;
+pub mod zdp;
 
 /// A specialized [`std::result::Result`] type for this crate.
 pub type Result<T> = core::result::Result<T, Error>;
@@ -13,24 +13,89 @@
 //! `apis-saltans` feature, `Ncp` also implements
 //! `apis_saltans_hw::Driver` for suitable communicators and gains conversions
 //! between EZSP and `apis-saltans` endpoint, scan, APS, and event types.
+//!
+//! The event handler maintains a [`DeviceRegistry`] mapping IEEE addresses to
+//! network addresses, available through [`Ncp::devices`]. [`Ncp::backup`]
+//! exports the network as a [`NetworkBackup`] that can re-form it on another
+//! NCP, and [`Ncp::write_custom_eui64`] clones the original coordinator's
+//! [`NcpIdentity`]. [`Ncp::rotate_network_key`] replaces the network key, and
+//! [`Ncp::commission_install_code`] admits a device with its [`InstallCode`].
+//! [`Ncp::serve_join_policy`] removes devices rejected by a [`JoinPolicy`],
+//! and [`Ncp::open_join_window`] permits joining network-wide.
+//! [`Ncp::migrate_to_best_channel`] moves the network away from interference
+//! found by a [`ChannelSurvey`], and [`Ncp::serve_pan_id_conflicts`] moves it
+//! to an unused PAN ID when another network beacons with its PAN ID.
+//! [`Ncp::serve_route_refresh`] keeps many-to-one routes fresh on a
+//! [`RouteRefresh`] schedule, and [`Ncp::serve_source_routes`] records the
+//! network's source routes in a [`SourceRouteTable`].
+//! [`Ncp::sample_counters`] turns the stack counters into [`CounterSnapshot`]s
+//! that render in the Prometheus text format. The [`BindingManager`] from
+//! [`Ncp::binding_manager`] allocates and deduplicates binding table slots,
+//! and the [`GroupManager`] from [`Ncp::group_manager`] does the same for the
+//! group memberships in the multicast table. [`Ncp::unicast_to_eui64`]
+//! addresses devices by IEEE address through the [`AddressTable`] managed by
+//! [`Ncp::serve_address_table`]. The [`LinkKeyManager`] reads, stores and
+//! migrates the application link keys of the key table. [`Ncp::backup_tokens`]
+//! dumps the NVM3 tokens into a [`TokenBackup`], which
+//! [`Ncp::restore_tokens`] writes to another NCP. [`Ncp::mfg_profile`]
+//! decodes all manufacturing tokens into an [`MfgProfile`].
+//!
+//! Cluster services answer device requests on the application's behalf. The
+//! [`OtaServer`] serves Zigbee OTA upgrade images through [`Ncp::serve_ota`],
+//! and the [`IasZoneResponder`] enrolls IAS zones through
+//! [`Ncp::serve_ias_zones`]. The [`ReportingManager`] configures attribute
+//! reporting and caches reported values. [`Ncp::crawl_topology`] walks the
+//! mesh and returns its [`Topology`], and [`Ncp::serve_interviews`] describes
+//! newly joined devices with an [`Interviewer`].
 
 use std::num::NonZero;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicU8, Ordering};
 
+use le_stream::ToLeStream;
 use log::debug;
 use tokio::sync::mpsc::Sender;
 use tokio::sync::mpsc::error::SendError;
 use tokio::sync::oneshot::channel;
 
+pub use self::address_table::{AddressTable, AddressTableEntry};
+pub use self::backup::{BackupDevice, BackupLinkKey, NetworkBackup};
+pub use self::binding_manager::BindingManager;
 pub use self::builder::{BuildResult, Builder};
+pub use self::channel_survey::{ChannelMigration, ChannelQuality, ChannelSurvey};
+pub use self::counters::{CounterSampler, CounterSnapshot};
+pub use self::device_registry::{Device, DeviceRegistry, DeviceStorage, FileStorage};
 pub use self::endpoint::Endpoint;
 pub use self::event_handler::EventHandler;
+pub use self::group_manager::{GroupFileStorage, GroupManager, GroupMembership, GroupStorage};
+pub use self::ias_zone_responder::{
+    IasZoneEvent, IasZoneResponder, SequentialZoneIds, ZoneIdAllocator,
+};
+pub use self::identity::{NcpIdentity, WriteOnceAcknowledgement};
 pub use self::initialization_parameters::InitializationParameters;
+pub use self::install_code::InstallCode;
+pub use self::interview::{DeviceDescription, InterviewEvent, InterviewStep, Interviewer};
+pub use self::join_policy::{JoinPolicy, JoinPolicyEvent, JoinRejection, JoinRules};
+use self::join_window::OpenWindows;
+pub use self::join_window::{JoinWindow, JoinWindowEvent, JoinWindowTarget};
+pub use self::key_rotation::{KeyRotation, KeyRotationEvent};
+pub use self::link_key_manager::{LinkKey, LinkKeyManager};
 pub use self::message::Message;
+pub use self::mfg_profile::MfgProfile;
 pub use self::multicast_options::MulticastOptions;
 pub use self::network_credentials::NetworkCredentials;
+pub use self::ota_server::{OtaEvent, OtaServer};
+pub use self::pan_id_conflict::{PanIdConflictEvent, PanIdResolution};
+pub use self::reporting::{
+    AttributeAddress, AttributeCache, CachedAttribute, ReportingEvent, ReportingManager,
+};
+pub use self::route_refresh::{RouteRefresh, RouteRefreshEvent};
 pub use self::scans::Scans;
+pub use self::source_routes::{SourceRoute, SourceRouteTable};
 pub use self::stack_response::StackResponse;
 pub use self::startup::Startup;
+pub use self::token_backup::{NvmToken, TokenBackup, TokenRestore};
+pub use self::topology::{Topology, TopologyLink, TopologyNode, TopologyRoute};
 use crate::ember::aps::{Frame as ApsFrame, Options};
 use crate::ember::message::Destination as EmberDestination;
 use crate::ember::{Status as EmberStatus, aps};
@@ -38,22 +103,48 @@ use crate::error::Status as ErrorStatus;
 use crate::ezsp::network::scan;
 use crate::parameters::networking::handler::{EnergyScanResult, NetworkFound};
 use crate::types::ByteSizedVec;
-use crate::{Connection, Error, Messaging, Networking};
+use crate::{Connection, DefragmentedMessage, Error, Messaging, Networking, zdp};
 
+mod address_table;
 mod await_event;
+mod backup;
+mod binding_manager;
 pub mod builder;
+mod channel_survey;
+mod counters;
+mod device_registry;
 mod endpoint;
 mod event_handler;
+mod group_manager;
+mod ias_zone_responder;
+mod identity;
 mod initialization_parameters;
+mod install_code;
+mod interview;
+mod join_policy;
+mod join_window;
+mod json;
+mod key_rotation;
+mod link_key_manager;
 mod message;
+mod mfg_profile;
 mod multicast_options;
 mod network_credentials;
+mod ota_server;
+mod pan_id_conflict;
+mod reporting;
+mod route_refresh;
 mod scans;
+mod service;
+mod source_routes;
 mod stack_response;
 mod startup;
+mod token_backup;
+mod topology;
+mod zdp_response;
 
 // The ZDP profile ID.
-const ZDP: u16 = 0x0000;
+const ZDP: u16 = zdp::PROFILE_ID;
 const STACK_ASSIGNED_APS_SEQUENCE: u8 = 0;
 const FIRST_FRAGMENT_INDEX: usize = 0;
 const MAX_FRAGMENT_COUNT: usize = u8::MAX as usize;
@@ -68,21 +159,33 @@ const MAX_FRAGMENT_COUNT: usize = u8::MAX as usize;
 /// options stored by [`Builder`] with options supplied to each send method. The
 /// builder gives another clone of the connected handle to the background
 /// [`EventHandler`].
-#[derive(Debug)]
+///
+/// Clones share the event handler and the message tag and transaction
+/// sequences, so services running in separate tasks can send through their
+/// own clone without confusing each other's `messageSent` confirmations or
+/// ZCL and ZDP responses.
+#[derive(Clone, Debug)]
 pub struct Ncp {
     pub(crate) connection: Connection,
     pub(crate) endpoints: Box<[Endpoint]>,
     event_handler_handle: Sender<Message>,
     options: Options,
-    message_tag: u8,
+    message_tag: Arc<AtomicU8>,
+    transaction_sequence: Arc<AtomicU8>,
+    devices: DeviceRegistry,
+    address_table: AddressTable,
+    join_windows: OpenWindows,
 }
 
 impl Ncp {
-    /// Returns the next message tag and increments the internal counter.
-    pub(crate) const fn next_message_tag(&mut self) -> u8 {
-        let tag = self.message_tag;
-        self.message_tag = self.message_tag.wrapping_add(1);
-        tag
+    /// Returns the next message tag and increments the shared counter.
+    pub(crate) fn next_message_tag(&self) -> u8 {
+        self.message_tag.fetch_add(1, Ordering::Relaxed)
+    }
+
+    /// Returns the next ZCL or ZDP transaction sequence number and increments the shared counter.
+    pub(crate) fn next_transaction_sequence(&self) -> u8 {
+        self.transaction_sequence.fetch_add(1, Ordering::Relaxed)
     }
 
     /// Builds an outgoing EZSP APS frame from baseline and per-message options.
@@ -141,6 +244,15 @@ impl Ncp {
             .ok_or(Error::NoMatchingSourceEndpoint(cluster_id))
     }
 
+    /// Returns the registry of devices observed by the event handler.
+    ///
+    /// The registry is empty unless the `Ncp` was created by a [`Builder`],
+    /// whose event handler keeps it up to date.
+    #[must_use]
+    pub const fn devices(&self) -> &DeviceRegistry {
+        &self.devices
+    }
+
     /// Sends a termination request to the background event handler.
     ///
     /// # Errors
@@ -177,7 +289,11 @@ impl Ncp {
             endpoints,
             event_handler_handle,
             options,
-            message_tag: 0,
+            message_tag: Arc::new(AtomicU8::new(0)),
+            transaction_sequence: Arc::new(AtomicU8::new(0)),
+            devices: DeviceRegistry::default(),
+            address_table: AddressTable::default(),
+            join_windows: OpenWindows::default(),
         })
     }
 
@@ -209,9 +325,139 @@ impl Ncp {
         payload: impl AsRef<[u8]>,
         aps_options: Options,
     ) -> Result<StackResponse, Error> {
-        let payload = payload.as_ref();
         let aps_frame =
             self.aps_frame(profile_id, cluster_id, destination_endpoint, 0, aps_options)?;
+        self.unicast_aps_frame(short_id, aps_frame, payload.as_ref())
+            .await
+    }
+
+    /// Sends a unicast reply to a complete incoming APS message.
+    ///
+    /// The reply uses the profile and cluster of `message`, is sent from the
+    /// local endpoint that received it, and is addressed to the sender's source
+    /// endpoint. Unlike [`Ncp::unicast`], the source endpoint is therefore not
+    /// looked up from the registered output clusters, which lets services answer
+    /// requests for clusters the application implements as a server. Payload
+    /// fragmentation and option handling match [`Ncp::unicast`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`Error`] under the same conditions as [`Ncp::unicast`],
+    /// except that no source endpoint lookup is performed.
+    pub async fn reply(
+        &mut self,
+        message: &DefragmentedMessage,
+        payload: impl AsRef<[u8]>,
+        aps_options: Options,
+    ) -> Result<StackResponse, Error> {
+        let aps_frame = self.reply_aps_frame(message, aps_options);
+        self.unicast_aps_frame(message.sender(), aps_frame, payload.as_ref())
+            .await
+    }
+
+    /// Sends an unfragmented unicast reply without tracking its stack response.
+    ///
+    /// The reply is framed like one sent by [`Ncp::reply`], but no `messageSent`
+    /// response is registered, so its delivery status is discarded. This suits
+    /// services whose clients retry lost requests.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`Error`] if the payload is larger than the EZSP maximum APS
+    /// payload length or sending the EZSP command fails.
+    pub(crate) async fn reply_unconfirmed(
+        &mut self,
+        message: &DefragmentedMessage,
+        payload: &[u8],
+        aps_options: Options,
+    ) -> Result<(), Error> {
+        let aps_frame = self.reply_aps_frame(message, aps_options);
+        let payload = self.reject_oversized_payload(payload).await?;
+        let destination = EmberDestination::Direct(message.sender());
+        let tag = self.next_message_tag();
+
+        debug!(
+            "Sending unconfirmed unicast to: {destination:?}, APS Frame: {aps_frame}, Tag: {tag:#04X}, Message: {:#04X?}",
+            payload.as_slice()
+        );
+
+        self.connection
+            .send_unicast(destination, aps_frame, tag, payload)
+            .await?;
+        Ok(())
+    }
+
+    /// Sends a ZDP request to a device.
+    ///
+    /// The request is framed with the next transaction sequence number, which
+    /// is returned together with the [`StackResponse`] so the caller can match
+    /// the device's response.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`Error`] under the same conditions as [`Ncp::unicast`].
+    pub async fn zdp_request<T>(
+        &mut self,
+        short_id: u16,
+        request: T,
+        aps_options: Options,
+    ) -> Result<(u8, StackResponse), Error>
+    where
+        T: zdp::Command + ToLeStream,
+    {
+        let transaction_sequence = self.next_transaction_sequence();
+        let stack_response = self
+            .unicast(
+                short_id,
+                ZDP,
+                T::CLUSTER_ID,
+                0,
+                zdp::Frame::new(transaction_sequence, request).to_bytes(),
+                aps_options,
+            )
+            .await?;
+        Ok((transaction_sequence, stack_response))
+    }
+
+    /// Broadcasts a ZDP request.
+    ///
+    /// The request is framed with the next transaction sequence number, which
+    /// is returned together with the [`StackResponse`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`Error`] under the same conditions as [`Ncp::broadcast`].
+    pub async fn zdp_broadcast<T>(
+        &mut self,
+        short_id: u16,
+        radius: u8,
+        request: T,
+        aps_options: Options,
+    ) -> Result<(u8, StackResponse), Error>
+    where
+        T: zdp::Command + ToLeStream,
+    {
+        let transaction_sequence = self.next_transaction_sequence();
+        let stack_response = self
+            .broadcast(
+                short_id,
+                radius,
+                ZDP,
+                T::CLUSTER_ID,
+                0,
+                zdp::Frame::new(transaction_sequence, request).to_bytes(),
+                aps_options,
+            )
+            .await?;
+        Ok((transaction_sequence, stack_response))
+    }
+
+    async fn unicast_aps_frame(
+        &mut self,
+        short_id: u16,
+        aps_frame: ApsFrame,
+        payload: &[u8],
+    ) -> Result<StackResponse, Error> {
         let destination = EmberDestination::Direct(short_id);
         let maximum_payload_length = usize::from(self.connection.maximum_payload_length().await?);
 
@@ -406,6 +652,23 @@ impl Ncp {
         Ok((rx.into(), sequence))
     }
 
+    const fn reply_aps_frame(
+        &self,
+        message: &DefragmentedMessage,
+        aps_options: Options,
+    ) -> ApsFrame {
+        let incoming = message.aps_frame();
+        aps::Frame::new(
+            incoming.profile_id(),
+            incoming.cluster_id(),
+            incoming.destination_endpoint(),
+            incoming.source_endpoint(),
+            self.options.union(aps_options),
+            0,
+            STACK_ASSIGNED_APS_SEQUENCE,
+        )
+    }
+
     async fn reject_oversized_payload(
         &mut self,
         payload: &[u8],
@@ -0,0 +1,438 @@
+//! Host-managed address table and EUI64-addressed sends.
+
+use std::collections::BTreeMap;
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::time::Duration;
+
+use log::{debug, trace, warn};
+use tokio::sync::mpsc::{Receiver, channel};
+use tokio::time::{Instant, timeout_at};
+
+pub use self::address_table_entry::AddressTableEntry;
+use crate::ember::aps::Options;
+use crate::ember::constants::NULL_NODE_ID;
+use crate::ember::{Eui64, NodeId};
+use crate::ezsp::config;
+use crate::frame::parameters::messaging::handler::Handler as MessagingHandler;
+use crate::ncp::service::{MESSAGES_CAPACITY, lock};
+use crate::ncp::{Message, Ncp, StackResponse};
+use crate::zdp::{self, NwkAddrRequest, NwkAddrResponse};
+use crate::{Callback, Configuration, Error, Messaging, TablesExt};
+
+mod address_table_entry;
+
+/// Broadcast address of all devices whose receiver is on when idle.
+const RX_ON_WHEN_IDLE: NodeId = 0xFFFD;
+/// Time to wait for a NWK Address Response.
+const RESOLVE_TIMEOUT: Duration = Duration::from_secs(5);
+/// Network status code of an address conflict.
+const ADDRESS_CONFLICT: u8 = 0x0D;
+/// Network status code of a network address update.
+const NETWORK_ADDRESS_UPDATE: u8 = 0x10;
+
+/// Address table entries of the NCP, keyed by table index.
+///
+/// [`Ncp::unicast_to_eui64`] stores every address it resolves in a free slot
+/// of the NCP's address table, replacing the least recently used entry if the
+/// table is full. [`Ncp::serve_address_table`] loads the table, frees entries
+/// that were not used for a while and invalidates addresses reported by
+/// address conflicts or changes. Access the table of a running NCP with
+/// [`Ncp::address_table`]. Clones share the same entries.
+#[derive(Clone, Debug, Default)]
+pub struct AddressTable {
+    state: Arc<Mutex<State>>,
+}
+
+impl AddressTable {
+    /// Returns the configured size of the address table.
+    ///
+    /// The size is zero until [`Ncp::serve_address_table`] loaded the table.
+    #[must_use]
+    pub fn size(&self) -> u8 {
+        self.lock().size
+    }
+
+    /// Returns the entry of the device with the given IEEE address.
+    #[must_use]
+    pub fn get(&self, ieee_address: Eui64) -> Option<AddressTableEntry> {
+        let state = self.lock();
+        state
+            .find(ieee_address)
+            .and_then(|index| state.entries.get(&index))
+            .copied()
+    }
+
+    /// Returns all entries keyed by their table index.
+    #[must_use]
+    pub fn entries(&self) -> BTreeMap<u8, AddressTableEntry> {
+        self.lock().entries.clone()
+    }
+
+    /// Returns the number of entries.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.lock().entries.len()
+    }
+
+    /// Returns whether the table contains no entries.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.lock().entries.is_empty()
+    }
+
+    fn lock(&self) -> MutexGuard<'_, State> {
+        lock(&self.state)
+    }
+}
+
+impl Ncp {
+    /// Returns the address table used by [`Ncp::unicast_to_eui64`].
+    #[must_use]
+    pub fn address_table(&self) -> AddressTable {
+        self.address_table.clone()
+    }
+
+    /// Starts managing the NCP's address table.
+    ///
+    /// The active entries of the NCP's address table are loaded into
+    /// [`Ncp::address_table`]. The event handler copies all callbacks to the
+    /// returned future, which invalidates the addresses named by `idConflict`
+    /// callbacks and by `incomingNetworkStatus` callbacks reporting an address
+    /// conflict or update, so they are resolved again before the next send.
+    /// Entries not used within `max_age` are freed. The future completes when
+    /// the event handler stops.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`Error`] if the size or the entries of the address table
+    /// cannot be read, or the callback subscription cannot be registered with
+    /// the event handler.
+    pub async fn serve_address_table(
+        &mut self,
+        max_age: Duration,
+    ) -> Result<impl Future<Output = ()> + Send + 'static, Error> {
+        let size = self
+            .connection
+            .get_configuration_value(config::Id::AddressTableSize)
+            .await?;
+        let now = Instant::now();
+        let entries = self
+            .connection
+            .address_table()
+            .await?
+            .collect()
+            .await?
+            .into_iter()
+            .map(|(index, (ieee_address, node_id))| {
+                (index, AddressTableEntry::new(ieee_address, node_id, now))
+            })
+            .collect::<BTreeMap<_, _>>();
+
+        let size = u8::try_from(size).unwrap_or(u8::MAX);
+        debug!("Loaded {} of {size} address table entries.", entries.len());
+        *self.address_table.lock() = State { size, entries };
+
+        let (sender, callbacks) = channel(MESSAGES_CAPACITY);
+        self.event_handler_handle
+            .send(Message::Callbacks { sender })
+            .await?;
+
+        let session = Session {
+            ncp: self.clone(),
+            max_age,
+        };
+
+        Ok(session.run(callbacks))
+    }
+
+    /// Starts a unicast APS send to the device with the given IEEE address.
+    ///
+    /// The network address is taken from [`Ncp::address_table`]. Unknown or
+    /// invalidated addresses are looked up with
+    /// [`Messaging::lookup_node_id_by_eui64`], and if the NCP does not know
+    /// the device either, resolved with a broadcast NWK Address Request. Resolved
+    /// addresses are stored in the address table once it was loaded by
+    /// [`Ncp::serve_address_table`]. Sending then proceeds as with
+    /// [`Ncp::unicast`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`Error`] if the network address cannot be resolved, or
+    /// sending fails as for [`Ncp::unicast`].
+    pub async fn unicast_to_eui64(
+        &mut self,
+        ieee_address: Eui64,
+        profile_id: u16,
+        cluster_id: u16,
+        destination_endpoint: u8,
+        payload: impl AsRef<[u8]>,
+        aps_options: Options,
+    ) -> Result<StackResponse, Error> {
+        let node_id = self.resolve(ieee_address).await?;
+        self.unicast(
+            node_id,
+            profile_id,
+            cluster_id,
+            destination_endpoint,
+            payload,
+            aps_options,
+        )
+        .await
+    }
+
+    /// Returns the network address of the device with the given IEEE address.
+    ///
+    /// Invalidated entries skip the NCP's lookup, since the NCP still knows the
+    /// outdated address.
+    async fn resolve(&mut self, ieee_address: Eui64) -> Result<NodeId, Error> {
+        let invalidated = {
+            let mut state = self.address_table.lock();
+
+            match state.find_mut(ieee_address) {
+                Some(entry) => {
+                    if let Some(node_id) = entry.node_id() {
+                        entry.touch(Instant::now());
+                        return Ok(node_id);
+                    }
+
+                    true
+                }
+                None => false,
+            }
+        };
+
+        let mut node_id = if invalidated {
+            NULL_NODE_ID
+        } else {
+            self.connection
+                .lookup_node_id_by_eui64(ieee_address)
+                .await?
+        };
+
+        if node_id == NULL_NODE_ID {
+            debug!("Resolving network address of {ieee_address}.");
+            node_id = self.nwk_addr(ieee_address).await?;
+        }
+
+        self.store(ieee_address, node_id).await;
+        Ok(node_id)
+    }
+
+    /// Broadcasts a NWK Address Request and waits for the device's response.
+    async fn nwk_addr(&mut self, ieee_address: Eui64) -> Result<NodeId, Error> {
+        let response = self
+            .zdp_broadcast_response(
+                RX_ON_WHEN_IDLE,
+                NwkAddrRequest::new(ieee_address),
+                RESOLVE_TIMEOUT,
+                |response: &NwkAddrResponse| response.ieee_address() == ieee_address,
+            )
+            .await?;
+
+        match response {
+            Some(response) if response.status() == Ok(zdp::Status::Success) => {
+                Ok(response.node_id())
+            }
+            Some(_) => Err(Error::AddressNotResolved(ieee_address)),
+            None => {
+                warn!("No response to NWK Address Request for {ieee_address}.");
+                Err(Error::AddressNotResolved(ieee_address))
+            }
+        }
+    }
+
+    /// Stores a resolved address in the NCP's address table.
+    async fn store(&mut self, ieee_address: Eui64, node_id: NodeId) {
+        let Some(index) = self
+            .address_table
+            .lock()
+            .allocate(ieee_address, node_id, Instant::now())
+        else {
+            return;
+        };
+
+        trace!("Storing {ieee_address} as {node_id:#06X} at address table index {index}.");
+
+        if let Err(error) = self
+            .connection
+            .replace_address_table_entry(index, ieee_address, node_id, false)
+            .await
+        {
+            warn!("Failed to write address table entry {index}: {error}");
+            self.address_table.lock().entries.remove(&index);
+        }
+    }
+}
+
+#[derive(Debug, Default)]
+struct State {
+    size: u8,
+    entries: BTreeMap<u8, AddressTableEntry>,
+}
+
+impl State {
+    fn find(&self, ieee_address: Eui64) -> Option<u8> {
+        self.entries
+            .iter()
+            .find_map(|(&index, entry)| (entry.ieee_address() == ieee_address).then_some(index))
+    }
+
+    fn find_mut(&mut self, ieee_address: Eui64) -> Option<&mut AddressTableEntry> {
+        self.entries
+            .values_mut()
+            .find(|entry| entry.ieee_address() == ieee_address)
+    }
+
+    /// Assigns a table index to an address and returns it.
+    ///
+    /// The device's own entry is reused, then a free slot, and finally the
+    /// least recently used entry. Returns `None` if the table has no slots.
+    fn allocate(&mut self, ieee_address: Eui64, node_id: NodeId, now: Instant) -> Option<u8> {
+        let index = self
+            .find(ieee_address)
+            .or_else(|| (0..self.size).find(|index| !self.entries.contains_key(index)))
+            .or_else(|| {
+                self.entries
+                    .iter()
+                    .min_by_key(|(_, entry)| entry.last_used())
+                    .map(|(&index, _)| index)
+            })?;
+        self.entries
+            .insert(index, AddressTableEntry::new(ieee_address, node_id, now));
+        Some(index)
+    }
+
+    /// Invalidates the entries using `node_id` and returns their number.
+    fn invalidate(&mut self, node_id: NodeId) -> usize {
+        let mut invalidated = 0;
+
+        for entry in self.entries.values_mut() {
+            if entry.node_id() == Some(node_id) {
+                entry.invalidate();
+                invalidated += 1;
+            }
+        }
+
+        invalidated
+    }
+
+    /// Removes the entries last used before `threshold` and returns their indices.
+    fn expire(&mut self, threshold: Instant) -> Vec<u8> {
+        let expired: Vec<u8> = self
+            .entries
+            .iter()
+            .filter(|(_, entry)| entry.last_used() < threshold)
+            .map(|(&index, _)| index)
+            .collect();
+
+        for index in &expired {
+            self.entries.remove(index);
+        }
+
+        expired
+    }
+}
+
+struct Session {
+    ncp: Ncp,
+    max_age: Duration,
+}
+
+impl Session {
+    async fn run(mut self, mut callbacks: Receiver<Callback>) {
+        let mut next_check = Instant::now() + self.max_age;
+
+        loop {
+            match timeout_at(next_check, callbacks.recv()).await {
+                Ok(Some(Callback::Messaging(messaging))) => self.invalidate(&messaging),
+                Ok(Some(_)) => {}
+                Ok(None) => break,
+                Err(_) => {
+                    self.expire().await;
+                    next_check = Instant::now() + self.max_age;
+                }
+            }
+        }
+
+        debug!("Callback subscription closed. Address table management terminating.");
+    }
+
+    fn invalidate(&self, messaging: &MessagingHandler) {
+        let node_id = match messaging {
+            MessagingHandler::IdConflict(conflict) => conflict.id(),
+            MessagingHandler::IncomingNetworkStatus(status)
+                if matches!(
+                    status.error_code(),
+                    ADDRESS_CONFLICT | NETWORK_ADDRESS_UPDATE
+                ) =>
+            {
+                status.target()
+            }
+            _ => return,
+        };
+
+        let invalidated = self.ncp.address_table.lock().invalidate(node_id);
+
+        if invalidated > 0 {
+            debug!("Invalidated {invalidated} address table entries using {node_id:#06X}.");
+        }
+    }
+
+    async fn expire(&mut self) {
+        let Some(threshold) = Instant::now().checked_sub(self.max_age) else {
+            return;
+        };
+        let expired = self.ncp.address_table.lock().expire(threshold);
+
+        for index in expired {
+            trace!("Freeing idle address table entry {index}.");
+
+            if let Err(error) = self
+                .ncp
+                .connection
+                .replace_address_table_entry(index, Eui64::nil(), NULL_NODE_ID, false)
+                .await
+            {
+                warn!("Failed to free address table entry {index}: {error}");
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use tokio::time::Instant;
+
+    use super::State;
+    use crate::ember::Eui64;
+
+    #[test]
+    fn full_table_replaces_least_recently_used_entry() {
+        let now = Instant::now();
+        let first = Eui64::new(0, 0, 0, 0, 0, 0, 0, 1);
+        let second = Eui64::new(0, 0, 0, 0, 0, 0, 0, 2);
+        let third = Eui64::new(0, 0, 0, 0, 0, 0, 0, 3);
+        let mut state = State {
+            size: 2,
+            ..State::default()
+        };
+
+        assert_eq!(state.allocate(first, 0x1111, now), Some(0));
+        assert_eq!(
+            state.allocate(second, 0x2222, now + Duration::from_secs(1)),
+            Some(1)
+        );
+        assert_eq!(state.invalidate(0x2222), 1);
+        assert_eq!(
+            state.allocate(second, 0x3333, now + Duration::from_secs(2)),
+            Some(1)
+        );
+        assert_eq!(
+            state.allocate(third, 0x4444, now + Duration::from_secs(3)),
+            Some(0)
+        );
+        assert_eq!(state.expire(now + Duration::from_secs(3)), [1]);
+    }
+}
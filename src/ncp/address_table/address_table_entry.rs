@@ -0,0 +1,50 @@
+use tokio::time::Instant;
+
+use crate::ember::{Eui64, NodeId};
+
+/// An entry of the [`AddressTable`](crate::AddressTable).
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct AddressTableEntry {
+    ieee_address: Eui64,
+    node_id: Option<NodeId>,
+    last_used: Instant,
+}
+
+impl AddressTableEntry {
+    pub(crate) const fn new(ieee_address: Eui64, node_id: NodeId, last_used: Instant) -> Self {
+        Self {
+            ieee_address,
+            node_id: Some(node_id),
+            last_used,
+        }
+    }
+
+    /// Returns the IEEE address of the device.
+    #[must_use]
+    pub const fn ieee_address(&self) -> Eui64 {
+        self.ieee_address
+    }
+
+    /// Returns the network address of the device.
+    ///
+    /// Returns `None` if the address was invalidated by an address conflict or
+    /// change and has to be resolved again.
+    #[must_use]
+    pub const fn node_id(&self) -> Option<NodeId> {
+        self.node_id
+    }
+
+    /// Returns when the entry was last used to send a message.
+    #[must_use]
+    pub const fn last_used(&self) -> Instant {
+        self.last_used
+    }
+
+    pub(crate) const fn invalidate(&mut self) {
+        self.node_id = None;
+    }
+
+    pub(crate) const fn touch(&mut self, now: Instant) {
+        self.last_used = now;
+    }
+}
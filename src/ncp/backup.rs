@@ -0,0 +1,667 @@
+//! Network backup and restore in the open coordinator backup format.
+
+use std::collections::BTreeMap;
+use std::fmt::{self, Display, Formatter};
+use std::io;
+use std::time::SystemTime;
+
+use log::{debug, info};
+use silizium::zigbee::security::man::{Context, DerivedKeyType, Flags, Key, KeyType};
+
+pub use self::device::BackupDevice;
+pub use self::link_key::BackupLinkKey;
+use crate::ember::join::Method;
+use crate::ember::security::initial::Bitmask;
+use crate::ember::{Eui64, NodeId, PanId};
+use crate::ncp::json::{Json, invalid, separator};
+use crate::ncp::{Device, Ncp};
+use crate::{
+    Error, InitializationParameters, NetworkCredentials, Networking, Security, TablesExt, Utilities,
+};
+
+mod device;
+mod link_key;
+
+const FORMAT: &str = "zigpy/open-coordinator-backup";
+const VERSION: u64 = 1;
+const SOURCE: &str = concat!("ezsp@", env!("CARGO_PKG_VERSION"));
+/// Zigbee security level 5: AES-CCM-32 with encryption.
+const SECURITY_LEVEL: u8 = 5;
+/// The Zigbee 3.0 well-known trust-center link key `ZigBeeAlliance09`.
+const DEFAULT_TC_LINK_KEY: Key = *b"ZigBeeAlliance09";
+const CHANNELS: std::ops::RangeInclusive<u8> = 11..=26;
+const COORDINATOR: NodeId = 0x0000;
+
+/// A coordinator backup in the open coordinator backup format.
+///
+/// The backup holds everything needed to re-form the same network on another
+/// NCP: the network identifiers, the network key with its sequence number and
+/// outgoing frame counter, the trust-center link key, the coordinator's IEEE
+/// address, and the known devices with their application link keys. It is
+/// written and read as the JSON document other Zigbee hosts exchange, so
+/// backups can be moved between host implementations.
+///
+/// Create a backup with [`Ncp::backup`]. To restore it on a fresh NCP, start
+/// a [`Builder`](crate::Builder) with
+/// [`Startup::Initialize`](crate::Startup::Initialize) and the parameters
+/// returned by [`initialization_parameters`](Self::initialization_parameters),
+/// then call [`Ncp::restore_devices`].
+///
+/// This type contains secret key material. Avoid logging its [`Debug`] output,
+/// and store backups using protections appropriate for network credentials.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct NetworkBackup {
+    coordinator_ieee: Eui64,
+    pan_id: PanId,
+    extended_pan_id: Eui64,
+    nwk_update_id: u8,
+    nwk_manager_id: NodeId,
+    channel: u8,
+    channel_mask: u32,
+    network_key: Key,
+    network_key_sequence_number: u8,
+    network_key_frame_counter: u32,
+    tc_link_key: Key,
+    tc_link_key_frame_counter: u32,
+    devices: Vec<BackupDevice>,
+}
+
+impl NetworkBackup {
+    /// Returns the coordinator's IEEE address.
+    #[must_use]
+    pub const fn coordinator_ieee(&self) -> Eui64 {
+        self.coordinator_ieee
+    }
+
+    /// Returns the PAN ID.
+    #[must_use]
+    pub const fn pan_id(&self) -> PanId {
+        self.pan_id
+    }
+
+    /// Returns the extended PAN ID.
+    #[must_use]
+    pub const fn extended_pan_id(&self) -> Eui64 {
+        self.extended_pan_id
+    }
+
+    /// Returns the network update ID.
+    #[must_use]
+    pub const fn nwk_update_id(&self) -> u8 {
+        self.nwk_update_id
+    }
+
+    /// Returns the network address of the network manager.
+    #[must_use]
+    pub const fn nwk_manager_id(&self) -> NodeId {
+        self.nwk_manager_id
+    }
+
+    /// Returns the radio channel.
+    #[must_use]
+    pub const fn channel(&self) -> u8 {
+        self.channel
+    }
+
+    /// Returns the channel mask.
+    #[must_use]
+    pub const fn channel_mask(&self) -> u32 {
+        self.channel_mask
+    }
+
+    /// Returns the network key.
+    #[must_use]
+    pub const fn network_key(&self) -> &Key {
+        &self.network_key
+    }
+
+    /// Returns the network key sequence number.
+    #[must_use]
+    pub const fn network_key_sequence_number(&self) -> u8 {
+        self.network_key_sequence_number
+    }
+
+    /// Returns the outgoing network key frame counter at the time of the backup.
+    #[must_use]
+    pub const fn network_key_frame_counter(&self) -> u32 {
+        self.network_key_frame_counter
+    }
+
+    /// Returns the trust-center link key.
+    #[must_use]
+    pub const fn tc_link_key(&self) -> &Key {
+        &self.tc_link_key
+    }
+
+    /// Returns the outgoing trust-center link key frame counter at the time of the backup.
+    #[must_use]
+    pub const fn tc_link_key_frame_counter(&self) -> u32 {
+        self.tc_link_key_frame_counter
+    }
+
+    /// Returns the devices ordered by IEEE address.
+    #[must_use]
+    pub fn devices(&self) -> &[BackupDevice] {
+        &self.devices
+    }
+
+    /// Returns the credentials of the backed up network.
+    #[must_use]
+    pub const fn credentials(&self) -> NetworkCredentials {
+        NetworkCredentials::new(
+            self.extended_pan_id,
+            self.pan_id,
+            self.coordinator_ieee,
+            self.network_key,
+        )
+    }
+
+    /// Returns the parameters forming the backed up network on a fresh NCP.
+    ///
+    /// The outgoing NWK and APS frame counters are set `frame_counter_increment`
+    /// above the counters recorded in the backup, so devices accept frames of
+    /// the restored coordinator even if it sent further frames after the
+    /// backup was taken. `bitmask` is combined with the security options
+    /// required for forming the network, as for [`InitializationParameters::new`].
+    #[must_use]
+    pub const fn initialization_parameters(
+        &self,
+        bitmask: Bitmask,
+        frame_counter_increment: u32,
+    ) -> InitializationParameters {
+        InitializationParameters::new(
+            self.credentials(),
+            self.tc_link_key,
+            self.channel,
+            Method::MacAssociation,
+            bitmask,
+        )
+        .with_nwk_update_id(self.nwk_update_id)
+        .with_network_key_sequence_number(self.network_key_sequence_number)
+        .with_frame_counters(
+            self.network_key_frame_counter
+                .saturating_add(frame_counter_increment),
+            self.tc_link_key_frame_counter
+                .saturating_add(frame_counter_increment),
+        )
+    }
+
+    /// Renders the backup as an open coordinator backup JSON document.
+    #[must_use]
+    pub fn to_json(&self) -> String {
+        OpenCoordinatorJson(self).to_string()
+    }
+
+    /// Parses an open coordinator backup JSON document.
+    ///
+    /// Missing trust-center link key metadata defaults to the well-known
+    /// Zigbee 3.0 link key with a frame counter of zero.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`io::Error`] of kind [`InvalidData`](io::ErrorKind::InvalidData)
+    /// if the document is not valid JSON, has an unsupported format or
+    /// version, or lacks a required field.
+    pub fn from_json(json: &str) -> io::Result<Self> {
+        let document = Json::parse(json)?;
+        let metadata = document
+            .get("metadata")
+            .ok_or_else(|| invalid("missing field `metadata`"))?;
+
+        if metadata.get("format").and_then(Json::as_str) != Some(FORMAT) {
+            return Err(invalid("unsupported backup format"));
+        }
+
+        if metadata.get("version").and_then(Json::as_u64) != Some(VERSION) {
+            return Err(invalid("unsupported backup version"));
+        }
+
+        let network = metadata
+            .get("internal")
+            .and_then(|internal| internal.get("network"));
+        let tc_link_key = network.and_then(|network| network.get("tc_link_key"));
+        let network_key = field(&document, "network_key")?;
+        let mut devices = field(&document, "devices")?
+            .as_array()
+            .ok_or_else(|| invalid("invalid field `devices`"))?
+            .iter()
+            .map(parse_device)
+            .collect::<io::Result<Vec<_>>>()?;
+        devices.sort_by_key(BackupDevice::ieee_address);
+
+        Ok(Self {
+            coordinator_ieee: eui64(&document, "coordinator_ieee")?,
+            pan_id: hex_u16(&document, "pan_id")?,
+            extended_pan_id: eui64(&document, "extended_pan_id")?,
+            nwk_update_id: integer(&document, "nwk_update_id")?,
+            nwk_manager_id: network
+                .map(|network| hex_u16(network, "nwk_manager_id"))
+                .transpose()?
+                .unwrap_or(COORDINATOR),
+            channel: integer(&document, "channel")?,
+            channel_mask: field(&document, "channel_mask")?
+                .as_array()
+                .ok_or_else(|| invalid("invalid field `channel_mask`"))?
+                .iter()
+                .try_fold(0, |mask, channel| {
+                    channel
+                        .as_u64()
+                        .filter(|channel| (0..32).contains(channel))
+                        .map(|channel| mask | 1 << channel)
+                        .ok_or_else(|| invalid("invalid channel in `channel_mask`"))
+                })?,
+            network_key: key(network_key, "key")?,
+            network_key_sequence_number: integer(network_key, "sequence_number")?,
+            network_key_frame_counter: integer(network_key, "frame_counter")?,
+            tc_link_key: tc_link_key
+                .map(|tc_link_key| key(tc_link_key, "key"))
+                .transpose()?
+                .unwrap_or(DEFAULT_TC_LINK_KEY),
+            tc_link_key_frame_counter: tc_link_key
+                .map(|tc_link_key| integer(tc_link_key, "frame_counter"))
+                .transpose()?
+                .unwrap_or_default(),
+            devices,
+        })
+    }
+}
+
+impl Ncp {
+    /// Reads a [`NetworkBackup`] of the current network from the NCP.
+    ///
+    /// The backup contains the network parameters, the network key and its
+    /// frame counter, the trust-center link key, the NCP's EUI64, and all
+    /// devices known from the [`DeviceRegistry`](crate::DeviceRegistry), the
+    /// child table, and the key table. Application link keys are exported from
+    /// the key table.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`Error`] if the network parameters, keys, or tables cannot
+    /// be read.
+    pub async fn backup(&mut self) -> Result<NetworkBackup, Error> {
+        let coordinator_ieee = self.connection.get_eui64().await?;
+        let (_, parameters) = self.connection.get_network_parameters().await?;
+        let network_key = self
+            .connection
+            .export_key(context(KeyType::Network))
+            .await?;
+        let network_key_info = self.connection.get_network_key_info().await?;
+        let tc_link_key = self.connection.export_key(context(KeyType::TcLink)).await?;
+        let tc_link_key_info = self
+            .connection
+            .get_aps_key_info(context(KeyType::TcLink))
+            .await?;
+        let mut devices = BTreeMap::new();
+
+        for device in self.devices.devices() {
+            let entry = devices
+                .entry(device.ieee_address())
+                .or_insert_with(|| BackupDevice::new(device.ieee_address(), None, false));
+
+            if let Some(node_id) = device.node_id() {
+                entry.set_node_id(node_id);
+            }
+        }
+
+        for child in self
+            .connection
+            .child_table()
+            .await?
+            .collect()
+            .await?
+            .into_values()
+        {
+            let entry = devices
+                .entry(child.eui64())
+                .or_insert_with(|| BackupDevice::new(child.eui64(), None, true));
+            entry.set_node_id(child.id());
+            entry.set_child();
+        }
+
+        for entry in self
+            .connection
+            .key_table()
+            .await?
+            .collect()
+            .await?
+            .into_values()
+        {
+            devices
+                .entry(entry.eui())
+                .or_insert_with(|| BackupDevice::new(entry.eui(), None, false))
+                .set_link_key(BackupLinkKey::new(
+                    *entry.plaintext_key(),
+                    entry.key_data().outgoing_frame_counter(),
+                    entry.key_data().incoming_frame_counter(),
+                ));
+        }
+
+        info!("Backed up network with {} devices.", devices.len());
+        Ok(NetworkBackup {
+            coordinator_ieee,
+            pan_id: parameters.pan_id(),
+            extended_pan_id: parameters.extended_pan_id(),
+            nwk_update_id: parameters.nwk_update_id(),
+            nwk_manager_id: parameters.nwk_manager_id(),
+            channel: parameters.radio_channel(),
+            channel_mask: parameters.channels(),
+            network_key,
+            network_key_sequence_number: network_key_info.network_key_sequence_number(),
+            network_key_frame_counter: network_key_info.network_key_frame_counter(),
+            tc_link_key,
+            tc_link_key_frame_counter: tc_link_key_info.key_data().outgoing_frame_counter(),
+            devices: devices.into_values().collect(),
+        })
+    }
+
+    /// Restores the devices of a [`NetworkBackup`] on a re-formed network.
+    ///
+    /// Call this once the network was formed from the backup's
+    /// [`initialization_parameters`](NetworkBackup::initialization_parameters).
+    /// Application link keys are stored with
+    /// [`LinkKeyManager::insert`](crate::LinkKeyManager::insert), which
+    /// replaces a device's existing key and otherwise takes a free slot of the
+    /// key table. Devices unknown to the
+    /// [`DeviceRegistry`](crate::DeviceRegistry) are added to it.
+    ///
+    /// The per-key frame counters of the backup's link keys are deliberately
+    /// not restored: unlike the NWK and trust-center APS counters, which
+    /// [`initialization_parameters`](NetworkBackup::initialization_parameters)
+    /// writes with `setValue`, no EZSP command sets the counters of a key
+    /// table entry, and `importLinkKey` starts them at zero. Devices that keep
+    /// their incoming counter may therefore reject APS-encrypted frames until
+    /// they rejoin or get a new key.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`Error`] with [`Status::TableFull`](crate::ember::Status::TableFull)
+    /// if the key table has no free slot, or if a link key cannot be imported.
+    pub async fn restore_devices(&mut self, backup: &NetworkBackup) -> Result<(), Error> {
+        let mut link_keys = self.link_key_manager();
+
+        for device in backup.devices() {
+            if let Some(link_key) = device.link_key() {
+                debug!("Importing link key of {}", device.ieee_address());
+                link_keys
+                    .insert(device.ieee_address(), *link_key.key())
+                    .await?;
+            }
+
+            if self.devices.device(device.ieee_address()).is_none() {
+                let mut restored =
+                    Device::new(device.ieee_address(), device.node_id(), SystemTime::now());
+
+                if device.is_child() {
+                    restored = restored.with_parent(COORDINATOR);
+                }
+
+                self.devices.insert(restored);
+            }
+        }
+
+        info!("Restored {} devices.", backup.devices().len());
+        Ok(())
+    }
+}
+
+/// Returns a security manager context for the key of the given type.
+const fn context(key_type: KeyType) -> Context {
+    Context::new(
+        key_type,
+        0,
+        DerivedKeyType::None,
+        Eui64::new(0, 0, 0, 0, 0, 0, 0, 0),
+        0,
+        Flags::NONE,
+        0,
+    )
+}
+
+struct OpenCoordinatorJson<'a>(&'a NetworkBackup);
+
+impl Display for OpenCoordinatorJson<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let backup = self.0;
+        write!(
+            f,
+            "{{\"metadata\":{{\"version\":{VERSION},\"format\":\"{FORMAT}\",\"source\":\"{SOURCE}\",\
+             \"internal\":{{\"network\":{{\"tc_link_key\":{{\"key\":\"{}\",\"frame_counter\":{}}},\
+             \"tc_address\":\"{}\",\"nwk_manager_id\":\"{:04x}\"}}}}}},\"stack_specific\":{{}},",
+            Hex(&backup.tc_link_key),
+            backup.tc_link_key_frame_counter,
+            Hex(backup.coordinator_ieee.as_bytes()),
+            backup.nwk_manager_id,
+        )?;
+        write!(
+            f,
+            "\"coordinator_ieee\":\"{}\",\"pan_id\":\"{:04x}\",\"extended_pan_id\":\"{}\",\
+             \"nwk_update_id\":{},\"security_level\":{SECURITY_LEVEL},\"channel\":{},\"channel_mask\":[",
+            Hex(backup.coordinator_ieee.as_bytes()),
+            backup.pan_id,
+            Hex(backup.extended_pan_id.as_bytes()),
+            backup.nwk_update_id,
+            backup.channel,
+        )?;
+
+        for (index, channel) in CHANNELS
+            .filter(|channel| backup.channel_mask & (1 << channel) != 0)
+            .enumerate()
+        {
+            separator(f, index)?;
+            write!(f, "{channel}")?;
+        }
+
+        write!(
+            f,
+            "],\"network_key\":{{\"key\":\"{}\",\"sequence_number\":{},\"frame_counter\":{}}},\"devices\":[",
+            Hex(&backup.network_key),
+            backup.network_key_sequence_number,
+            backup.network_key_frame_counter,
+        )?;
+
+        for (index, device) in backup.devices.iter().enumerate() {
+            separator(f, index)?;
+            write!(f, "{{\"nwk_address\":")?;
+
+            match device.node_id() {
+                Some(node_id) => write!(f, "\"{node_id:04x}\"")?,
+                None => write!(f, "null")?,
+            }
+
+            write!(
+                f,
+                ",\"ieee_address\":\"{}\",\"is_child\":{}",
+                Hex(device.ieee_address().as_bytes()),
+                device.is_child()
+            )?;
+
+            if let Some(link_key) = device.link_key() {
+                write!(
+                    f,
+                    ",\"link_key\":{{\"key\":\"{}\",\"tx_counter\":{},\"rx_counter\":{}}}",
+                    Hex(link_key.key()),
+                    link_key.tx_counter(),
+                    link_key.rx_counter()
+                )?;
+            }
+
+            write!(f, "}}")?;
+        }
+
+        write!(f, "]}}")
+    }
+}
+
+struct Hex<'a>(&'a [u8]);
+
+impl Display for Hex<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        self.0.iter().try_for_each(|byte| write!(f, "{byte:02x}"))
+    }
+}
+
+fn parse_device(device: &Json) -> io::Result<BackupDevice> {
+    let node_id = match field(device, "nwk_address")? {
+        Json::Null => None,
+        _ => Some(hex_u16(device, "nwk_address")?),
+    };
+    let is_child = field(device, "is_child")?
+        .as_bool()
+        .ok_or_else(|| invalid("invalid field `is_child`"))?;
+    let parsed = BackupDevice::new(eui64(device, "ieee_address")?, node_id, is_child);
+
+    match device.get("link_key") {
+        None | Some(Json::Null) => Ok(parsed),
+        Some(link_key) => Ok(parsed.with_link_key(BackupLinkKey::new(
+            key(link_key, "key")?,
+            integer(link_key, "tx_counter")?,
+            integer(link_key, "rx_counter")?,
+        ))),
+    }
+}
+
+fn field<'a>(object: &'a Json, name: &str) -> io::Result<&'a Json> {
+    object
+        .get(name)
+        .ok_or_else(|| invalid(format!("missing field `{name}`")))
+}
+
+fn integer<T>(object: &Json, name: &str) -> io::Result<T>
+where
+    T: TryFrom<u64>,
+{
+    field(object, name)?
+        .as_u64()
+        .and_then(|value| T::try_from(value).ok())
+        .ok_or_else(|| invalid(format!("invalid field `{name}`")))
+}
+
+fn hex<const SIZE: usize>(object: &Json, name: &str) -> io::Result<[u8; SIZE]> {
+    let error = || invalid(format!("invalid field `{name}`"));
+    let digits = field(object, name)?.as_str().ok_or_else(error)?;
+
+    if digits.len() != SIZE * 2 || !digits.is_ascii() {
+        return Err(error());
+    }
+
+    let mut bytes = [0; SIZE];
+
+    for (byte, pair) in bytes.iter_mut().zip(digits.as_bytes().chunks_exact(2)) {
+        *byte = std::str::from_utf8(pair)
+            .ok()
+            .and_then(|pair| u8::from_str_radix(pair, 16).ok())
+            .ok_or_else(error)?;
+    }
+
+    Ok(bytes)
+}
+
+fn hex_u16(object: &Json, name: &str) -> io::Result<u16> {
+    hex(object, name).map(u16::from_be_bytes)
+}
+
+fn eui64(object: &Json, name: &str) -> io::Result<Eui64> {
+    hex::<8>(object, name).map(Eui64::from)
+}
+
+fn key(object: &Json, name: &str) -> io::Result<Key> {
+    hex(object, name)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{BackupDevice, BackupLinkKey, NetworkBackup};
+    use crate::ember::Eui64;
+    use crate::ember::security::initial::Bitmask;
+
+    const BACKUP: &str = r#"{
+        "metadata": {
+            "version": 1,
+            "format": "zigpy/open-coordinator-backup",
+            "source": "bellows@0.40.0",
+            "internal": {}
+        },
+        "stack_specific": {"ezsp": {"hashed_tclk": "00112233445566778899aabbccddeeff"}},
+        "coordinator_ieee": "00124b0001020304",
+        "pan_id": "1a2b",
+        "extended_pan_id": "dddddddddddddddd",
+        "nwk_update_id": 2,
+        "security_level": 5,
+        "channel": 15,
+        "channel_mask": [11, 15, 20],
+        "network_key": {
+            "key": "01030507090b0d0f00020406080a0c0d",
+            "sequence_number": 1,
+            "frame_counter": 123456
+        },
+        "devices": [
+            {
+                "nwk_address": "4567",
+                "ieee_address": "ccccccccccccccc1",
+                "is_child": false,
+                "link_key": {"key": "000102030405060708090a0b0c0d0e0f", "tx_counter": 7, "rx_counter": 9}
+            },
+            {"nwk_address": null, "ieee_address": "aaaaaaaaaaaaaaa1", "is_child": true}
+        ]
+    }"#;
+
+    #[test]
+    fn parses_open_coordinator_backup() {
+        let backup = NetworkBackup::from_json(BACKUP).expect("backup should parse");
+
+        assert_eq!(
+            backup.coordinator_ieee(),
+            Eui64::new(0x00, 0x12, 0x4B, 0x00, 0x01, 0x02, 0x03, 0x04)
+        );
+        assert_eq!(backup.pan_id(), 0x1A2B);
+        assert_eq!(backup.nwk_update_id(), 2);
+        assert_eq!(backup.channel(), 15);
+        assert_eq!(backup.channel_mask(), 1 << 11 | 1 << 15 | 1 << 20);
+        assert_eq!(backup.network_key_sequence_number(), 1);
+        assert_eq!(backup.network_key_frame_counter(), 123_456);
+        assert_eq!(backup.tc_link_key(), b"ZigBeeAlliance09");
+        assert_eq!(
+            backup.devices(),
+            [
+                BackupDevice::new(
+                    Eui64::new(0xAA, 0xAA, 0xAA, 0xAA, 0xAA, 0xAA, 0xAA, 0xA1),
+                    None,
+                    true
+                ),
+                BackupDevice::new(
+                    Eui64::new(0xCC, 0xCC, 0xCC, 0xCC, 0xCC, 0xCC, 0xCC, 0xC1),
+                    Some(0x4567),
+                    false
+                )
+                .with_link_key(BackupLinkKey::new(
+                    [0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15],
+                    7,
+                    9
+                )),
+            ]
+        );
+
+        let parameters = backup.initialization_parameters(Bitmask::empty(), 10_000);
+        assert_eq!(parameters.network_frame_counter(), Some(133_456));
+        assert_eq!(parameters.aps_frame_counter(), Some(10_000));
+    }
+
+    #[test]
+    fn round_trips_through_json() {
+        let backup = NetworkBackup::from_json(BACKUP).expect("backup should parse");
+        let json = backup.to_json();
+
+        assert!(json.contains(r#""coordinator_ieee":"00124b0001020304","pan_id":"1a2b""#));
+        assert!(json.contains(r#""channel_mask":[11,15,20]"#));
+        assert_eq!(NetworkBackup::from_json(&json).ok(), Some(backup));
+    }
+
+    #[test]
+    fn ignores_duplicate_channels() {
+        let json = BACKUP.replace("[11, 15, 20]", "[11, 15, 15, 31, 31]");
+        let backup = NetworkBackup::from_json(&json).expect("backup should parse");
+
+        assert_eq!(backup.channel_mask(), 1 << 11 | 1 << 15 | 1 << 31);
+    }
+}
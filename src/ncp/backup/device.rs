@@ -0,0 +1,67 @@
+use crate::ember::{Eui64, NodeId};
+use crate::ncp::backup::BackupLinkKey;
+
+/// A device recorded in a [`NetworkBackup`](crate::NetworkBackup).
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct BackupDevice {
+    ieee_address: Eui64,
+    node_id: Option<NodeId>,
+    is_child: bool,
+    link_key: Option<BackupLinkKey>,
+}
+
+impl BackupDevice {
+    /// Creates a device entry.
+    #[must_use]
+    pub const fn new(ieee_address: Eui64, node_id: Option<NodeId>, is_child: bool) -> Self {
+        Self {
+            ieee_address,
+            node_id,
+            is_child,
+            link_key: None,
+        }
+    }
+
+    /// Sets the device's application link key.
+    #[must_use]
+    pub const fn with_link_key(mut self, link_key: BackupLinkKey) -> Self {
+        self.link_key = Some(link_key);
+        self
+    }
+
+    /// Returns the IEEE address.
+    #[must_use]
+    pub const fn ieee_address(&self) -> Eui64 {
+        self.ieee_address
+    }
+
+    /// Returns the network address, if known.
+    #[must_use]
+    pub const fn node_id(&self) -> Option<NodeId> {
+        self.node_id
+    }
+
+    /// Returns whether the device is a child of the coordinator.
+    #[must_use]
+    pub const fn is_child(&self) -> bool {
+        self.is_child
+    }
+
+    /// Returns the device's application link key, if any.
+    #[must_use]
+    pub const fn link_key(&self) -> Option<&BackupLinkKey> {
+        self.link_key.as_ref()
+    }
+
+    pub(super) const fn set_node_id(&mut self, node_id: NodeId) {
+        self.node_id = Some(node_id);
+    }
+
+    pub(super) const fn set_child(&mut self) {
+        self.is_child = true;
+    }
+
+    pub(super) const fn set_link_key(&mut self, link_key: BackupLinkKey) {
+        self.link_key = Some(link_key);
+    }
+}
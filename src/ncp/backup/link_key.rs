@@ -0,0 +1,41 @@
+use silizium::zigbee::security::man::Key;
+
+/// A link key recorded in a [`NetworkBackup`](crate::NetworkBackup).
+///
+/// This type contains secret key material.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct BackupLinkKey {
+    key: Key,
+    tx_counter: u32,
+    rx_counter: u32,
+}
+
+impl BackupLinkKey {
+    /// Creates a link key with its outgoing and incoming frame counters.
+    #[must_use]
+    pub const fn new(key: Key, tx_counter: u32, rx_counter: u32) -> Self {
+        Self {
+            key,
+            tx_counter,
+            rx_counter,
+        }
+    }
+
+    /// Returns the key.
+    #[must_use]
+    pub const fn key(&self) -> &Key {
+        &self.key
+    }
+
+    /// Returns the outgoing frame counter.
+    #[must_use]
+    pub const fn tx_counter(&self) -> u32 {
+        self.tx_counter
+    }
+
+    /// Returns the incoming frame counter.
+    #[must_use]
+    pub const fn rx_counter(&self) -> u32 {
+        self.rx_counter
+    }
+}
@@ -0,0 +1,317 @@
+//! Binding table management.
+
+use std::collections::BTreeMap;
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::time::Duration;
+
+use le_stream::{FromLeStream, ToLeStream};
+use log::{debug, trace, warn};
+use tokio::sync::mpsc::{Receiver, channel};
+
+use crate::ember::binding::TableEntry;
+use crate::ember::{Eui64, NodeId, Status};
+use crate::ezsp::config;
+use crate::frame::parameters::binding::handler::Handler as BindingHandler;
+use crate::ncp::service::{MESSAGES_CAPACITY, lock};
+use crate::ncp::{Message, Ncp};
+use crate::zdp::{self, BindRequest, BindResponse, UnbindRequest, UnbindResponse};
+use crate::{Binding, Callback, Configuration, Error, TablesExt, ValueError};
+
+/// Manages the NCP's binding table.
+///
+/// Obtain a manager with [`Ncp::binding_manager`]. It caches the table,
+/// allocates free slots for new bindings and reuses the slot of an identical
+/// binding instead of adding it twice. Spawn the future returned by
+/// [`serve`](Self::serve) to keep the cache in sync with bindings set or
+/// deleted by remote devices. [`bind`](Self::bind) and
+/// [`unbind`](Self::unbind) manage bindings on remote devices with the same
+/// [`TableEntry`] type. Clones of the manager share their cache.
+#[derive(Clone, Debug)]
+pub struct BindingManager {
+    ncp: Ncp,
+    state: Arc<Mutex<State>>,
+}
+
+impl BindingManager {
+    /// Returns the configured size of the binding table.
+    #[must_use]
+    pub fn size(&self) -> u8 {
+        self.lock().size
+    }
+
+    /// Returns the cached bindings keyed by their table index.
+    #[must_use]
+    pub fn entries(&self) -> BTreeMap<u8, TableEntry> {
+        self.lock().entries.clone()
+    }
+
+    /// Returns the table index of a binding identical to `entry`.
+    #[must_use]
+    pub fn find(&self, entry: &TableEntry) -> Option<u8> {
+        self.lock().find(entry)
+    }
+
+    /// Reloads the cache from the NCP.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`Error`] if the table size or its entries cannot be read.
+    pub async fn refresh(&mut self) -> Result<(), Error> {
+        let state = State::load(&mut self.ncp).await?;
+        *self.lock() = state;
+        Ok(())
+    }
+
+    /// Adds a binding to the NCP's binding table and returns its index.
+    ///
+    /// If an identical binding exists, its index is returned without changing
+    /// the table. Otherwise the binding is written to the first free slot.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`Error`] with [`Status::TableFull`] if there is no free
+    /// slot, or if the NCP rejects the binding.
+    pub async fn add(&mut self, entry: TableEntry) -> Result<u8, Error> {
+        let index = {
+            let mut state = self.lock();
+
+            if let Some(index) = state.find(&entry) {
+                trace!("Binding already exists at index {index}.");
+                return Ok(index);
+            }
+
+            let index = state.free_index().ok_or(Status::TableFull)?;
+            state.entries.insert(index, entry.clone());
+            index
+        };
+
+        debug!("Adding binding at index {index}.");
+
+        if let Err(error) = self.ncp.connection.set(index, entry).await {
+            self.lock().entries.remove(&index);
+            return Err(error);
+        }
+
+        Ok(index)
+    }
+
+    /// Removes a binding identical to `entry` and returns its former index.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`Error`] if the NCP fails to delete the binding.
+    pub async fn remove(&mut self, entry: &TableEntry) -> Result<Option<u8>, Error> {
+        let Some(index) = self.find(entry) else {
+            return Ok(None);
+        };
+
+        self.delete(index).await.map(|()| Some(index))
+    }
+
+    /// Deletes the binding at `index`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`Error`] if the NCP fails to delete the binding.
+    pub async fn delete(&mut self, index: u8) -> Result<(), Error> {
+        debug!("Deleting binding at index {index}.");
+        self.ncp.connection.delete(index).await?;
+        self.lock().entries.remove(&index);
+        Ok(())
+    }
+
+    /// Starts keeping the cache in sync with remote binding changes.
+    ///
+    /// The event handler copies all callbacks to the returned future, which
+    /// applies the bindings accepted in `remoteSetBindingHandler` and
+    /// `remoteDeleteBindingHandler` callbacks to the cache. Requests the NCP's
+    /// binding policy rejected are ignored. The future completes when the
+    /// event handler stops.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`Error`] if the callback subscription cannot be registered
+    /// with the event handler.
+    pub async fn serve(&mut self) -> Result<impl Future<Output = ()> + Send + 'static, Error> {
+        let (sender, callbacks) = channel(MESSAGES_CAPACITY);
+        self.ncp
+            .event_handler_handle
+            .send(Message::Callbacks { sender })
+            .await?;
+
+        Ok(self.clone().sync(callbacks))
+    }
+
+    /// Sends a ZDP Bind Request to a device and waits for its response.
+    ///
+    /// `source` is the IEEE address of the device `node_id`, whose binding
+    /// table receives `entry`. Returns `None` if the device did not respond
+    /// within `timeout`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`Error`] if `entry` is not a unicast, many-to-one or
+    /// multicast binding, the response subscription cannot be registered
+    /// with the event handler, or the request cannot be sent.
+    pub async fn bind(
+        &mut self,
+        node_id: NodeId,
+        source: Eui64,
+        entry: &TableEntry,
+        timeout: Duration,
+    ) -> Result<Option<BindResponse>, Error> {
+        let request = BindRequest::new(source, entry).ok_or_else(|| invalid(entry))?;
+        self.request(node_id, request, timeout).await
+    }
+
+    /// Sends a ZDP Unbind Request to a device and waits for its response.
+    ///
+    /// `source` is the IEEE address of the device `node_id`, whose binding
+    /// table loses `entry`. Returns `None` if the device did not respond
+    /// within `timeout`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`Error`] if `entry` is not a unicast, many-to-one or
+    /// multicast binding, the response subscription cannot be registered
+    /// with the event handler, or the request cannot be sent.
+    pub async fn unbind(
+        &mut self,
+        node_id: NodeId,
+        source: Eui64,
+        entry: &TableEntry,
+        timeout: Duration,
+    ) -> Result<Option<UnbindResponse>, Error> {
+        let request = UnbindRequest::new(source, entry).ok_or_else(|| invalid(entry))?;
+        self.request(node_id, request, timeout).await
+    }
+
+    async fn request<T, R>(
+        &mut self,
+        node_id: NodeId,
+        request: T,
+        timeout: Duration,
+    ) -> Result<Option<R>, Error>
+    where
+        T: zdp::Command + ToLeStream,
+        R: zdp::Command + FromLeStream,
+    {
+        let response = self
+            .ncp
+            .zdp_request_response(node_id, request, timeout)
+            .await?;
+
+        if response.is_none() {
+            warn!("No response from {node_id:#06X} to binding request.");
+        }
+
+        Ok(response)
+    }
+
+    async fn sync(self, mut callbacks: Receiver<Callback>) {
+        while let Some(callback) = callbacks.recv().await {
+            if let Callback::Binding(handler) = callback {
+                self.lock().apply(handler);
+            }
+        }
+
+        debug!("Callback subscription closed. Binding table sync terminating.");
+    }
+
+    fn lock(&self) -> MutexGuard<'_, State> {
+        lock(&self.state)
+    }
+}
+
+impl Ncp {
+    /// Creates a [`BindingManager`] caching the NCP's binding table.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`Error`] if the size or the entries of the binding table
+    /// cannot be read.
+    pub async fn binding_manager(&mut self) -> Result<BindingManager, Error> {
+        let state = State::load(self).await?;
+        debug!("Loaded {} of {} bindings.", state.entries.len(), state.size);
+
+        Ok(BindingManager {
+            ncp: self.clone(),
+            state: Arc::new(Mutex::new(state)),
+        })
+    }
+}
+
+#[derive(Debug, Default)]
+struct State {
+    size: u8,
+    entries: BTreeMap<u8, TableEntry>,
+}
+
+impl State {
+    async fn load(ncp: &mut Ncp) -> Result<Self, Error> {
+        let size = ncp
+            .connection
+            .get_configuration_value(config::Id::BindingTableSize)
+            .await?;
+
+        Ok(Self {
+            size: u8::try_from(size).unwrap_or(u8::MAX),
+            entries: ncp.connection.binding_table().await?.collect().await?,
+        })
+    }
+
+    fn find(&self, entry: &TableEntry) -> Option<u8> {
+        self.entries
+            .iter()
+            .find_map(|(&index, existing)| (existing == entry).then_some(index))
+    }
+
+    fn free_index(&self) -> Option<u8> {
+        (0..self.size).find(|index| !self.entries.contains_key(index))
+    }
+
+    fn apply(&mut self, handler: BindingHandler) {
+        match handler {
+            BindingHandler::RemoteSetBinding(set) => match <(u8, TableEntry)>::try_from(*set) {
+                Ok((index, entry)) => {
+                    debug!("Remote device set binding at index {index}.");
+                    self.entries.insert(index, entry);
+                }
+                Err(error) => trace!("Remote binding was not set: {error}"),
+            },
+            BindingHandler::RemoteDeleteBinding(delete) => match u8::try_from(*delete) {
+                Ok(index) => {
+                    debug!("Remote device deleted binding at index {index}.");
+                    self.entries.remove(&index);
+                }
+                Err(error) => trace!("Remote binding was not deleted: {error}"),
+            },
+        }
+    }
+}
+
+fn invalid(entry: &TableEntry) -> Error {
+    ValueError::BindingType(entry.typ().map_or_else(|typ| typ, u8::from)).into()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use super::State;
+    use crate::ember::Eui64;
+    use crate::ember::binding::{TableEntry, Type};
+
+    #[test]
+    fn identical_bindings_share_a_slot() {
+        let entry = |cluster_id| TableEntry::new(Type::Unicast, 1, cluster_id, 1, Eui64::nil(), 0);
+        let state = State {
+            size: 3,
+            entries: BTreeMap::from([(0, entry(0x0006)), (2, entry(0x0008))]),
+        };
+
+        assert_eq!(state.find(&entry(0x0008)), Some(2));
+        assert_eq!(state.find(&entry(0x0300)), None);
+        assert_eq!(state.free_index(), Some(1));
+    }
+}
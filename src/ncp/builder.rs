@@ -7,13 +7,14 @@ use tokio::sync::mpsc::{Sender, channel};
 pub use self::build_result::BuildResult;
 use crate::ember::aps::Options;
 use crate::ember::concentrator;
-use crate::ezsp::{config, policy};
-use crate::ncp::Endpoint;
+use crate::ezsp::{config, policy, value};
 use crate::ncp::await_event::AwaitEvent;
+use crate::ncp::{DeviceRegistry, Endpoint};
+use crate::types::ByteSizedVec;
 use crate::{
     Client, Configuration, ConfigurationExt, Displayable, Error, EventHandler,
-    MIN_NON_LEGACY_VERSION, Messaging, Ncp, Networking, PolicyExt, Security, Startup,
-    TranslatableEvent, Utilities, ValueError,
+    InitializationParameters, MIN_NON_LEGACY_VERSION, Messaging, Ncp, Networking, PolicyExt,
+    Security, Startup, TranslatableEvent, Utilities, ValueError,
 };
 
 mod build_result;
@@ -40,6 +41,7 @@ pub struct Builder {
     pub(crate) radio_tx_power: i8,
     pub(crate) manufacturer_code: Option<u16>,
     pub(crate) options: Options,
+    pub(crate) device_registry: Option<DeviceRegistry>,
 }
 
 impl Builder {
@@ -58,6 +60,7 @@ impl Builder {
             radio_tx_power: RADIO_POWER,
             manufacturer_code: None,
             options: Options::NONE,
+            device_registry: None,
         }
     }
 
@@ -130,6 +133,16 @@ impl Builder {
         self
     }
 
+    /// Sets the device registry maintained by the event handler.
+    ///
+    /// Use this to start from devices restored with [`DeviceRegistry::load`].
+    /// By default, the registry starts empty.
+    #[must_use]
+    pub fn with_device_registry(mut self, device_registry: DeviceRegistry) -> Self {
+        self.device_registry.replace(device_registry);
+        self
+    }
+
     /// Enables route discovery when an outgoing frame has no known route.
     #[must_use]
     pub fn enable_route_discovery(mut self) -> Self {
@@ -236,13 +249,15 @@ impl Builder {
         let (message_tx, message_rx) = channel(self.event_messages_capacity);
 
         info!("Initializing NCP.");
-        let ncp = Ncp::new(
+        let mut ncp = Ncp::new(
             connected.clone(),
             endpoints,
             message_tx.clone(),
             self.options,
         )
         .await?;
+        let device_registry = self.device_registry.unwrap_or_default();
+        ncp.devices = device_registry.clone();
 
         match startup {
             Startup::Initialize(init) => {
@@ -256,6 +271,8 @@ impl Builder {
                     .set_initial_security_state(init.initial_security_state())
                     .await?;
 
+                restore_frame_counters(&mut connected, &init).await?;
+
                 info!("Reinitializing network");
                 connected
                     .form_network(init.parameters(self.radio_tx_power))
@@ -304,7 +321,7 @@ impl Builder {
         };
 
         info!("Creating event handler future.");
-        let event_handler = EventHandler::new(connected, events).run(message_rx);
+        let event_handler = EventHandler::new(connected, events, device_registry).run(message_rx);
 
         Ok(BuildResult {
             ncp,
@@ -335,3 +352,37 @@ where
 
     Ok(())
 }
+
+async fn restore_frame_counters<T>(
+    transport: &mut T,
+    init: &InitializationParameters,
+) -> Result<(), Error>
+where
+    T: Configuration + Send,
+{
+    if let Some(frame_counter) = init.network_frame_counter() {
+        debug!("Restoring NWK frame counter: {frame_counter}");
+        transport
+            .set_value(
+                value::Id::NwkFrameCounter,
+                frame_counter_value(frame_counter),
+            )
+            .await?;
+    }
+
+    if let Some(frame_counter) = init.aps_frame_counter() {
+        debug!("Restoring APS frame counter: {frame_counter}");
+        transport
+            .set_value(
+                value::Id::ApsFrameCounter,
+                frame_counter_value(frame_counter),
+            )
+            .await?;
+    }
+
+    Ok(())
+}
+
+fn frame_counter_value(frame_counter: u32) -> ByteSizedVec<u8> {
+    frame_counter.to_le_bytes().into_iter().collect()
+}
@@ -0,0 +1,370 @@
+//! Channel quality surveys and channel migration.
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::time::Duration;
+
+use log::{debug, info, warn};
+use tokio::sync::mpsc::{Receiver, channel};
+use tokio::time::{Instant, sleep, timeout_at};
+
+pub use self::channel_migration::ChannelMigration;
+pub use self::channel_quality::ChannelQuality;
+use crate::ember::aps::Options;
+use crate::ember::node::Type;
+use crate::ember::{Eui64, NodeId};
+use crate::ncp::service::MESSAGES_CAPACITY;
+use crate::ncp::{Message, Ncp};
+use crate::zdp::{self, MgmtNwkUpdateNotify, MgmtNwkUpdateRequest, NodeDescriptorRequest};
+use crate::{DefragmentedMessage, Error, Networking};
+
+mod channel_migration;
+mod channel_quality;
+
+/// Broadcast address of all devices whose receiver is on when idle.
+const RX_ON_WHEN_IDLE: NodeId = 0xFFFD;
+/// Broadcast radius of zero, which the stack replaces with its maximum radius.
+const MAX_RADIUS: u8 = 0;
+/// Time for a broadcast to reach the whole network, after which devices change channels.
+const BROADCAST_DELIVERY_TIME: Duration = Duration::from_secs(9);
+/// RSSI mapped to the lowest energy value.
+const MIN_RSSI: i16 = -92;
+/// RSSI mapped to the highest energy value.
+const MAX_RSSI: i16 = -5;
+
+/// Settings of a channel quality survey.
+///
+/// Run a survey with [`Ncp::survey_channels`]. The NCP scans the channels in
+/// the [`channel mask`](Self::channel_mask) itself and asks each of the
+/// [`scanners`](Self::scanners) to scan them as well, so that interference
+/// elsewhere in the installation is taken into account.
+/// [`Ncp::migrate_to_best_channel`] only moves the network if the best channel
+/// improves on the current one by at least
+/// [`min_improvement`](Self::min_improvement).
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub struct ChannelSurvey {
+    channel_mask: u32,
+    scan_duration: u8,
+    scanners: Vec<NodeId>,
+    response_timeout: Duration,
+    min_improvement: u8,
+}
+
+impl ChannelSurvey {
+    /// Sets the mask of the channels to scan.
+    #[must_use]
+    pub const fn with_channel_mask(mut self, channel_mask: u32) -> Self {
+        self.channel_mask = channel_mask;
+        self
+    }
+
+    /// Sets the exponent of the scan duration per channel.
+    #[must_use]
+    pub const fn with_scan_duration(mut self, scan_duration: u8) -> Self {
+        self.scan_duration = scan_duration;
+        self
+    }
+
+    /// Sets the routers asked to scan the channels.
+    #[must_use]
+    pub fn with_scanners(mut self, scanners: impl IntoIterator<Item = NodeId>) -> Self {
+        self.scanners = scanners.into_iter().collect();
+        self
+    }
+
+    /// Sets the time to wait for the results of remote scans.
+    #[must_use]
+    pub const fn with_response_timeout(mut self, response_timeout: Duration) -> Self {
+        self.response_timeout = response_timeout;
+        self
+    }
+
+    /// Sets the energy reduction required to move the network.
+    #[must_use]
+    pub const fn with_min_improvement(mut self, min_improvement: u8) -> Self {
+        self.min_improvement = min_improvement;
+        self
+    }
+
+    /// Returns the mask of the channels to scan.
+    #[must_use]
+    pub const fn channel_mask(&self) -> u32 {
+        self.channel_mask
+    }
+
+    /// Returns the exponent of the scan duration per channel.
+    #[must_use]
+    pub const fn scan_duration(&self) -> u8 {
+        self.scan_duration
+    }
+
+    /// Returns the routers asked to scan the channels.
+    #[must_use]
+    pub fn scanners(&self) -> &[NodeId] {
+        &self.scanners
+    }
+
+    /// Returns the time to wait for the results of remote scans.
+    #[must_use]
+    pub const fn response_timeout(&self) -> Duration {
+        self.response_timeout
+    }
+
+    /// Returns the energy reduction required to move the network.
+    #[must_use]
+    pub const fn min_improvement(&self) -> u8 {
+        self.min_improvement
+    }
+}
+
+impl Default for ChannelSurvey {
+    fn default() -> Self {
+        Self {
+            channel_mask: 0x07FF_F800,
+            scan_duration: 3,
+            scanners: Vec::new(),
+            response_timeout: Duration::from_secs(10),
+            min_improvement: 20,
+        }
+    }
+}
+
+impl Ncp {
+    /// Measures the energy on the surveyed channels.
+    ///
+    /// The NCP's energy scan reports the maximum RSSI per channel, which is
+    /// mapped linearly from -92 to -5 dBm onto the 0–255 energy scale of
+    /// remote scans. Scanners that do not answer within the response timeout
+    /// are skipped. Returns the channels ordered from least to most energy.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`Error`] if the local energy scan fails or the response
+    /// subscription cannot be registered with the event handler.
+    pub async fn survey_channels(
+        &self,
+        survey: &ChannelSurvey,
+    ) -> Result<Vec<ChannelQuality>, Error> {
+        let (sender, responses) = channel(MESSAGES_CAPACITY);
+        self.event_handler_handle
+            .send(Message::Zdp {
+                cluster_id: <MgmtNwkUpdateNotify as zdp::Command>::CLUSTER_ID,
+                sender,
+            })
+            .await?;
+
+        let mut ncp = self.clone();
+        let mut channels = BTreeMap::new();
+
+        for result in ncp
+            .scan_channels(survey.channel_mask, survey.scan_duration)
+            .await?
+        {
+            channels.insert(
+                result.channel(),
+                ChannelQuality::new(result.channel(), rssi_to_energy(result.max_rssi_value())),
+            );
+        }
+
+        for (channel, energy) in ncp.remote_scans(survey, responses).await {
+            channels
+                .entry(channel)
+                .and_modify(|quality: &mut ChannelQuality| quality.add(energy))
+                .or_insert_with(|| ChannelQuality::new(channel, energy));
+        }
+
+        let mut channels: Vec<_> = channels.into_values().collect();
+        channels.sort_unstable();
+        Ok(channels)
+    }
+
+    /// Moves the network to another channel.
+    ///
+    /// A Mgmt NWK Update Request with the incremented network update ID is
+    /// broadcast to all devices whose receiver is on when idle, which change
+    /// channels once the broadcast has been delivered. The NCP then moves to
+    /// the channel as well, unless it already followed its own request.
+    /// Finally, every known router and non-sleepy end device is asked for its
+    /// node descriptor on the new channel. Devices acknowledging the request
+    /// have followed, while sleepy end devices find the new channel when their
+    /// parent no longer answers.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`Error`] if the network parameters cannot be read, the
+    /// broadcast fails, or the NCP cannot change its channel.
+    pub async fn migrate_channel(&self, channel: u8) -> Result<ChannelMigration, Error> {
+        let mut ncp = self.clone();
+        let (_, parameters) = ncp.connection.get_network_parameters().await?;
+        let nwk_update_id = parameters.nwk_update_id().wrapping_add(1);
+
+        info!(
+            "Moving network from channel {} to {channel}.",
+            parameters.radio_channel()
+        );
+        let (_, stack_response) = ncp
+            .zdp_broadcast(
+                RX_ON_WHEN_IDLE,
+                MAX_RADIUS,
+                MgmtNwkUpdateRequest::change_channel(channel, nwk_update_id),
+                Options::NONE,
+            )
+            .await?;
+        stack_response.await?;
+        sleep(BROADCAST_DELIVERY_TIME).await;
+
+        if ncp.connection.get_radio_channel().await? != channel {
+            ncp.connection
+                .set_logical_and_radio_channel(channel)
+                .await?;
+        }
+
+        let (_, parameters) = ncp.connection.get_network_parameters().await?;
+        let (followed, missing) = ncp.confirm_devices().await;
+
+        if !missing.is_empty() {
+            warn!(
+                "{} devices did not answer on channel {channel}.",
+                missing.len()
+            );
+        }
+
+        Ok(ChannelMigration {
+            channel: parameters.radio_channel(),
+            nwk_update_id: parameters.nwk_update_id(),
+            followed,
+            missing,
+        })
+    }
+
+    /// Surveys the channels and moves the network to the best one.
+    ///
+    /// The network stays on its current channel unless the best channel's
+    /// energy is lower by at least the survey's minimum improvement. Returns
+    /// the migration if the network was moved.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`Error`] if the survey or the migration fails.
+    pub async fn migrate_to_best_channel(
+        &self,
+        survey: &ChannelSurvey,
+    ) -> Result<Option<ChannelMigration>, Error> {
+        let channels = self.survey_channels(survey).await?;
+        let current = self.clone().connection.get_radio_channel().await?;
+
+        let Some(best) = channels.first() else {
+            return Ok(None);
+        };
+
+        let current_energy = channels
+            .iter()
+            .find(|quality| quality.channel() == current)
+            .map_or(u8::MAX, ChannelQuality::energy);
+
+        if best.channel() == current
+            || current_energy.saturating_sub(best.energy()) < survey.min_improvement
+        {
+            debug!("Staying on channel {current} with energy {current_energy}, best is {best:?}.");
+            return Ok(None);
+        }
+
+        self.migrate_channel(best.channel()).await.map(Some)
+    }
+
+    /// Asks the survey's scanners to scan and returns their measurements.
+    async fn remote_scans(
+        &mut self,
+        survey: &ChannelSurvey,
+        mut responses: Receiver<DefragmentedMessage>,
+    ) -> Vec<(u8, u8)> {
+        let mut pending = BTreeSet::new();
+
+        for &scanner in &survey.scanners {
+            match self
+                .connection
+                .energy_scan_request(scanner, survey.channel_mask, survey.scan_duration, 1)
+                .await
+            {
+                Ok(()) => {
+                    pending.insert(scanner);
+                }
+                Err(error) => warn!("Failed to request energy scan from {scanner:#06X}: {error}"),
+            }
+        }
+
+        let deadline = Instant::now() + survey.response_timeout;
+        let mut energies = Vec::new();
+
+        while !pending.is_empty()
+            && let Ok(Some(message)) = timeout_at(deadline, responses.recv()).await
+        {
+            let Some(frame) = zdp::Frame::<MgmtNwkUpdateNotify>::parse(
+                message.aps_frame().cluster_id(),
+                message.message(),
+            ) else {
+                continue;
+            };
+
+            if pending.remove(&message.sender()) {
+                energies.extend(frame.command().channel_energies());
+            }
+        }
+
+        for scanner in pending {
+            warn!("No energy scan result from {scanner:#06X}.");
+        }
+
+        energies
+    }
+
+    /// Returns the known devices that acknowledge a request and those that do not.
+    async fn confirm_devices(&mut self) -> (Vec<Eui64>, Vec<Eui64>) {
+        let mut followed = Vec::new();
+        let mut missing = Vec::new();
+
+        for device in self.devices().devices() {
+            let (Some(node_id), Some(Type::Router | Type::EndDevice)) =
+                (device.node_id(), device.device_type())
+            else {
+                continue;
+            };
+
+            let acknowledged = match self
+                .zdp_request(node_id, NodeDescriptorRequest::new(node_id), Options::RETRY)
+                .await
+            {
+                Ok((_, stack_response)) => stack_response.await.is_ok(),
+                Err(_) => false,
+            };
+
+            if acknowledged {
+                followed.push(device.ieee_address());
+            } else {
+                missing.push(device.ieee_address());
+            }
+        }
+
+        (followed, missing)
+    }
+}
+
+/// Maps an RSSI in dBm onto the energy scale of remote scans.
+fn rssi_to_energy(rssi: i8) -> u8 {
+    let rssi = i16::from(rssi).clamp(MIN_RSSI, MAX_RSSI);
+    let energy = (rssi - MIN_RSSI) * i16::from(u8::MAX) / (MAX_RSSI - MIN_RSSI);
+    u8::try_from(energy).unwrap_or(u8::MAX)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::rssi_to_energy;
+
+    #[test]
+    fn rssi_maps_onto_energy_scale() {
+        assert_eq!(rssi_to_energy(-100), 0);
+        assert_eq!(rssi_to_energy(-92), 0);
+        assert_eq!(rssi_to_energy(-5), 255);
+        assert_eq!(rssi_to_energy(10), 255);
+        assert!(rssi_to_energy(-70) < rssi_to_energy(-50));
+    }
+}
@@ -0,0 +1,36 @@
+use crate::ember::Eui64;
+
+/// The outcome of moving the network to another channel.
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub struct ChannelMigration {
+    pub(crate) channel: u8,
+    pub(crate) nwk_update_id: u8,
+    pub(crate) followed: Vec<Eui64>,
+    pub(crate) missing: Vec<Eui64>,
+}
+
+impl ChannelMigration {
+    /// Returns the channel the network operates on.
+    #[must_use]
+    pub const fn channel(&self) -> u8 {
+        self.channel
+    }
+
+    /// Returns the network update ID the NCP reports after the migration.
+    #[must_use]
+    pub const fn nwk_update_id(&self) -> u8 {
+        self.nwk_update_id
+    }
+
+    /// Returns the devices that answered on the new channel.
+    #[must_use]
+    pub fn followed(&self) -> &[Eui64] {
+        &self.followed
+    }
+
+    /// Returns the devices that did not answer on the new channel.
+    #[must_use]
+    pub fn missing(&self) -> &[Eui64] {
+        &self.missing
+    }
+}
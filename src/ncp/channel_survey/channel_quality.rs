@@ -0,0 +1,45 @@
+/// The measured energy on a channel.
+///
+/// Lower energy means less interference. The energy is the highest value
+/// reported for the channel by any scanner, on the 0–255 scale of remote
+/// energy scans.
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub struct ChannelQuality {
+    energy: u8,
+    channel: u8,
+    reports: u8,
+}
+
+impl ChannelQuality {
+    pub(crate) const fn new(channel: u8, energy: u8) -> Self {
+        Self {
+            energy,
+            channel,
+            reports: 1,
+        }
+    }
+
+    /// Returns the channel.
+    #[must_use]
+    pub const fn channel(&self) -> u8 {
+        self.channel
+    }
+
+    /// Returns the highest energy measured on the channel.
+    #[must_use]
+    pub const fn energy(&self) -> u8 {
+        self.energy
+    }
+
+    /// Returns the number of scanners that measured the channel.
+    #[must_use]
+    pub const fn reports(&self) -> u8 {
+        self.reports
+    }
+
+    /// Adds another scanner's measurement.
+    pub(crate) fn add(&mut self, energy: u8) {
+        self.energy = self.energy.max(energy);
+        self.reports = self.reports.saturating_add(1);
+    }
+}
@@ -0,0 +1,239 @@
+//! Periodic sampling of the Ember stack counters.
+
+use std::collections::BTreeMap;
+use std::time::{Duration, SystemTime};
+
+use log::{debug, trace, warn};
+use tokio::sync::mpsc::{Receiver, Sender, channel};
+use tokio::time::{Instant, timeout_at};
+
+pub use self::counter_snapshot::CounterSnapshot;
+use crate::ember::constants::COUNTER_TYPE_COUNT;
+use crate::ember::counter::{Counters, Type};
+use crate::ezsp::value;
+use crate::frame::parameters::utilities::handler::Handler as UtilitiesHandler;
+use crate::ncp::service::MESSAGES_CAPACITY;
+use crate::ncp::{Message, Ncp};
+use crate::{Callback, Configuration, Error, Utilities};
+
+mod counter_snapshot;
+
+/// The threshold at which the NCP's 16-bit counters roll over by default.
+const DEFAULT_THRESHOLD: u16 = u16::MAX;
+
+/// Settings of the counter sampling.
+///
+/// Sample the counters with [`Ncp::sample_counters`]. The counters are read
+/// and cleared every [`interval`](Self::interval). A counter reaching its
+/// threshold rolls over and is reported with a `counterRollover` callback.
+/// Thresholds set with [`with_threshold`](Self::with_threshold) are written to
+/// the NCP with [`value::Id::SetCounterThreshold`] when sampling starts.
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub struct CounterSampler {
+    interval: Duration,
+    thresholds: BTreeMap<Type, u8>,
+}
+
+impl CounterSampler {
+    /// Sets the time between two samples.
+    #[must_use]
+    pub const fn with_interval(mut self, interval: Duration) -> Self {
+        self.interval = interval;
+        self
+    }
+
+    /// Sets the value at which the given counter rolls over.
+    #[must_use]
+    pub fn with_threshold(mut self, typ: Type, threshold: u8) -> Self {
+        self.thresholds.insert(typ, threshold);
+        self
+    }
+
+    /// Returns the time between two samples.
+    #[must_use]
+    pub const fn interval(&self) -> Duration {
+        self.interval
+    }
+
+    /// Returns the configured rollover thresholds.
+    #[must_use]
+    pub const fn thresholds(&self) -> &BTreeMap<Type, u8> {
+        &self.thresholds
+    }
+}
+
+impl Default for CounterSampler {
+    fn default() -> Self {
+        Self {
+            interval: Duration::from_mins(1),
+            thresholds: BTreeMap::new(),
+        }
+    }
+}
+
+impl Ncp {
+    /// Starts sampling the Ember counters.
+    ///
+    /// The thresholds of `sampler` are written to the NCP and its counters are
+    /// cleared. The returned future then reads and clears the counters with
+    /// [`Utilities::read_and_clear_counters`] every interval and sends a
+    /// [`CounterSnapshot`] with the deltas, rates and totals to `snapshots`.
+    /// Rollovers reported by `counterRollover` callbacks since the previous
+    /// sample add the counter's threshold to its delta. The future completes
+    /// when the event handler or the snapshot receiver stops.
+    ///
+    /// Since the counters are cleared, other readers of the counters only see
+    /// the increase since the previous sample.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`Error`] if a threshold cannot be set, the counters cannot
+    /// be cleared, or the callback subscription cannot be registered with the
+    /// event handler.
+    pub async fn sample_counters(
+        &mut self,
+        sampler: CounterSampler,
+        snapshots: Sender<CounterSnapshot>,
+    ) -> Result<impl Future<Output = ()> + Send + 'static, Error> {
+        let mut thresholds = [DEFAULT_THRESHOLD; COUNTER_TYPE_COUNT];
+
+        for (&typ, &threshold) in &sampler.thresholds {
+            debug!("Setting threshold of {typ:?} to {threshold}.");
+            self.connection
+                .set_value(
+                    value::Id::SetCounterThreshold,
+                    [typ.into(), threshold].into_iter().collect(),
+                )
+                .await?;
+            thresholds[typ as usize] = threshold.into();
+        }
+
+        let (sender, callbacks) = channel(MESSAGES_CAPACITY);
+        self.event_handler_handle
+            .send(Message::Callbacks { sender })
+            .await?;
+        self.connection.read_and_clear_counters().await?;
+
+        let session = Session {
+            ncp: self.clone(),
+            interval: sampler.interval,
+            thresholds,
+            snapshots,
+            sampled: Instant::now(),
+            rollovers: [0; COUNTER_TYPE_COUNT],
+            totals: [0; COUNTER_TYPE_COUNT],
+        };
+
+        Ok(session.run(callbacks))
+    }
+}
+
+struct Session {
+    ncp: Ncp,
+    interval: Duration,
+    thresholds: [u16; COUNTER_TYPE_COUNT],
+    snapshots: Sender<CounterSnapshot>,
+    sampled: Instant,
+    rollovers: [u64; COUNTER_TYPE_COUNT],
+    totals: [u64; COUNTER_TYPE_COUNT],
+}
+
+impl Session {
+    async fn run(mut self, mut callbacks: Receiver<Callback>) {
+        loop {
+            match timeout_at(self.sampled + self.interval, callbacks.recv()).await {
+                Ok(Some(Callback::Utilities(UtilitiesHandler::CounterRollover(rollover)))) => {
+                    match rollover.typ() {
+                        Ok(typ) => {
+                            trace!("Counter {typ:?} rolled over.");
+                            self.rollovers[typ as usize] += 1;
+                        }
+                        Err(typ) => warn!("Rollover of unknown counter {typ}."),
+                    }
+                }
+                Ok(Some(_)) => {}
+                Ok(None) => break,
+                Err(_) => {
+                    let Some(snapshot) = self.sample().await else {
+                        continue;
+                    };
+
+                    if let Err(error) = self.snapshots.send(snapshot).await {
+                        trace!("Counter snapshot receiver dropped: {error}");
+                        return;
+                    }
+                }
+            }
+        }
+
+        debug!("Callback subscription closed. Counter sampling terminating.");
+    }
+
+    async fn sample(&mut self) -> Option<CounterSnapshot> {
+        let now = Instant::now();
+        let elapsed = now.duration_since(self.sampled);
+        self.sampled = now;
+
+        let counters: Counters = match self.ncp.connection.read_and_clear_counters().await {
+            Ok(counters) => counters.into(),
+            Err(error) => {
+                warn!("Failed to read counters: {error}");
+                return None;
+            }
+        };
+
+        let deltas = deltas(&counters, &self.thresholds, &self.rollovers);
+        self.rollovers = [0; COUNTER_TYPE_COUNT];
+
+        for (total, delta) in self.totals.iter_mut().zip(deltas) {
+            *total = total.saturating_add(delta);
+        }
+
+        Some(CounterSnapshot::new(
+            SystemTime::now(),
+            elapsed,
+            &deltas,
+            &self.totals,
+        ))
+    }
+}
+
+/// Returns the counters' increase including their rollovers.
+fn deltas(
+    counters: &Counters,
+    thresholds: &[u16; COUNTER_TYPE_COUNT],
+    rollovers: &[u64; COUNTER_TYPE_COUNT],
+) -> [u64; COUNTER_TYPE_COUNT] {
+    let mut deltas = [0; COUNTER_TYPE_COUNT];
+
+    for (index, delta) in deltas.iter_mut().enumerate() {
+        *delta =
+            u64::from(counters.as_array()[index]) + rollovers[index] * u64::from(thresholds[index]);
+    }
+
+    deltas
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{DEFAULT_THRESHOLD, deltas};
+    use crate::ember::constants::COUNTER_TYPE_COUNT;
+    use crate::ember::counter::{Counters, Type};
+
+    #[test]
+    fn rollovers_add_thresholds() {
+        let mut values = [0; COUNTER_TYPE_COUNT];
+        values[Type::MacRxUnicast as usize] = 5;
+        values[Type::ApsDataRxUnicast as usize] = 7;
+        let mut thresholds = [DEFAULT_THRESHOLD; COUNTER_TYPE_COUNT];
+        thresholds[Type::ApsDataRxUnicast as usize] = 100;
+        let mut rollovers = [0; COUNTER_TYPE_COUNT];
+        rollovers[Type::MacRxUnicast as usize] = 1;
+        rollovers[Type::ApsDataRxUnicast as usize] = 2;
+
+        let deltas = deltas(&Counters::from(values), &thresholds, &rollovers);
+        assert_eq!(deltas[Type::MacRxUnicast as usize], 5 + 65_535);
+        assert_eq!(deltas[Type::ApsDataRxUnicast as usize], 207);
+        assert_eq!(deltas[Type::MacTxBroadcast as usize], 0);
+    }
+}
@@ -0,0 +1,163 @@
+use std::fmt::Write;
+use std::time::{Duration, SystemTime};
+
+use crate::ember::constants::COUNTER_TYPE_COUNT;
+use crate::ember::counter::Type;
+
+/// Ember counters sampled by [`Ncp::sample_counters`](crate::Ncp::sample_counters).
+///
+/// The NCP's counters are 16 bits wide and cleared by every sample, while the
+/// snapshot accumulates their totals on the host since sampling started.
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub struct CounterSnapshot {
+    taken: SystemTime,
+    elapsed: Duration,
+    deltas: [u64; COUNTER_TYPE_COUNT],
+    totals: [u64; COUNTER_TYPE_COUNT],
+}
+
+impl CounterSnapshot {
+    pub(crate) const fn new(
+        taken: SystemTime,
+        elapsed: Duration,
+        deltas: &[u64; COUNTER_TYPE_COUNT],
+        totals: &[u64; COUNTER_TYPE_COUNT],
+    ) -> Self {
+        Self {
+            taken,
+            elapsed,
+            deltas: *deltas,
+            totals: *totals,
+        }
+    }
+
+    /// Returns when the snapshot was taken.
+    #[must_use]
+    pub const fn taken(&self) -> SystemTime {
+        self.taken
+    }
+
+    /// Returns the time since the previous sample.
+    #[must_use]
+    pub const fn elapsed(&self) -> Duration {
+        self.elapsed
+    }
+
+    /// Returns the increase of the given counter since the previous sample.
+    #[must_use]
+    pub const fn delta(&self, typ: Type) -> u64 {
+        self.deltas[typ as usize]
+    }
+
+    /// Returns the total of the given counter since sampling started.
+    #[must_use]
+    pub const fn total(&self, typ: Type) -> u64 {
+        self.totals[typ as usize]
+    }
+
+    /// Returns the increase of the given counter per second since the previous sample.
+    #[must_use]
+    #[expect(clippy::cast_precision_loss)]
+    pub fn rate(&self, typ: Type) -> f64 {
+        let seconds = self.elapsed.as_secs_f64();
+
+        if seconds > 0.0 {
+            self.delta(typ) as f64 / seconds
+        } else {
+            0.0
+        }
+    }
+
+    /// Renders the totals and rates in the Prometheus text exposition format.
+    ///
+    /// Totals are exposed as the counter `ezsp_counter_total` and rates as the
+    /// gauge `ezsp_counter_rate`, each labelled with the counter type in
+    /// snake case.
+    #[must_use]
+    pub fn to_prometheus(&self) -> String {
+        let mut text = String::new();
+        text.push_str(
+            "# HELP ezsp_counter_total Ember stack counter events since sampling started.\n",
+        );
+        text.push_str("# TYPE ezsp_counter_total counter\n");
+
+        for typ in types() {
+            writeln!(
+                text,
+                "ezsp_counter_total{{counter=\"{}\"}} {}",
+                label(typ),
+                self.total(typ)
+            )
+            .ok();
+        }
+
+        text.push_str("# HELP ezsp_counter_rate Ember stack counter events per second over the last sampling interval.\n");
+        text.push_str("# TYPE ezsp_counter_rate gauge\n");
+
+        for typ in types() {
+            writeln!(
+                text,
+                "ezsp_counter_rate{{counter=\"{}\"}} {}",
+                label(typ),
+                self.rate(typ)
+            )
+            .ok();
+        }
+
+        text
+    }
+}
+
+/// Returns all counter types except the count placeholder.
+fn types() -> impl Iterator<Item = Type> {
+    (0..).map_while(|index| match Type::try_from(index) {
+        Ok(Type::TypeCount) | Err(_) => None,
+        Ok(typ) => Some(typ),
+    })
+}
+
+/// Returns the counter type's name in snake case.
+fn label(typ: Type) -> String {
+    let mut label = String::new();
+
+    for character in format!("{typ:?}").chars() {
+        if character.is_ascii_uppercase() && !label.is_empty() {
+            label.push('_');
+        }
+
+        label.push(character.to_ascii_lowercase());
+    }
+
+    label
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::{Duration, SystemTime};
+
+    use super::CounterSnapshot;
+    use crate::ember::constants::COUNTER_TYPE_COUNT;
+    use crate::ember::counter::Type;
+
+    #[test]
+    fn snapshot_renders_prometheus_text() {
+        let mut deltas = [0; COUNTER_TYPE_COUNT];
+        deltas[Type::MacRxBroadcast as usize] = 30;
+        let mut totals = [0; COUNTER_TYPE_COUNT];
+        totals[Type::MacRxBroadcast as usize] = 120;
+        let snapshot = CounterSnapshot::new(
+            SystemTime::UNIX_EPOCH,
+            Duration::from_mins(1),
+            &deltas,
+            &totals,
+        );
+
+        assert!((snapshot.rate(Type::MacRxBroadcast) - 0.5).abs() < f64::EPSILON);
+
+        let text = snapshot.to_prometheus();
+        assert!(text.contains("ezsp_counter_total{counter=\"mac_rx_broadcast\"} 120\n"));
+        assert!(text.contains("ezsp_counter_rate{counter=\"mac_rx_broadcast\"} 0.5\n"));
+        assert!(text.contains("ezsp_counter_total{counter=\"pta_hi_pri_tx_aborted\"} 0\n"));
+        assert!(!text.contains("type_count"));
+    }
+}
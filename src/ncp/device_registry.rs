@@ -0,0 +1,331 @@
+//! Registry of known devices maintained from NCP callbacks.
+
+use std::collections::BTreeMap;
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::time::SystemTime;
+
+use log::{debug, trace};
+
+pub use self::device::Device;
+pub use self::file_storage::FileStorage;
+pub use self::storage::DeviceStorage;
+use crate::Callback;
+use crate::ember::device::Update;
+use crate::ember::node::Type;
+use crate::ember::{Eui64, NodeId};
+use crate::frame::parameters::messaging::handler::{Handler as Messaging, IncomingMessage};
+use crate::frame::parameters::networking::handler::Handler as Networking;
+use crate::frame::parameters::trust_center::handler::Handler as TrustCenter;
+use crate::ncp::ZDP;
+use crate::ncp::service::lock;
+use crate::zdp::{self, DeviceAnnounce};
+
+mod device;
+mod file_storage;
+mod storage;
+
+/// MAC capability flag of devices that can act as routers.
+const FULL_FUNCTION_DEVICE: u8 = 0b0000_0010;
+/// MAC capability flag of devices whose receiver is on when idle.
+const RX_ON_WHEN_IDLE: u8 = 0b0000_1000;
+
+/// Maps IEEE addresses to network addresses and tracks device metadata.
+///
+/// The [`EventHandler`](crate::EventHandler) keeps the registry up to date
+/// from `incomingSenderEui64`, `childJoin`, `trustCenterJoin`, and `idConflict`
+/// callbacks, ZDP device announcements, and the link quality of incoming
+/// messages. The callbacks are still translated into application events.
+///
+/// Access the registry of a running NCP with [`Ncp::devices`](crate::Ncp::devices).
+/// Pass a registry restored with [`load`](Self::load) to
+/// [`Builder::with_device_registry`](crate::Builder::with_device_registry) to
+/// keep devices across restarts, and persist it with [`save`](Self::save).
+/// Clones share the same devices.
+#[derive(Clone, Debug, Default)]
+pub struct DeviceRegistry {
+    state: Arc<Mutex<State>>,
+}
+
+impl DeviceRegistry {
+    /// Creates a registry with the devices of `storage`.
+    ///
+    /// # Errors
+    ///
+    /// Returns the storage's error if the devices cannot be loaded.
+    pub fn load<S>(storage: &mut S) -> Result<Self, S::Error>
+    where
+        S: DeviceStorage + ?Sized,
+    {
+        let registry = Self::default();
+
+        {
+            let mut state = registry.lock();
+
+            for device in storage.load()? {
+                state.insert(device);
+            }
+        }
+
+        Ok(registry)
+    }
+
+    /// Writes all devices to `storage`.
+    ///
+    /// # Errors
+    ///
+    /// Returns the storage's error if the devices cannot be saved.
+    pub fn save<S>(&self, storage: &mut S) -> Result<(), S::Error>
+    where
+        S: DeviceStorage + ?Sized,
+    {
+        storage.save(&self.devices())
+    }
+
+    /// Returns the device with the given IEEE address.
+    #[must_use]
+    pub fn device(&self, ieee_address: Eui64) -> Option<Device> {
+        self.lock().devices.get(&ieee_address).copied()
+    }
+
+    /// Returns the device currently using the given network address.
+    #[must_use]
+    pub fn device_by_node_id(&self, node_id: NodeId) -> Option<Device> {
+        let state = self.lock();
+        state
+            .node_ids
+            .get(&node_id)
+            .and_then(|ieee_address| state.devices.get(ieee_address))
+            .copied()
+    }
+
+    /// Returns the IEEE address of the device using the given network address.
+    #[must_use]
+    pub fn ieee_address(&self, node_id: NodeId) -> Option<Eui64> {
+        self.lock().node_ids.get(&node_id).copied()
+    }
+
+    /// Returns the network address of the device with the given IEEE address.
+    #[must_use]
+    pub fn node_id(&self, ieee_address: Eui64) -> Option<NodeId> {
+        self.lock()
+            .devices
+            .get(&ieee_address)
+            .and_then(Device::node_id)
+    }
+
+    /// Returns all devices ordered by IEEE address.
+    #[must_use]
+    pub fn devices(&self) -> Vec<Device> {
+        self.lock().devices.values().copied().collect()
+    }
+
+    /// Inserts or replaces a device.
+    pub fn insert(&self, device: Device) {
+        self.lock().insert(device);
+    }
+
+    /// Removes the device with the given IEEE address.
+    #[must_use]
+    pub fn remove(&self, ieee_address: Eui64) -> Option<Device> {
+        self.lock().remove(ieee_address)
+    }
+
+    /// Returns the number of devices.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.lock().devices.len()
+    }
+
+    /// Returns `true` if the registry contains no devices.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.lock().devices.is_empty()
+    }
+
+    /// Updates the registry from an EZSP callback.
+    pub(crate) fn update(&self, callback: &Callback) {
+        self.lock().update(callback, SystemTime::now());
+    }
+
+    fn lock(&self) -> MutexGuard<'_, State> {
+        lock(&self.state)
+    }
+}
+
+#[derive(Debug, Default)]
+struct State {
+    devices: BTreeMap<Eui64, Device>,
+    node_ids: BTreeMap<NodeId, Eui64>,
+    pending_sender: Option<Eui64>,
+}
+
+impl State {
+    fn update(&mut self, callback: &Callback, now: SystemTime) {
+        match callback {
+            Callback::Messaging(messaging) => match messaging {
+                Messaging::IncomingSenderEui64(sender) => {
+                    self.pending_sender = Some(sender.sender_eui64());
+                }
+                Messaging::IncomingMessage(message) => self.incoming_message(message, now),
+                Messaging::IdConflict(conflict) => {
+                    debug!("Address conflict reported for {:#06X}", conflict.id());
+                    self.unbind(conflict.id());
+                }
+                _ => {}
+            },
+            Callback::Networking(networking) => {
+                if let Networking::ChildJoin(child_join) = networking
+                    && child_join.joining()
+                {
+                    let device = self.bind(child_join.child_eui64(), child_join.child_id(), now);
+
+                    if let Ok(device_type) = child_join.child_type() {
+                        device.set_device_type(device_type);
+                    }
+                }
+            }
+            Callback::TrustCenter(trust_center) => {
+                let TrustCenter::TrustCenterJoin(join) = trust_center;
+
+                if join.status() == Ok(Update::DeviceLeft) {
+                    debug!("Device {} left the network", join.new_node_eui64());
+                    self.remove(join.new_node_eui64());
+                } else {
+                    let device = self.bind(join.new_node_eui64(), join.new_node_id(), now);
+                    device.set_parent(join.parent_of_new_node_id());
+
+                    if join.status() == Ok(Update::StandardSecurityUnsecuredJoin) {
+                        device.set_joined(now);
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn insert(&mut self, device: Device) {
+        self.remove(device.ieee_address());
+
+        if let Some(node_id) = device.node_id() {
+            self.unbind(node_id);
+            self.node_ids.insert(node_id, device.ieee_address());
+        }
+
+        self.devices.insert(device.ieee_address(), device);
+    }
+
+    fn remove(&mut self, ieee_address: Eui64) -> Option<Device> {
+        let device = self.devices.remove(&ieee_address)?;
+
+        if let Some(node_id) = device.node_id() {
+            self.node_ids.remove(&node_id);
+        }
+
+        Some(device)
+    }
+
+    /// Assigns a network address to a device, creating the device if it is unknown.
+    fn bind(&mut self, ieee_address: Eui64, node_id: NodeId, now: SystemTime) -> &mut Device {
+        if self.node_ids.get(&node_id) != Some(&ieee_address) {
+            trace!("Binding {node_id:#06X} to {ieee_address}");
+            self.unbind(node_id);
+            self.node_ids.insert(node_id, ieee_address);
+        }
+
+        let device = self
+            .devices
+            .entry(ieee_address)
+            .or_insert_with(|| Device::new(ieee_address, None, now));
+
+        if let Some(previous) = device.node_id()
+            && previous != node_id
+        {
+            self.node_ids.remove(&previous);
+        }
+
+        device.set_node_id(Some(node_id));
+        device.seen(now);
+        device
+    }
+
+    /// Removes a network address from the device using it.
+    fn unbind(&mut self, node_id: NodeId) {
+        if let Some(ieee_address) = self.node_ids.remove(&node_id)
+            && let Some(device) = self.devices.get_mut(&ieee_address)
+        {
+            device.set_node_id(None);
+        }
+    }
+
+    fn incoming_message(&mut self, message: &IncomingMessage, now: SystemTime) {
+        let sender = message.sender();
+
+        let device = if let Some(ieee_address) = self.pending_sender.take() {
+            Some(self.bind(ieee_address, sender, now))
+        } else if message.aps_frame().profile_id() == ZDP
+            && let Some(frame) = zdp::Frame::<DeviceAnnounce>::parse(
+                message.aps_frame().cluster_id(),
+                message.message(),
+            )
+        {
+            let announce = frame.into_command();
+            let device = self.bind(announce.ieee_address(), announce.node_id(), now);
+            device.set_device_type(device_type(announce.capability()));
+            Some(device)
+        } else {
+            self.node_ids
+                .get(&sender)
+                .and_then(|ieee_address| self.devices.get_mut(ieee_address))
+        };
+
+        if let Some(device) = device {
+            device.seen(now);
+            device.set_link_quality(message.last_hop_lqi(), message.last_hop_rssi());
+        }
+    }
+}
+
+/// Derives the device type from the MAC capability flags of a device announcement.
+const fn device_type(capability: u8) -> Type {
+    if capability & FULL_FUNCTION_DEVICE != 0 {
+        Type::Router
+    } else if capability & RX_ON_WHEN_IDLE != 0 {
+        Type::EndDevice
+    } else {
+        Type::SleepyEndDevice
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::{Duration, UNIX_EPOCH};
+
+    use super::State;
+    use crate::ember::Eui64;
+
+    #[test]
+    fn rebinding_node_id_unbinds_previous_device() {
+        let first = Eui64::new(0, 0, 0, 0, 0, 0, 0, 1);
+        let second = Eui64::new(0, 0, 0, 0, 0, 0, 0, 2);
+        let mut state = State::default();
+
+        state.bind(first, 0x1234, UNIX_EPOCH);
+        state.bind(second, 0x1234, UNIX_EPOCH + Duration::from_secs(1));
+
+        assert_eq!(state.node_ids.get(&0x1234), Some(&second));
+        assert_eq!(state.devices[&first].node_id(), None);
+        assert_eq!(state.devices[&second].node_id(), Some(0x1234));
+    }
+
+    #[test]
+    fn changing_node_id_removes_stale_mapping() {
+        let device = Eui64::new(0, 0, 0, 0, 0, 0, 0, 1);
+        let mut state = State::default();
+
+        state.bind(device, 0x1234, UNIX_EPOCH);
+        state.bind(device, 0x5678, UNIX_EPOCH);
+
+        assert!(!state.node_ids.contains_key(&0x1234));
+        assert_eq!(state.node_ids.get(&0x5678), Some(&device));
+    }
+}
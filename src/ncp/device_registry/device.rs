@@ -0,0 +1,142 @@
+use std::time::SystemTime;
+
+use crate::ember::node::Type;
+use crate::ember::{Eui64, NodeId};
+
+/// A device known to the [`DeviceRegistry`](crate::DeviceRegistry).
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct Device {
+    ieee_address: Eui64,
+    node_id: Option<NodeId>,
+    typ: Option<Type>,
+    parent: Option<NodeId>,
+    joined: Option<SystemTime>,
+    last_seen: SystemTime,
+    last_lqi: Option<u8>,
+    last_rssi: Option<i8>,
+}
+
+impl Device {
+    /// Creates a device last seen at `last_seen`.
+    ///
+    /// Use the `with_` methods to restore further properties, for example when
+    /// loading devices from a [`DeviceStorage`](crate::DeviceStorage).
+    #[must_use]
+    pub const fn new(ieee_address: Eui64, node_id: Option<NodeId>, last_seen: SystemTime) -> Self {
+        Self {
+            ieee_address,
+            node_id,
+            typ: None,
+            parent: None,
+            joined: None,
+            last_seen,
+            last_lqi: None,
+            last_rssi: None,
+        }
+    }
+
+    /// Sets the device type.
+    #[must_use]
+    pub const fn with_device_type(mut self, device_type: Type) -> Self {
+        self.typ = Some(device_type);
+        self
+    }
+
+    /// Sets the network address of the device's parent.
+    #[must_use]
+    pub const fn with_parent(mut self, parent: NodeId) -> Self {
+        self.parent = Some(parent);
+        self
+    }
+
+    /// Sets the time the device joined the network.
+    #[must_use]
+    pub const fn with_joined(mut self, joined: SystemTime) -> Self {
+        self.joined = Some(joined);
+        self
+    }
+
+    /// Sets the link quality and signal strength of the last message received from the device.
+    #[must_use]
+    pub const fn with_link_quality(mut self, lqi: u8, rssi: i8) -> Self {
+        self.last_lqi = Some(lqi);
+        self.last_rssi = Some(rssi);
+        self
+    }
+
+    /// Returns the IEEE address of the device.
+    #[must_use]
+    pub const fn ieee_address(&self) -> Eui64 {
+        self.ieee_address
+    }
+
+    /// Returns the current network address of the device.
+    ///
+    /// Returns [`None`] if the address is unknown, for example after an
+    /// address conflict was reported for it.
+    #[must_use]
+    pub const fn node_id(&self) -> Option<NodeId> {
+        self.node_id
+    }
+
+    /// Returns the device type, if known.
+    #[must_use]
+    pub const fn device_type(&self) -> Option<Type> {
+        self.typ
+    }
+
+    /// Returns the network address of the device's parent, if known.
+    #[must_use]
+    pub const fn parent(&self) -> Option<NodeId> {
+        self.parent
+    }
+
+    /// Returns the time the device joined the network, if observed.
+    #[must_use]
+    pub const fn joined(&self) -> Option<SystemTime> {
+        self.joined
+    }
+
+    /// Returns the time the device was last seen.
+    #[must_use]
+    pub const fn last_seen(&self) -> SystemTime {
+        self.last_seen
+    }
+
+    /// Returns the link quality of the last message received from the device.
+    #[must_use]
+    pub const fn last_lqi(&self) -> Option<u8> {
+        self.last_lqi
+    }
+
+    /// Returns the signal strength of the last message received from the device.
+    #[must_use]
+    pub const fn last_rssi(&self) -> Option<i8> {
+        self.last_rssi
+    }
+
+    pub(super) const fn set_node_id(&mut self, node_id: Option<NodeId>) {
+        self.node_id = node_id;
+    }
+
+    pub(super) const fn set_device_type(&mut self, device_type: Type) {
+        self.typ = Some(device_type);
+    }
+
+    pub(super) const fn set_parent(&mut self, parent: NodeId) {
+        self.parent = Some(parent);
+    }
+
+    pub(super) fn set_joined(&mut self, joined: SystemTime) {
+        self.joined.get_or_insert(joined);
+    }
+
+    pub(super) const fn seen(&mut self, time: SystemTime) {
+        self.last_seen = time;
+    }
+
+    pub(super) const fn set_link_quality(&mut self, lqi: u8, rssi: i8) {
+        self.last_lqi = Some(lqi);
+        self.last_rssi = Some(rssi);
+    }
+}
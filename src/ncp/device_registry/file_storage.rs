@@ -0,0 +1,228 @@
+use std::fmt::Display;
+use std::fs::File;
+use std::io::{self, BufRead, BufReader, ErrorKind, Write};
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use num_traits::FromPrimitive;
+
+use crate::ember::node::Type;
+use crate::ember::{Eui64, NodeId};
+use crate::ncp::device_registry::{Device, DeviceStorage};
+use crate::ncp::service::write_atomically;
+
+const NONE: &str = "-";
+const FIELDS: usize = 8;
+
+/// Stores devices in a text file with one device per line.
+///
+/// Each line holds the IEEE address, network address, device type, parent,
+/// join time, last-seen time, LQI, and RSSI separated by whitespace. Times are
+/// seconds since the Unix epoch and unknown values are written as `-`. A
+/// missing file loads as an empty registry. Saving writes a temporary file
+/// next to the target and renames it, so an interrupted save keeps the
+/// previous contents.
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub struct FileStorage {
+    path: PathBuf,
+}
+
+impl FileStorage {
+    /// Creates a storage backed by the file at `path`.
+    #[must_use]
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self { path: path.into() }
+    }
+
+    /// Returns the path of the file.
+    #[must_use]
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+}
+
+impl DeviceStorage for FileStorage {
+    type Error = io::Error;
+
+    fn load(&mut self) -> Result<Vec<Device>, Self::Error> {
+        let file = match File::open(&self.path) {
+            Ok(file) => file,
+            Err(error) if error.kind() == ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(error) => return Err(error),
+        };
+
+        BufReader::new(file)
+            .lines()
+            .filter(|line| line.as_ref().map_or(true, |line| !line.trim().is_empty()))
+            .map(|line| parse_device(&line?))
+            .collect()
+    }
+
+    fn save(&mut self, devices: &[Device]) -> Result<(), Self::Error> {
+        write_atomically(&self.path, |writer| {
+            for device in devices {
+                writeln!(
+                    writer,
+                    "{} {} {} {} {} {} {} {}",
+                    device.ieee_address(),
+                    Field(device.node_id().map(NodeIdField)),
+                    Field(device.device_type().map(u8::from)),
+                    Field(device.parent().map(NodeIdField)),
+                    Field(device.joined().map(seconds)),
+                    seconds(device.last_seen()),
+                    Field(device.last_lqi()),
+                    Field(device.last_rssi()),
+                )?;
+            }
+
+            Ok(())
+        })
+    }
+}
+
+struct Field<T>(Option<T>);
+
+impl<T> Display for Field<T>
+where
+    T: Display,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match &self.0 {
+            Some(value) => value.fmt(f),
+            None => f.write_str(NONE),
+        }
+    }
+}
+
+struct NodeIdField(NodeId);
+
+impl Display for NodeIdField {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:#06X}", self.0)
+    }
+}
+
+fn seconds(time: SystemTime) -> u64 {
+    time.duration_since(UNIX_EPOCH)
+        .map_or(0, |duration| duration.as_secs())
+}
+
+fn parse_device(line: &str) -> io::Result<Device> {
+    let fields: Vec<&str> = line.split_whitespace().collect();
+    let [
+        ieee_address,
+        node_id,
+        device_type,
+        parent,
+        joined,
+        last_seen,
+        last_lqi,
+        last_rssi,
+    ] = <[&str; FIELDS]>::try_from(fields).map_err(|_| invalid(line))?;
+
+    let mut device = Device::new(
+        Eui64::from_str(ieee_address).map_err(|_| invalid(line))?,
+        optional(node_id, parse_node_id).map_err(|()| invalid(line))?,
+        time(last_seen).map_err(|()| invalid(line))?,
+    );
+
+    if let Some(device_type) = optional(device_type, |value| {
+        u8::from_str(value).ok().and_then(Type::from_u8).ok_or(())
+    })
+    .map_err(|()| invalid(line))?
+    {
+        device = device.with_device_type(device_type);
+    }
+
+    if let Some(parent) = optional(parent, parse_node_id).map_err(|()| invalid(line))? {
+        device = device.with_parent(parent);
+    }
+
+    if let Some(joined) = optional(joined, time).map_err(|()| invalid(line))? {
+        device = device.with_joined(joined);
+    }
+
+    let lqi = optional(last_lqi, |value| u8::from_str(value).map_err(drop));
+    let rssi = optional(last_rssi, |value| i8::from_str(value).map_err(drop));
+
+    match (lqi, rssi) {
+        (Ok(Some(lqi)), Ok(Some(rssi))) => device = device.with_link_quality(lqi, rssi),
+        (Ok(None), Ok(None)) => {}
+        _ => return Err(invalid(line)),
+    }
+
+    Ok(device)
+}
+
+fn optional<T>(value: &str, parse: impl Fn(&str) -> Result<T, ()>) -> Result<Option<T>, ()> {
+    if value == NONE {
+        Ok(None)
+    } else {
+        parse(value).map(Some)
+    }
+}
+
+fn parse_node_id(value: &str) -> Result<NodeId, ()> {
+    value
+        .strip_prefix("0x")
+        .and_then(|hex| NodeId::from_str_radix(hex, 16).ok())
+        .ok_or(())
+}
+
+fn time(value: &str) -> Result<SystemTime, ()> {
+    u64::from_str(value)
+        .map(|seconds| UNIX_EPOCH + Duration::from_secs(seconds))
+        .map_err(drop)
+}
+
+fn invalid(line: &str) -> io::Error {
+    io::Error::new(
+        ErrorKind::InvalidData,
+        format!("Invalid device entry: {line}"),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::{Duration, UNIX_EPOCH};
+
+    use super::{FileStorage, parse_device};
+    use crate::DeviceStorage;
+    use crate::ember::Eui64;
+    use crate::ember::node::Type;
+    use crate::ncp::device_registry::Device;
+
+    #[test]
+    fn saves_and_loads_devices() {
+        let path = std::env::temp_dir().join(format!("ezsp-devices-{}.txt", std::process::id()));
+        let mut storage = FileStorage::new(&path);
+        let devices = [
+            Device::new(
+                Eui64::new(0, 1, 2, 3, 4, 5, 6, 7),
+                Some(0x1234),
+                UNIX_EPOCH + Duration::from_secs(1_700_000_000),
+            )
+            .with_device_type(Type::Router)
+            .with_parent(0x0000)
+            .with_joined(UNIX_EPOCH + Duration::from_secs(1_600_000_000))
+            .with_link_quality(255, -40),
+            Device::new(Eui64::new(8, 9, 10, 11, 12, 13, 14, 15), None, UNIX_EPOCH),
+        ];
+
+        storage.save(&devices).expect("devices should be saved");
+        let loaded = storage.load().expect("devices should be loaded");
+        std::fs::remove_file(&path).expect("file should be removed");
+
+        assert_eq!(loaded, devices);
+    }
+
+    #[test]
+    fn rejects_malformed_entries() {
+        assert!(parse_device("00:01:02:03:04:05:06:07 0x1234 2").is_err());
+        assert!(parse_device("00:01:02:03:04:05:06:07 1234 - - - 0 - -").is_err());
+        assert!(parse_device("00:01:02:03:04:05:06:07 0x1234 - - - 0 256 -40").is_err());
+        assert!(parse_device("00:01:02:03:04:05:06:07 0x1234 - - - 0 255 x").is_err());
+        assert!(parse_device("00:01:02:03:04:05:06:07 0x1234 - - - 0 255 -").is_err());
+    }
+}
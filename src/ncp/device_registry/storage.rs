@@ -0,0 +1,25 @@
+use crate::ncp::device_registry::Device;
+
+/// Persistent storage for the devices of a [`DeviceRegistry`](crate::DeviceRegistry).
+///
+/// Implement this trait to keep the registry in a database or another store
+/// of the application's choice. [`FileStorage`](crate::FileStorage) provides a
+/// simple line-based file format.
+pub trait DeviceStorage {
+    /// The error returned when loading or saving fails.
+    type Error;
+
+    /// Loads the stored devices.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Self::Error`] if the devices cannot be read.
+    fn load(&mut self) -> Result<Vec<Device>, Self::Error>;
+
+    /// Replaces the stored devices with `devices`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Self::Error`] if the devices cannot be written.
+    fn save(&mut self, devices: &[Device]) -> Result<(), Self::Error>;
+}
@@ -1,36 +1,50 @@
 use std::collections::BTreeMap;
 
+use le_stream::FromLeStream;
 use log::{debug, trace, warn};
+use tokio::sync::mpsc::error::TrySendError;
 use tokio::sync::mpsc::{Receiver, Sender};
 use tokio::sync::oneshot;
 
 use crate::ember::Status;
 use crate::frame::parameters::networking::handler::Handler as Networking;
-use crate::ncp::{Message, Scans};
+use crate::ncp::{DeviceRegistry, Message, Scans, ZDP};
 use crate::parameters::messaging::handler::{Handler as Messaging, IncomingMessage, MessageSent};
-use crate::{Callback, Communicate, Defragmenter, TranslatableEvent};
+use crate::{Callback, Communicate, DefragmentedMessage, Defragmenter, TranslatableEvent, zcl};
 
 /// Correlates internal callbacks and translates application-facing events.
 ///
 /// The builder runs this handler in a background task. It aggregates scan
 /// callbacks, resolves `messageSent` confirmations, reassembles fragmented APS
-/// messages, and converts remaining callbacks into the configured output event
-/// type.
+/// messages, forwards messages of service-owned clusters to their services,
+/// copies callbacks, ZDP messages, and global ZCL commands to subscribed
+/// services, updates the [`DeviceRegistry`], and converts remaining callbacks
+/// into the configured output event type.
 #[derive(Debug)]
 pub struct EventHandler<T, U> {
     defragmenter: Defragmenter<T>,
     output: Sender<U>,
     scans: Scans,
     responses: BTreeMap<u8, oneshot::Sender<Result<Status, u8>>>,
+    clusters: BTreeMap<u16, Sender<DefragmentedMessage>>,
+    zdp_subscriptions: BTreeMap<u16, Vec<Sender<DefragmentedMessage>>>,
+    global_command_subscriptions: BTreeMap<u8, Vec<Sender<DefragmentedMessage>>>,
+    callback_subscriptions: Vec<Sender<Callback>>,
+    devices: DeviceRegistry,
 }
 
 impl<T, U> EventHandler<T, U> {
-    pub(crate) fn new(transport: T, output: Sender<U>) -> Self {
+    pub(crate) fn new(transport: T, output: Sender<U>, devices: DeviceRegistry) -> Self {
         Self {
             defragmenter: Defragmenter::new(transport),
             output,
             scans: Scans::default(),
             responses: BTreeMap::new(),
+            clusters: BTreeMap::new(),
+            zdp_subscriptions: BTreeMap::new(),
+            global_command_subscriptions: BTreeMap::new(),
+            callback_subscriptions: Vec::new(),
+            devices,
         }
     }
 }
@@ -70,6 +84,25 @@ where
                         warn!("Overwrote response channel for message tag: {tag}");
                     }
                 }
+                Message::Cluster { cluster_id, sender } => {
+                    if self.clusters.insert(cluster_id, sender).is_some() {
+                        warn!("Replaced service route for cluster: {cluster_id:#06X}");
+                    }
+                }
+                Message::Zdp { cluster_id, sender } => {
+                    let subscribers = self.zdp_subscriptions.entry(cluster_id).or_default();
+                    subscribers.retain(|subscriber| !subscriber.is_closed());
+                    subscribers.push(sender);
+                }
+                Message::GlobalCommand { command_id, sender } => {
+                    self.global_command_subscriptions
+                        .entry(command_id)
+                        .or_default()
+                        .push(sender);
+                }
+                Message::Callbacks { sender } => {
+                    self.callback_subscriptions.push(sender);
+                }
                 Message::Terminate => {
                     trace!("Received termination message.");
                     return;
@@ -86,6 +119,9 @@ where
         &mut self,
         callback: Callback,
     ) -> Option<Result<U, <U as TryFrom<Callback>>::Error>> {
+        self.devices.update(&callback);
+        forward_to_subscribers(Some(&mut self.callback_subscriptions), &callback);
+
         match callback {
             Callback::Messaging(messaging) => self
                 .handle_messaging_callbacks(messaging)
@@ -146,6 +182,10 @@ where
 
         trace!("Message defragmented: {defragmented_message:?}");
 
+        let Some(defragmented_message) = self.route_to_service(defragmented_message) else {
+            return;
+        };
+
         match defragmented_message.try_into() {
             Ok(event) => {
                 trace!("Successfully converted defragmented message into an event: {event:?}");
@@ -160,6 +200,47 @@ where
         }
     }
 
+    /// Forwards a message to the service owning its cluster.
+    ///
+    /// ZDP messages and global ZCL commands are copied to all subscribed
+    /// services. ZDP messages are always returned. Other messages are returned
+    /// if no service owns the cluster or the owning service has stopped. The
+    /// handler does not wait for a busy service, so a message is dropped if the
+    /// service's inbox is full.
+    fn route_to_service(&mut self, message: DefragmentedMessage) -> Option<DefragmentedMessage> {
+        let cluster_id = message.aps_frame().cluster_id();
+
+        if message.aps_frame().profile_id() == ZDP {
+            forward_to_subscribers(self.zdp_subscriptions.get_mut(&cluster_id), &message);
+            return Some(message);
+        }
+
+        if let Some(header) = zcl::Header::from_le_stream(message.message().iter().copied())
+            && header.frame_type() == Ok(zcl::FrameType::Global)
+        {
+            forward_to_subscribers(
+                self.global_command_subscriptions
+                    .get_mut(&header.command_id()),
+                &message,
+            );
+        }
+
+        let sender = self.clusters.get(&cluster_id)?;
+
+        match sender.try_send(message) {
+            Ok(()) => None,
+            Err(TrySendError::Full(_)) => {
+                warn!("Service for cluster {cluster_id:#06X} is busy. Dropping message.");
+                None
+            }
+            Err(TrySendError::Closed(message)) => {
+                debug!("Service for cluster {cluster_id:#06X} has stopped. Removing route.");
+                self.clusters.remove(&cluster_id);
+                Some(message)
+            }
+        }
+    }
+
     fn handle_message_sent(&mut self, message_sent: &MessageSent) {
         if let Some(response) = self.responses.remove(&message_sent.message_tag())
             && let Err(error) = response.send(message_sent.status())
@@ -175,3 +256,53 @@ where
         }
     }
 }
+
+/// Sends copies of a message to subscribers and removes stopped subscribers.
+///
+/// Subscribers are not awaited. A subscriber whose channel is full misses the
+/// message, so one slow subscriber cannot delay the others.
+fn forward_to_subscribers<T>(subscribers: Option<&mut Vec<Sender<T>>>, message: &T)
+where
+    T: Clone,
+{
+    let Some(subscribers) = subscribers else {
+        return;
+    };
+
+    subscribers.retain(|subscriber| match subscriber.try_send(message.clone()) {
+        Ok(()) => true,
+        Err(TrySendError::Full(_)) => {
+            warn!("Subscriber is busy. Dropping message.");
+            true
+        }
+        Err(TrySendError::Closed(_)) => {
+            debug!("Subscriber has stopped. Removing it.");
+            false
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use tokio::sync::mpsc::channel;
+
+    use super::forward_to_subscribers;
+
+    #[test]
+    fn busy_subscribers_do_not_block_others() {
+        let (busy, mut busy_receiver) = channel(1);
+        let (idle, mut idle_receiver) = channel(2);
+        let (stopped, stopped_receiver) = channel(1);
+        drop(stopped_receiver);
+        let mut subscribers = vec![busy, stopped, idle];
+
+        forward_to_subscribers(Some(&mut subscribers), &1);
+        forward_to_subscribers(Some(&mut subscribers), &2);
+
+        assert_eq!(subscribers.len(), 2);
+        assert_eq!(busy_receiver.try_recv().ok(), Some(1));
+        assert!(busy_receiver.try_recv().is_err());
+        assert_eq!(idle_receiver.try_recv().ok(), Some(1));
+        assert_eq!(idle_receiver.try_recv().ok(), Some(2));
+    }
+}
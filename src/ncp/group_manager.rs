@@ -0,0 +1,291 @@
+//! Group membership management.
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::sync::{Arc, Mutex, MutexGuard};
+
+use log::{debug, trace};
+
+pub use self::file_storage::GroupFileStorage;
+pub use self::group_membership::GroupMembership;
+pub use self::storage::GroupStorage;
+use crate::ember::aps::Options;
+use crate::ember::{NodeId, Status, multicast};
+use crate::ezsp::config;
+use crate::ncp::service::lock;
+use crate::ncp::{Ncp, StackResponse};
+use crate::zcl::groups::{self, AddGroup, RemoveGroup};
+use crate::zcl::{Command, Frame, HOME_AUTOMATION_PROFILE_ID};
+use crate::{Configuration, Error, Messaging, TablesExt};
+
+mod file_storage;
+mod group_membership;
+mod storage;
+
+/// Manages the group memberships of the local endpoints.
+///
+/// Obtain a manager with [`Ncp::group_manager`]. The NCP only receives
+/// multicasts of groups that are listed in its multicast table, so the manager
+/// writes each membership to a free slot of the table and clears the slot when
+/// the membership is removed. Memberships already in the table are reused.
+///
+/// The NCP's multicast table is volatile. Persist the memberships with
+/// [`save`](Self::save) and write them back after a reset with
+/// [`restore`](Self::restore). [`add_remote`](Self::add_remote) and
+/// [`remove_remote`](Self::remove_remote) change the memberships of a device's
+/// endpoint through the Groups cluster. Clones of the manager share their
+/// state.
+#[derive(Clone, Debug)]
+pub struct GroupManager {
+    ncp: Ncp,
+    state: Arc<Mutex<State>>,
+}
+
+impl GroupManager {
+    /// Returns the configured size of the multicast table.
+    #[must_use]
+    pub fn size(&self) -> u8 {
+        self.lock().size
+    }
+
+    /// Returns all memberships ordered by group and endpoint.
+    #[must_use]
+    pub fn memberships(&self) -> Vec<GroupMembership> {
+        self.lock()
+            .entries
+            .values()
+            .copied()
+            .collect::<BTreeSet<_>>()
+            .into_iter()
+            .collect()
+    }
+
+    /// Returns the groups of which `endpoint` is a member.
+    #[must_use]
+    pub fn groups(&self, endpoint: u8) -> BTreeSet<multicast::Id> {
+        self.lock()
+            .entries
+            .values()
+            .filter(|membership| membership.endpoint() == endpoint)
+            .map(GroupMembership::group_id)
+            .collect()
+    }
+
+    /// Returns whether the membership is in the multicast table.
+    #[must_use]
+    pub fn contains(&self, membership: GroupMembership) -> bool {
+        self.lock().find(membership).is_some()
+    }
+
+    /// Adds a local endpoint to a group and returns its multicast table index.
+    ///
+    /// If the membership exists, its index is returned without changing the
+    /// table. Otherwise it is written to the first free slot.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`Error`] with [`Status::TableFull`] if there is no free
+    /// slot, or if the NCP rejects the entry.
+    pub async fn add(&mut self, membership: GroupMembership) -> Result<u8, Error> {
+        let index = {
+            let mut state = self.lock();
+
+            if let Some(index) = state.find(membership) {
+                trace!("Membership already exists at index {index}.");
+                return Ok(index);
+            }
+
+            let index = state.free_index().ok_or(Status::TableFull)?;
+            state.entries.insert(index, membership);
+            index
+        };
+
+        debug!(
+            "Adding endpoint {} to group {:#06X} at index {index}.",
+            membership.endpoint(),
+            membership.group_id()
+        );
+
+        if let Err(error) = self
+            .ncp
+            .connection
+            .set_multicast_table_entry(
+                index,
+                multicast::TableEntry::new(membership.group_id(), membership.endpoint(), 0),
+            )
+            .await
+        {
+            self.lock().entries.remove(&index);
+            return Err(error);
+        }
+
+        Ok(index)
+    }
+
+    /// Removes a local endpoint from a group and returns its former index.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`Error`] if the NCP fails to clear the entry.
+    pub async fn remove(&mut self, membership: GroupMembership) -> Result<Option<u8>, Error> {
+        let Some(index) = self.lock().find(membership) else {
+            return Ok(None);
+        };
+
+        debug!(
+            "Removing endpoint {} from group {:#06X} at index {index}.",
+            membership.endpoint(),
+            membership.group_id()
+        );
+        self.ncp
+            .connection
+            .set_multicast_table_entry(index, multicast::TableEntry::new(0, 0, 0))
+            .await?;
+        self.lock().entries.remove(&index);
+        Ok(Some(index))
+    }
+
+    /// Adds the memberships of `storage` to the multicast table.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`Error`] if the memberships cannot be loaded or added.
+    pub async fn restore<S>(&mut self, storage: &mut S) -> Result<(), Error>
+    where
+        S: GroupStorage + ?Sized,
+        Error: From<S::Error>,
+    {
+        for membership in storage.load()? {
+            self.add(membership).await?;
+        }
+
+        Ok(())
+    }
+
+    /// Writes all memberships to `storage`.
+    ///
+    /// # Errors
+    ///
+    /// Returns the storage's error if the memberships cannot be saved.
+    pub fn save<S>(&self, storage: &mut S) -> Result<(), S::Error>
+    where
+        S: GroupStorage + ?Sized,
+    {
+        storage.save(&self.memberships())
+    }
+
+    /// Sends an Add Group command to a device endpoint.
+    ///
+    /// The device answers with an Add Group Response, which is delivered to
+    /// the application as an ordinary incoming message.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`Error`] if sending fails as for [`Ncp::unicast`].
+    pub async fn add_remote(
+        &mut self,
+        node_id: NodeId,
+        endpoint: u8,
+        group_id: multicast::Id,
+    ) -> Result<StackResponse, Error> {
+        self.send(node_id, endpoint, AddGroup::new(group_id)).await
+    }
+
+    /// Sends a Remove Group command to a device endpoint.
+    ///
+    /// The device answers with a Remove Group Response, which is delivered to
+    /// the application as an ordinary incoming message.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`Error`] if sending fails as for [`Ncp::unicast`].
+    pub async fn remove_remote(
+        &mut self,
+        node_id: NodeId,
+        endpoint: u8,
+        group_id: multicast::Id,
+    ) -> Result<StackResponse, Error> {
+        self.send(node_id, endpoint, RemoveGroup::new(group_id))
+            .await
+    }
+
+    async fn send<T>(
+        &mut self,
+        node_id: NodeId,
+        endpoint: u8,
+        command: T,
+    ) -> Result<StackResponse, Error>
+    where
+        T: Command + le_stream::ToLeStream,
+    {
+        let frame = Frame::from_command(self.ncp.next_transaction_sequence(), true, command);
+        self.ncp
+            .unicast(
+                node_id,
+                HOME_AUTOMATION_PROFILE_ID,
+                groups::CLUSTER_ID,
+                endpoint,
+                frame.to_bytes(),
+                Options::NONE,
+            )
+            .await
+    }
+
+    fn lock(&self) -> MutexGuard<'_, State> {
+        lock(&self.state)
+    }
+}
+
+impl Ncp {
+    /// Creates a [`GroupManager`] with the memberships of the NCP's multicast table.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`Error`] if the size or the entries of the multicast table
+    /// cannot be read.
+    pub async fn group_manager(&mut self) -> Result<GroupManager, Error> {
+        let size = self
+            .connection
+            .get_configuration_value(config::Id::MulticastTableSize)
+            .await?;
+        let state = State {
+            size: u8::try_from(size).unwrap_or(u8::MAX),
+            entries: self
+                .connection
+                .multicast_table()
+                .await?
+                .collect()
+                .await?
+                .iter()
+                .map(|(&index, entry)| (index, entry.into()))
+                .collect(),
+        };
+        debug!(
+            "Loaded {} of {} multicast table entries.",
+            state.entries.len(),
+            state.size
+        );
+
+        Ok(GroupManager {
+            ncp: self.clone(),
+            state: Arc::new(Mutex::new(state)),
+        })
+    }
+}
+
+#[derive(Debug, Default)]
+struct State {
+    size: u8,
+    entries: BTreeMap<u8, GroupMembership>,
+}
+
+impl State {
+    fn find(&self, membership: GroupMembership) -> Option<u8> {
+        self.entries
+            .iter()
+            .find_map(|(&index, existing)| (*existing == membership).then_some(index))
+    }
+
+    fn free_index(&self) -> Option<u8> {
+        (0..self.size).find(|index| !self.entries.contains_key(index))
+    }
+}
@@ -0,0 +1,103 @@
+use std::fs::File;
+use std::io::{self, BufRead, BufReader, ErrorKind, Write};
+use std::path::{Path, PathBuf};
+
+use crate::ncp::group_manager::{GroupMembership, GroupStorage};
+use crate::ncp::service::write_atomically;
+
+/// Stores group memberships in a text file with one membership per line.
+///
+/// Each line holds the hexadecimal group ID and the endpoint separated by
+/// whitespace. A missing file loads as no memberships. Saving writes a
+/// temporary file next to the target and renames it, so an interrupted save
+/// keeps the previous contents.
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub struct GroupFileStorage {
+    path: PathBuf,
+}
+
+impl GroupFileStorage {
+    /// Creates a storage backed by the file at `path`.
+    #[must_use]
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self { path: path.into() }
+    }
+
+    /// Returns the path of the file.
+    #[must_use]
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+}
+
+impl GroupStorage for GroupFileStorage {
+    type Error = io::Error;
+
+    fn load(&mut self) -> Result<Vec<GroupMembership>, Self::Error> {
+        let file = match File::open(&self.path) {
+            Ok(file) => file,
+            Err(error) if error.kind() == ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(error) => return Err(error),
+        };
+
+        BufReader::new(file)
+            .lines()
+            .filter(|line| line.as_ref().map_or(true, |line| !line.trim().is_empty()))
+            .map(|line| parse_membership(&line?))
+            .collect()
+    }
+
+    fn save(&mut self, memberships: &[GroupMembership]) -> Result<(), Self::Error> {
+        write_atomically(&self.path, |writer| {
+            for membership in memberships {
+                writeln!(
+                    writer,
+                    "{:#06X} {}",
+                    membership.group_id(),
+                    membership.endpoint()
+                )?;
+            }
+
+            Ok(())
+        })
+    }
+}
+
+fn parse_membership(line: &str) -> io::Result<GroupMembership> {
+    let mut fields = line.split_whitespace();
+
+    let (Some(group_id), Some(endpoint), None) = (fields.next(), fields.next(), fields.next())
+    else {
+        return Err(invalid(line));
+    };
+
+    let group_id = group_id
+        .strip_prefix("0x")
+        .and_then(|hex| u16::from_str_radix(hex, 16).ok())
+        .ok_or_else(|| invalid(line))?;
+    let endpoint = endpoint.parse().map_err(|_| invalid(line))?;
+    Ok(GroupMembership::new(group_id, endpoint))
+}
+
+fn invalid(line: &str) -> io::Error {
+    io::Error::new(
+        ErrorKind::InvalidData,
+        format!("Invalid group membership: {line}"),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::parse_membership;
+    use crate::ncp::group_manager::GroupMembership;
+
+    #[test]
+    fn parses_memberships() {
+        assert_eq!(
+            parse_membership("0x1234 1").expect("valid membership"),
+            GroupMembership::new(0x1234, 1)
+        );
+        assert!(parse_membership("0x1234").is_err());
+        assert!(parse_membership("4660 1").is_err());
+    }
+}
@@ -0,0 +1,34 @@
+use crate::ember::multicast;
+
+/// Membership of a local endpoint in a multicast group.
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub struct GroupMembership {
+    group_id: multicast::Id,
+    endpoint: u8,
+}
+
+impl GroupMembership {
+    /// Creates a membership of `endpoint` in the group `group_id`.
+    #[must_use]
+    pub const fn new(group_id: multicast::Id, endpoint: u8) -> Self {
+        Self { group_id, endpoint }
+    }
+
+    /// Returns the group ID.
+    #[must_use]
+    pub const fn group_id(&self) -> multicast::Id {
+        self.group_id
+    }
+
+    /// Returns the member endpoint.
+    #[must_use]
+    pub const fn endpoint(&self) -> u8 {
+        self.endpoint
+    }
+}
+
+impl From<&multicast::TableEntry> for GroupMembership {
+    fn from(entry: &multicast::TableEntry) -> Self {
+        Self::new(entry.multicast_id(), entry.endpoint())
+    }
+}
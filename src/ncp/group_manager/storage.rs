@@ -0,0 +1,27 @@
+use crate::ncp::group_manager::GroupMembership;
+
+/// Persistent storage for the memberships of a [`GroupManager`](crate::GroupManager).
+///
+/// The NCP's multicast table does not survive a reset, so the memberships are
+/// kept by the host and restored with
+/// [`GroupManager::restore`](crate::GroupManager::restore).
+/// [`GroupFileStorage`](crate::GroupFileStorage) provides a simple line-based
+/// file format.
+pub trait GroupStorage {
+    /// The error returned when loading or saving fails.
+    type Error;
+
+    /// Loads the stored memberships.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Self::Error`] if the memberships cannot be read.
+    fn load(&mut self) -> Result<Vec<GroupMembership>, Self::Error>;
+
+    /// Replaces the stored memberships with `memberships`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Self::Error`] if the memberships cannot be written.
+    fn save(&mut self, memberships: &[GroupMembership]) -> Result<(), Self::Error>;
+}
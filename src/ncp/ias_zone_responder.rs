@@ -0,0 +1,394 @@
+//! IAS Zone enrollment responder.
+
+use std::collections::BTreeMap;
+use std::iter;
+
+use log::{debug, info, trace, warn};
+use tokio::sync::mpsc::{Sender, channel};
+
+pub use self::event::IasZoneEvent;
+pub use self::zone_id_allocator::{SequentialZoneIds, ZoneIdAllocator};
+use crate::ember::aps::Options;
+use crate::ember::{Eui64, NodeId};
+use crate::ncp::service::{MESSAGES_CAPACITY, emit};
+use crate::ncp::{Message, Ncp, ZDP};
+use crate::types::ByteSizedVec;
+use crate::zcl::general::{DefaultResponse, WriteAttributes, WriteAttributesResponse};
+use crate::zcl::ias_zone::{
+    self, CIE_ADDRESS, EnrollResponseCode, UNENROLLED_ZONE_ID, ZoneEnrollRequest,
+    ZoneEnrollResponse, ZoneStatusChangeNotification,
+};
+use crate::zcl::{
+    Command, DataType, Direction, Frame, FrameType, HOME_AUTOMATION_PROFILE_ID, Header, Status,
+};
+use crate::zdp::{self, DeviceAnnounce, MatchDescriptorRequest, MatchDescriptorResponse};
+use crate::{DefragmentedMessage, Error, Messaging, Utilities};
+
+mod event;
+mod zone_id_allocator;
+
+/// Enrolls IAS zones with the coordinator acting as CIE.
+///
+/// Start the responder with [`Ncp::serve_ias_zones`]. When a device announces
+/// itself after joining, the responder asks it for endpoints implementing the
+/// IAS Zone server and writes the coordinator's IEEE address to their
+/// [`CIE_ADDRESS`] attribute. Zone Enroll Requests are answered with a zone ID
+/// from the configured [`ZoneIdAllocator`], and Zone Status Change
+/// Notifications are reported as [`IasZoneEvent::StatusChanged`].
+///
+/// The CIE address is written from the first local endpoint that lists the
+/// IAS Zone cluster as an output cluster, so the application must register
+/// such an endpoint. All IAS Zone cluster messages are handled by the
+/// responder and are not translated into application events.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct IasZoneResponder<A = SequentialZoneIds> {
+    allocator: A,
+}
+
+impl<A> IasZoneResponder<A> {
+    /// Creates a responder assigning zone IDs with `allocator`.
+    #[must_use]
+    pub const fn new(allocator: A) -> Self {
+        Self { allocator }
+    }
+
+    /// Returns the zone ID allocator.
+    #[must_use]
+    pub const fn allocator(&self) -> &A {
+        &self.allocator
+    }
+}
+
+impl Ncp {
+    /// Starts enrolling IAS zones and returns the responder future.
+    ///
+    /// The coordinator's IEEE address is read once with
+    /// [`Utilities::get_eui64`] and used as CIE address. The event handler
+    /// forwards IAS Zone cluster messages as well as device announcements and
+    /// match descriptor responses to the returned future, which reports
+    /// enrollments and zone status changes to `events`. Spawn the returned
+    /// future; it runs until the event handler stops.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`Error`] if the IEEE address cannot be read or the message
+    /// routes cannot be registered with the event handler.
+    pub async fn serve_ias_zones<A>(
+        &self,
+        responder: IasZoneResponder<A>,
+        events: Sender<IasZoneEvent>,
+    ) -> Result<impl Future<Output = ()> + Send + 'static, Error>
+    where
+        A: ZoneIdAllocator + Send + 'static,
+    {
+        let mut ncp = self.clone();
+        let cie_address = ncp.connection.get_eui64().await?;
+        let (sender, mut messages) = channel(MESSAGES_CAPACITY);
+
+        for message in [
+            Message::Cluster {
+                cluster_id: ias_zone::CLUSTER_ID,
+                sender: sender.clone(),
+            },
+            Message::Zdp {
+                cluster_id: <DeviceAnnounce as zdp::Command>::CLUSTER_ID,
+                sender: sender.clone(),
+            },
+            Message::Zdp {
+                cluster_id: <MatchDescriptorResponse as zdp::Command>::CLUSTER_ID,
+                sender,
+            },
+        ] {
+            ncp.event_handler_handle.send(message).await?;
+        }
+
+        let mut session = Session {
+            ncp,
+            allocator: responder.allocator,
+            events,
+            cie_address,
+            pending_matches: BTreeMap::new(),
+        };
+
+        Ok(async move {
+            while let Some(message) = messages.recv().await {
+                let result = if message.aps_frame().profile_id() == ZDP {
+                    session.handle_zdp(&message).await
+                } else {
+                    session.handle_zcl(&message).await
+                };
+
+                if let Err(error) = result {
+                    warn!(
+                        "Failed to handle IAS zone message from {:#06X}: {error}",
+                        message.sender()
+                    );
+                }
+            }
+
+            debug!("IAS zone message route closed. IAS zone responder terminating.");
+        })
+    }
+}
+
+struct Session<A> {
+    ncp: Ncp,
+    allocator: A,
+    events: Sender<IasZoneEvent>,
+    cie_address: Eui64,
+    pending_matches: BTreeMap<NodeId, u8>,
+}
+
+impl<A> Session<A>
+where
+    A: ZoneIdAllocator,
+{
+    async fn handle_zdp(&mut self, message: &DefragmentedMessage) -> Result<(), Error> {
+        let cluster_id = message.aps_frame().cluster_id();
+
+        if let Some(frame) = zdp::Frame::<DeviceAnnounce>::parse(cluster_id, message.message()) {
+            let node_id = frame.command().node_id();
+            let (transaction_sequence, stack_response) = self
+                .ncp
+                .zdp_request(
+                    node_id,
+                    MatchDescriptorRequest::new(
+                        node_id,
+                        HOME_AUTOMATION_PROFILE_ID,
+                        iter::once(ias_zone::CLUSTER_ID).collect(),
+                        ByteSizedVec::new(),
+                    ),
+                    Options::NONE,
+                )
+                .await?;
+            drop(stack_response);
+            self.pending_matches.insert(node_id, transaction_sequence);
+        } else if let Some(frame) =
+            zdp::Frame::<MatchDescriptorResponse>::parse(cluster_id, message.message())
+        {
+            let node_id = message.sender();
+
+            if self.pending_matches.get(&node_id) != Some(&frame.transaction_sequence()) {
+                trace!("Ignoring match descriptor response not requested by the IAS responder.");
+                return Ok(());
+            }
+
+            self.pending_matches.remove(&node_id);
+            let response = frame.into_command();
+
+            if response.status() != Ok(zdp::Status::Success) {
+                debug!(
+                    "No IAS zone endpoints on {node_id:#06X}: {:?}",
+                    response.status()
+                );
+                return Ok(());
+            }
+
+            for &endpoint in response.endpoints() {
+                self.write_cie_address(node_id, endpoint).await?;
+            }
+        }
+
+        Ok(())
+    }
+
+    async fn write_cie_address(&mut self, node_id: NodeId, endpoint: u8) -> Result<(), Error> {
+        debug!("Writing CIE address to IAS zone {node_id:#06X}, endpoint {endpoint}");
+        let frame = Frame::new(
+            Header::new(
+                FrameType::Global,
+                Direction::ClientToServer,
+                true,
+                self.ncp.next_transaction_sequence(),
+                WriteAttributes::<Eui64>::ID,
+            ),
+            WriteAttributes::new(CIE_ADDRESS, DataType::IeeeAddress, self.cie_address),
+        );
+        drop(
+            self.ncp
+                .unicast(
+                    node_id,
+                    HOME_AUTOMATION_PROFILE_ID,
+                    ias_zone::CLUSTER_ID,
+                    endpoint,
+                    frame.to_bytes(),
+                    Options::NONE,
+                )
+                .await?,
+        );
+        Ok(())
+    }
+
+    async fn handle_zcl(&mut self, message: &DefragmentedMessage) -> Result<(), Error> {
+        let Some(frame) = Frame::parse(message.message()) else {
+            warn!(
+                "Received malformed IAS zone frame from {:#06X}",
+                message.sender()
+            );
+            return Ok(());
+        };
+        let header = *frame.header();
+
+        if header.direction() != Direction::ServerToClient {
+            trace!("Ignoring IAS zone frame: {header:?}");
+            return Ok(());
+        }
+
+        match header.frame_type() {
+            Ok(FrameType::ClusterSpecific) => match header.command_id() {
+                ZoneStatusChangeNotification::ID => match frame.command() {
+                    Some(notification) => self.status_changed(message, &header, notification).await,
+                    None => {
+                        self.default_response(message, &header, Status::MalformedCommand)
+                            .await
+                    }
+                },
+                ZoneEnrollRequest::ID => match frame.command() {
+                    Some(request) => self.enroll(message, &header, request).await,
+                    None => {
+                        self.default_response(message, &header, Status::MalformedCommand)
+                            .await
+                    }
+                },
+                _ => {
+                    self.default_response(message, &header, Status::UnsupportedClusterCommand)
+                        .await
+                }
+            },
+            Ok(FrameType::Global) if header.command_id() == WriteAttributesResponse::ID => {
+                if let Some(response) = frame.parse_payload::<WriteAttributesResponse>() {
+                    emit(
+                        &self.events,
+                        IasZoneEvent::CieAddressWritten {
+                            node_id: message.sender(),
+                            endpoint: message.aps_frame().source_endpoint(),
+                            status: response.status(),
+                        },
+                    )
+                    .await;
+                }
+
+                Ok(())
+            }
+            _ => {
+                trace!("Ignoring IAS zone frame: {header:?}");
+                Ok(())
+            }
+        }
+    }
+
+    async fn status_changed(
+        &mut self,
+        message: &DefragmentedMessage,
+        header: &Header,
+        notification: ZoneStatusChangeNotification,
+    ) -> Result<(), Error> {
+        self.default_response(message, header, Status::Success)
+            .await?;
+        emit(
+            &self.events,
+            IasZoneEvent::StatusChanged {
+                node_id: message.sender(),
+                endpoint: message.aps_frame().source_endpoint(),
+                zone_id: notification.zone_id(),
+                zone_status: notification.zone_status(),
+                extended_status: notification.extended_status(),
+                delay: notification.delay(),
+            },
+        )
+        .await;
+        Ok(())
+    }
+
+    async fn enroll(
+        &mut self,
+        message: &DefragmentedMessage,
+        header: &Header,
+        request: ZoneEnrollRequest,
+    ) -> Result<(), Error> {
+        let node_id = message.sender();
+        let endpoint = message.aps_frame().source_endpoint();
+        let ieee_address = self.ncp.connection.lookup_eui64_by_node_id(node_id).await?;
+        let zone_id = self
+            .allocator
+            .allocate(ieee_address, endpoint, request.zone_type());
+
+        let Some(zone_id) = zone_id else {
+            warn!("No zone ID available for IAS zone {ieee_address}, endpoint {endpoint}");
+            self.send_enroll_response(
+                message,
+                header,
+                ZoneEnrollResponse::new(EnrollResponseCode::TooManyZones, UNENROLLED_ZONE_ID),
+            )
+            .await?;
+            emit(
+                &self.events,
+                IasZoneEvent::EnrollmentRejected {
+                    node_id,
+                    ieee_address,
+                    endpoint,
+                    zone_type: request.zone_type(),
+                },
+            )
+            .await;
+            return Ok(());
+        };
+
+        info!("Enrolling IAS zone {ieee_address}, endpoint {endpoint} as zone {zone_id}");
+        self.send_enroll_response(
+            message,
+            header,
+            ZoneEnrollResponse::new(EnrollResponseCode::Success, zone_id),
+        )
+        .await?;
+        emit(
+            &self.events,
+            IasZoneEvent::Enrolled {
+                node_id,
+                ieee_address,
+                endpoint,
+                zone_type: request.zone_type(),
+                zone_id,
+            },
+        )
+        .await;
+        Ok(())
+    }
+
+    async fn send_enroll_response(
+        &mut self,
+        message: &DefragmentedMessage,
+        header: &Header,
+        response: ZoneEnrollResponse,
+    ) -> Result<(), Error> {
+        let frame = Frame::from_command(header.transaction_sequence(), true, response);
+        drop(
+            self.ncp
+                .reply(message, frame.to_bytes(), Options::NONE)
+                .await?,
+        );
+        Ok(())
+    }
+
+    async fn default_response(
+        &mut self,
+        message: &DefragmentedMessage,
+        header: &Header,
+        status: Status,
+    ) -> Result<(), Error> {
+        if header.disable_default_response() && status == Status::Success {
+            return Ok(());
+        }
+
+        drop(
+            self.ncp
+                .reply(
+                    message,
+                    DefaultResponse::reply_to(header, status).to_bytes(),
+                    Options::NONE,
+                )
+                .await?,
+        );
+        Ok(())
+    }
+}
@@ -0,0 +1,60 @@
+use crate::ember::{Eui64, NodeId};
+use crate::zcl::Status;
+use crate::zcl::ias_zone::ZoneStatus;
+
+/// IAS zone enrollment and status events reported by an
+/// [`IasZoneResponder`](crate::IasZoneResponder).
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum IasZoneEvent {
+    /// A zone answered the write of the coordinator's IEEE address as its CIE address.
+    CieAddressWritten {
+        /// The zone device.
+        node_id: NodeId,
+        /// The zone's endpoint.
+        endpoint: u8,
+        /// The status of the attribute write.
+        status: Result<Status, u8>,
+    },
+
+    /// A zone was enrolled.
+    Enrolled {
+        /// The zone device.
+        node_id: NodeId,
+        /// The IEEE address of the zone device.
+        ieee_address: Eui64,
+        /// The zone's endpoint.
+        endpoint: u8,
+        /// The zone type.
+        zone_type: u16,
+        /// The assigned zone ID.
+        zone_id: u8,
+    },
+
+    /// No zone ID was available for an enrolling zone.
+    EnrollmentRejected {
+        /// The zone device.
+        node_id: NodeId,
+        /// The IEEE address of the zone device.
+        ieee_address: Eui64,
+        /// The zone's endpoint.
+        endpoint: u8,
+        /// The zone type.
+        zone_type: u16,
+    },
+
+    /// A zone reported a status change.
+    StatusChanged {
+        /// The zone device.
+        node_id: NodeId,
+        /// The zone's endpoint.
+        endpoint: u8,
+        /// The zone ID assigned at enrollment.
+        zone_id: u8,
+        /// The new zone status.
+        zone_status: ZoneStatus,
+        /// The reserved extended status.
+        extended_status: u8,
+        /// The delay in quarter seconds between the change and its notification.
+        delay: u16,
+    },
+}
@@ -0,0 +1,89 @@
+use std::collections::BTreeMap;
+
+use crate::ember::Eui64;
+use crate::zcl::ias_zone::UNENROLLED_ZONE_ID;
+
+/// Assigns zone IDs to enrolling IAS zones.
+///
+/// The allocator is called for every Zone Enroll Request. Returning [`None`]
+/// rejects the enrollment with
+/// [`EnrollResponseCode::TooManyZones`](crate::zcl::ias_zone::EnrollResponseCode::TooManyZones).
+/// Closures with a matching signature implement the trait.
+pub trait ZoneIdAllocator {
+    /// Returns the zone ID for the zone on `endpoint` of the device `ieee_address`.
+    fn allocate(&mut self, ieee_address: Eui64, endpoint: u8, zone_type: u16) -> Option<u8>;
+}
+
+impl<F> ZoneIdAllocator for F
+where
+    F: FnMut(Eui64, u8, u16) -> Option<u8>,
+{
+    fn allocate(&mut self, ieee_address: Eui64, endpoint: u8, zone_type: u16) -> Option<u8> {
+        self(ieee_address, endpoint, zone_type)
+    }
+}
+
+/// Assigns the lowest free zone ID to each zone.
+///
+/// A zone that enrolls again keeps its zone ID. At most 255 zones can be
+/// enrolled, because zone ID `0xFF` marks an unenrolled zone.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct SequentialZoneIds {
+    zones: BTreeMap<(Eui64, u8), u8>,
+}
+
+impl SequentialZoneIds {
+    /// Creates an allocator resuming from previously assigned zone IDs.
+    #[must_use]
+    pub fn new(zones: impl IntoIterator<Item = ((Eui64, u8), u8)>) -> Self {
+        Self {
+            zones: zones.into_iter().collect(),
+        }
+    }
+
+    /// Returns the assigned zone IDs by device and endpoint.
+    #[must_use]
+    pub const fn zones(&self) -> &BTreeMap<(Eui64, u8), u8> {
+        &self.zones
+    }
+}
+
+impl ZoneIdAllocator for SequentialZoneIds {
+    fn allocate(&mut self, ieee_address: Eui64, endpoint: u8, _zone_type: u16) -> Option<u8> {
+        if let Some(zone_id) = self.zones.get(&(ieee_address, endpoint)) {
+            return Some(*zone_id);
+        }
+
+        let zone_id =
+            (0..UNENROLLED_ZONE_ID).find(|zone_id| !self.zones.values().any(|id| id == zone_id))?;
+        self.zones.insert((ieee_address, endpoint), zone_id);
+        Some(zone_id)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reuses_zone_ids_of_known_zones() {
+        let sensor = Eui64::new(0, 1, 2, 3, 4, 5, 6, 7);
+        let mut allocator = SequentialZoneIds::default();
+
+        assert_eq!(allocator.allocate(sensor, 1, 0x000D), Some(0));
+        assert_eq!(allocator.allocate(sensor, 2, 0x0015), Some(1));
+        assert_eq!(allocator.allocate(sensor, 1, 0x000D), Some(0));
+    }
+
+    #[test]
+    fn rejects_zones_when_exhausted() {
+        let mut allocator = SequentialZoneIds::new(
+            (0..UNENROLLED_ZONE_ID).map(|id| ((Eui64::new(0, 0, 0, 0, 0, 0, 0, id), 1), id)),
+        );
+
+        assert_eq!(
+            allocator.allocate(Eui64::new(1, 0, 0, 0, 0, 0, 0, 0), 1, 0x000D),
+            None
+        );
+    }
+}
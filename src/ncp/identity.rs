@@ -0,0 +1,193 @@
+//! NCP identity and the custom EUI64 manufacturing token.
+
+use le_stream::{FromLeStream, ToLeStream};
+use log::{info, warn};
+
+pub use self::write_once_acknowledgement::WriteOnceAcknowledgement;
+use crate::ember::Eui64;
+use crate::ezsp::mfg_token::{Id, Mfg};
+use crate::ncp::Ncp;
+use crate::types::ByteSizedVec;
+use crate::{Error, Utilities, ValueError};
+
+mod write_once_acknowledgement;
+
+const EUI64_LENGTH: usize = 8;
+const MULTICAST_BIT: u8 = 0x01;
+/// Value of an erased manufacturing token byte.
+const ERASED: u8 = 0xFF;
+
+/// The IEEE address an NCP operates with.
+///
+/// The NCP uses the EUI64 programmed by its chip vendor unless its custom
+/// EUI64 manufacturing token has been written. A written token takes effect
+/// after the NCP has been reset, so a freshly written token is reported as
+/// [`pending`](Self::is_pending) until then.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct NcpIdentity {
+    eui64: Eui64,
+    custom_eui64: Option<Eui64>,
+}
+
+impl NcpIdentity {
+    /// Returns the EUI64 the NCP currently operates with.
+    #[must_use]
+    pub const fn eui64(&self) -> Eui64 {
+        self.eui64
+    }
+
+    /// Returns the EUI64 stored in the custom EUI64 token, if it was written.
+    #[must_use]
+    pub const fn custom_eui64(&self) -> Option<Eui64> {
+        self.custom_eui64
+    }
+
+    /// Returns whether the NCP operates with its custom EUI64.
+    #[must_use]
+    pub fn is_custom(&self) -> bool {
+        self.custom_eui64 == Some(self.eui64)
+    }
+
+    /// Returns whether the NCP operates with its factory EUI64.
+    #[must_use]
+    pub fn is_factory(&self) -> bool {
+        !self.is_custom()
+    }
+
+    /// Returns whether a custom EUI64 was written but is not in use until the NCP is reset.
+    #[must_use]
+    pub fn is_pending(&self) -> bool {
+        self.custom_eui64
+            .is_some_and(|custom_eui64| custom_eui64 != self.eui64)
+    }
+}
+
+impl Ncp {
+    /// Returns the NCP's current EUI64 and the contents of its custom EUI64 token.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`Error`] if the EUI64 or the token cannot be read or the
+    /// token has an unexpected length.
+    pub async fn identity(&mut self) -> Result<NcpIdentity, Error> {
+        Ok(NcpIdentity {
+            eui64: self.connection.get_eui64().await?,
+            custom_eui64: self.custom_eui64().await?,
+        })
+    }
+
+    /// Writes the custom EUI64 manufacturing token.
+    ///
+    /// This clones the identity of another NCP, for example the trust center
+    /// EUI64 of a [`NetworkBackup`](crate::NetworkBackup), so that devices
+    /// accept a replacement coordinator. The EUI64 must be a unicast address
+    /// other than the all-zero and all-one values. The token is read back to
+    /// verify the write, and the NCP must be reset before it uses the new
+    /// EUI64.
+    ///
+    /// Writing the EUI64 the token already holds succeeds without writing.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`Error`] if `eui64` is invalid, the token already holds a
+    /// different EUI64, the token cannot be read or written, or the read-back
+    /// value differs from `eui64`.
+    pub async fn write_custom_eui64(
+        &mut self,
+        eui64: Eui64,
+        _acknowledgement: WriteOnceAcknowledgement,
+    ) -> Result<NcpIdentity, Error> {
+        validate(eui64)?;
+
+        match self.custom_eui64().await? {
+            Some(custom_eui64) if custom_eui64 == eui64 => {
+                info!("Custom EUI64 {eui64} already written.");
+                return self.identity().await;
+            }
+            Some(custom_eui64) => {
+                return Err(ValueError::CustomEui64AlreadySet(custom_eui64).into());
+            }
+            None => {}
+        }
+
+        warn!("Writing custom EUI64 {eui64}. This cannot be undone.");
+        self.connection
+            .set_mfg_token(
+                Id::Mfg(Mfg::CustomEui64),
+                eui64.to_le_stream().collect::<ByteSizedVec<u8>>(),
+            )
+            .await?;
+
+        let identity = self.identity().await?;
+
+        if identity.custom_eui64 != Some(eui64) {
+            return Err(ValueError::CustomEui64Mismatch(identity.custom_eui64).into());
+        }
+
+        info!("Custom EUI64 {eui64} written. Reset the NCP to apply it.");
+        Ok(identity)
+    }
+
+    async fn custom_eui64(&mut self) -> Result<Option<Eui64>, Error> {
+        let token = self
+            .connection
+            .get_mfg_token(Id::Mfg(Mfg::CustomEui64))
+            .await?;
+        parse_token(&token)
+    }
+}
+
+fn validate(eui64: Eui64) -> Result<(), ValueError> {
+    let bytes = eui64.as_bytes();
+
+    if bytes.iter().all(|&byte| byte == 0)
+        || bytes.iter().all(|&byte| byte == ERASED)
+        || bytes[0] & MULTICAST_BIT != 0
+    {
+        return Err(ValueError::InvalidEui64(eui64));
+    }
+
+    Ok(())
+}
+
+fn parse_token(token: &[u8]) -> Result<Option<Eui64>, Error> {
+    if token.len() != EUI64_LENGTH {
+        return Err(ValueError::MfgTokenLength {
+            token: Mfg::CustomEui64.into(),
+            length: token.len(),
+        }
+        .into());
+    }
+
+    if token.iter().all(|&byte| byte == ERASED) {
+        return Ok(None);
+    }
+
+    Ok(Eui64::from_le_stream(token.iter().copied()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{parse_token, validate};
+    use crate::ember::Eui64;
+
+    #[test]
+    fn parses_custom_eui64_token() {
+        assert_eq!(parse_token(&[0xFF; 8]).ok(), Some(None));
+        assert_eq!(
+            parse_token(&[0x04, 0x03, 0x02, 0x01, 0x00, 0x4B, 0x12, 0x00]).ok(),
+            Some(Some(Eui64::new(
+                0x00, 0x12, 0x4B, 0x00, 0x01, 0x02, 0x03, 0x04
+            )))
+        );
+        assert!(parse_token(&[0xFF; 4]).is_err());
+    }
+
+    #[test]
+    fn rejects_non_unicast_eui64() {
+        assert!(validate(Eui64::new(0x00, 0x12, 0x4B, 0x00, 0x01, 0x02, 0x03, 0x04)).is_ok());
+        assert!(validate(Eui64::new(0, 0, 0, 0, 0, 0, 0, 0)).is_err());
+        assert!(validate(Eui64::new(0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF)).is_err());
+        assert!(validate(Eui64::new(0x01, 0x12, 0x4B, 0x00, 0x01, 0x02, 0x03, 0x04)).is_err());
+    }
+}
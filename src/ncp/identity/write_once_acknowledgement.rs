@@ -0,0 +1,16 @@
+/// Explicit acknowledgement that a manufacturing token can be written only once.
+///
+/// The custom EUI64 token lives in the NCP's manufacturing token area. Once it
+/// has been written, it cannot be erased or changed through EZSP, so
+/// [`Ncp::write_custom_eui64`](crate::Ncp::write_custom_eui64) requires this
+/// acknowledgement to be constructed deliberately.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct WriteOnceAcknowledgement(());
+
+impl WriteOnceAcknowledgement {
+    /// Acknowledges that the write cannot be undone.
+    #[must_use]
+    pub const fn irreversible() -> Self {
+        Self(())
+    }
+}
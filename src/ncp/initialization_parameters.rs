@@ -9,7 +9,7 @@ use crate::ncp::network_credentials::NetworkCredentials;
 const DEFAULT_NWK_MANAGER_ID: u16 = 0x0000;
 const DEFAULT_NWK_UPDATE_ID: u8 = 0x00;
 const CHANNEL_BIT: u32 = 1;
-const NETWORK_KEY_SEQUENCE_NUMBER: u8 = 0;
+const DEFAULT_NETWORK_KEY_SEQUENCE_NUMBER: u8 = 0;
 
 /// Parameters used to leave any current network and form a new Zigbee network.
 ///
@@ -22,6 +22,9 @@ const NETWORK_KEY_SEQUENCE_NUMBER: u8 = 0;
 /// [`NetworkCredentials`](NetworkCredentials). The remaining values are
 /// specific to forming the network: the preconfigured link key, radio channel,
 /// and join method.
+///
+/// When re-forming a network from a backup, the network update ID, network
+/// key sequence number, and outgoing frame counters can be restored as well.
 #[derive(Clone, Copy, Debug, Eq, PartialEq)]
 pub struct InitializationParameters {
     network_credentials: NetworkCredentials,
@@ -31,6 +34,9 @@ pub struct InitializationParameters {
     bitmask: Bitmask,
     nwk_manager_id: u16,
     nwk_update_id: u8,
+    network_key_sequence_number: u8,
+    network_frame_counter: Option<u32>,
+    aps_frame_counter: Option<u32>,
 }
 
 impl InitializationParameters {
@@ -59,24 +65,77 @@ impl InitializationParameters {
             bitmask,
             nwk_manager_id: DEFAULT_NWK_MANAGER_ID,
             nwk_update_id: DEFAULT_NWK_UPDATE_ID,
+            network_key_sequence_number: DEFAULT_NETWORK_KEY_SEQUENCE_NUMBER,
+            network_frame_counter: None,
+            aps_frame_counter: None,
         }
     }
 
+    /// Sets the network update ID of the formed network.
+    #[must_use]
+    pub const fn with_nwk_update_id(mut self, nwk_update_id: u8) -> Self {
+        self.nwk_update_id = nwk_update_id;
+        self
+    }
+
+    /// Sets the sequence number of the network key.
+    #[must_use]
+    pub const fn with_network_key_sequence_number(
+        mut self,
+        network_key_sequence_number: u8,
+    ) -> Self {
+        self.network_key_sequence_number = network_key_sequence_number;
+        self
+    }
+
+    /// Sets the outgoing NWK and APS frame counters of the formed network.
+    ///
+    /// Devices drop frames whose counters do not exceed the last counter they
+    /// received, so a restored network must continue above the counters of
+    /// the network it replaces. The counters are written to the NCP before the
+    /// network is formed, and the initial security state keeps them instead of
+    /// resetting them.
+    #[must_use]
+    pub const fn with_frame_counters(mut self, network: u32, aps: u32) -> Self {
+        self.network_frame_counter = Some(network);
+        self.aps_frame_counter = Some(aps);
+        self
+    }
+
+    /// Returns the outgoing NWK frame counter to restore, if any.
+    #[must_use]
+    pub const fn network_frame_counter(&self) -> Option<u32> {
+        self.network_frame_counter
+    }
+
+    /// Returns the outgoing APS frame counter to restore, if any.
+    #[must_use]
+    pub const fn aps_frame_counter(&self) -> Option<u32> {
+        self.aps_frame_counter
+    }
+
     /// Creates the Ember initial security state for network formation.
     ///
     /// The returned state contains both the network key from the credentials
-    /// and the separately supplied preconfigured link key.
+    /// and the separately supplied preconfigured link key. If frame counters
+    /// are restored, the state keeps the NCP's frame counters.
     #[must_use]
     pub fn initial_security_state(&self) -> State {
+        let mut bitmask = self.bitmask;
+
+        if self.network_frame_counter.is_some() || self.aps_frame_counter.is_some() {
+            bitmask |= Bitmask::NO_FRAME_COUNTER_RESET;
+        }
+
         State::new(
-            self.bitmask
+            bitmask
                 | Bitmask::TRUST_CENTER_GLOBAL_LINK_KEY
                 | Bitmask::HAVE_PRECONFIGURED_KEY
                 | Bitmask::REQUIRE_ENCRYPTED_KEY
                 | Bitmask::HAVE_NETWORK_KEY,
             self.link_key,
             self.network_credentials.network_key,
-            NETWORK_KEY_SEQUENCE_NUMBER,
+            self.network_key_sequence_number,
             MacAddr8::default(),
         )
     }
@@ -0,0 +1,255 @@
+//! Install-code commissioning.
+
+use std::time::Duration;
+
+use log::{info, warn};
+use silizium::zigbee::security::man::{Context, DerivedKeyType, Flags, Key, KeyType};
+use tokio::time::Instant;
+
+use crate::ember::aes::{MmoHashContext, mmo_hash};
+use crate::ember::{Eui64, network};
+use crate::ncp::{JoinWindowTarget, Ncp};
+use crate::types::ByteSizedVec;
+use crate::{Error, Networking, Security, TrustCenter, ValueError};
+
+const CRC_LENGTH: usize = 2;
+const MAX_LENGTH: usize = 16 + CRC_LENGTH;
+const VALID_CODE_LENGTHS: [usize; 4] = [6, 8, 12, 16];
+const AES_BLOCK_SIZE: usize = 16;
+/// Reflected polynomial of CRC-16/X-25.
+const CRC_POLYNOMIAL: u16 = 0x8408;
+
+/// A Zigbee install code with its CRC.
+///
+/// Install codes are printed on devices that join with a unique link key
+/// instead of the well-known default key. The code is 6, 8, 12, or 16 bytes
+/// long and followed by its CRC-16/X-25 in little-endian byte order. The
+/// device's link key is the AES-MMO hash of the code including the CRC.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct InstallCode {
+    bytes: [u8; MAX_LENGTH],
+    length: usize,
+}
+
+impl InstallCode {
+    /// Returns the code without its CRC.
+    #[must_use]
+    pub fn code(&self) -> &[u8] {
+        &self.bytes[..self.length - CRC_LENGTH]
+    }
+
+    /// Returns the CRC of the code.
+    #[must_use]
+    pub const fn crc(&self) -> u16 {
+        let code_length = self.length - CRC_LENGTH;
+        u16::from_le_bytes([self.bytes[code_length], self.bytes[code_length + 1]])
+    }
+
+    /// Returns the code followed by its CRC.
+    #[must_use]
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.bytes[..self.length]
+    }
+
+    /// Derives the link key on the host.
+    ///
+    /// This returns the same key as [`Ncp::install_code_key`] without NCP round trips.
+    #[must_use]
+    pub fn link_key(&self) -> Key {
+        mmo_hash(self.as_bytes())
+    }
+}
+
+impl TryFrom<&[u8]> for InstallCode {
+    type Error = ValueError;
+
+    /// Validates an install code followed by its CRC.
+    fn try_from(bytes: &[u8]) -> Result<Self, Self::Error> {
+        let Some(code_length) = bytes
+            .len()
+            .checked_sub(CRC_LENGTH)
+            .filter(|code_length| VALID_CODE_LENGTHS.contains(code_length))
+        else {
+            return Err(ValueError::InstallCodeLength(bytes.len()));
+        };
+
+        let (code, crc) = bytes.split_at(code_length);
+        let expected = crc16(code);
+        let actual = u16::from_le_bytes([crc[0], crc[1]]);
+
+        if actual != expected {
+            return Err(ValueError::InstallCodeCrc { expected, actual });
+        }
+
+        let mut install_code = Self {
+            bytes: [0; MAX_LENGTH],
+            length: bytes.len(),
+        };
+        install_code.bytes[..bytes.len()].copy_from_slice(bytes);
+        Ok(install_code)
+    }
+}
+
+impl Ncp {
+    /// Derives the link key of an install code.
+    ///
+    /// The NCP computes the AES-MMO hash of the code including its CRC. Use
+    /// [`InstallCode::link_key`] to derive the key on the host instead.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`Error`] if the NCP fails to compute the hash.
+    pub async fn install_code_key(&mut self, install_code: &InstallCode) -> Result<Key, Error> {
+        let mut context = MmoHashContext::new([0; AES_BLOCK_SIZE], 0);
+        let mut blocks = install_code.as_bytes().chunks(AES_BLOCK_SIZE).peekable();
+
+        while let Some(block) = blocks.next() {
+            context = self
+                .connection
+                .aes_mmo_hash(
+                    context,
+                    blocks.peek().is_none(),
+                    block.iter().copied().collect::<ByteSizedVec<u8>>(),
+                )
+                .await?;
+        }
+
+        Ok(*context.result())
+    }
+
+    /// Commissions a device with its install code.
+    ///
+    /// The link key derived from `install_code` is imported as a transient
+    /// trust center link key for `eui64`, and joining is opened for
+    /// `duration`. The key expires when the NCP's transient key timeout
+    /// elapses. Joining is opened network-wide, so configure the trust center
+    /// policy to reject the well-known link key if only the commissioned device
+    /// may join.
+    ///
+    /// A finite `duration` is registered as a join window, so
+    /// [`JoinRules`](crate::JoinRules) configured with
+    /// [`with_join_window_only`](crate::JoinRules::with_join_window_only)
+    /// accept the device. Joining opened indefinitely is not registered. To
+    /// admit the device under install-code-only rules, also register it with
+    /// [`add_install_code_device`](crate::JoinRules::add_install_code_device).
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`Error`] if the key cannot be imported or joining cannot be
+    /// opened.
+    pub async fn commission_install_code(
+        &mut self,
+        eui64: Eui64,
+        install_code: &InstallCode,
+        duration: network::Duration,
+    ) -> Result<(), Error> {
+        self.connection
+            .import_transient_key(
+                transient_key_context(eui64),
+                eui64,
+                install_code.link_key(),
+                Flags::NONE,
+            )
+            .await?;
+        self.connection.permit_joining(duration).await?;
+
+        match u8::from(duration) {
+            0 => {}
+            u8::MAX => {
+                warn!("Joining opened indefinitely is not registered as a join window.");
+            }
+            seconds => {
+                let now = Instant::now();
+                self.join_windows.open(
+                    JoinWindowTarget::Network,
+                    now + Duration::from_secs(seconds.into()),
+                    now,
+                );
+            }
+        }
+
+        info!("Opened joining for install-code device {eui64}.");
+        Ok(())
+    }
+}
+
+/// Returns the security manager context of a transient link key for `eui64`.
+const fn transient_key_context(eui64: Eui64) -> Context {
+    Context::new(
+        KeyType::TcLinkWithTimeout,
+        0,
+        DerivedKeyType::None,
+        eui64,
+        0,
+        Flags::EUI_IS_VALID,
+        0,
+    )
+}
+
+/// Computes the CRC-16/X-25 of an install code.
+fn crc16(bytes: &[u8]) -> u16 {
+    !bytes.iter().fold(u16::MAX, |crc, &byte| {
+        (0..u8::BITS).fold(crc ^ u16::from(byte), |crc, _| {
+            if crc & 1 == 0 {
+                crc >> 1
+            } else {
+                (crc >> 1) ^ CRC_POLYNOMIAL
+            }
+        })
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{InstallCode, crc16};
+    use crate::ValueError;
+
+    const INSTALL_CODE: [u8; 18] = [
+        0x83, 0xFE, 0xD3, 0x40, 0x7A, 0x93, 0x97, 0x23, 0xA5, 0xC6, 0x39, 0xB2, 0x69, 0x16, 0xD5,
+        0x05, 0xC3, 0xB5,
+    ];
+
+    #[test]
+    fn crc16_matches_check_value() {
+        assert_eq!(crc16(b"123456789"), 0x906E);
+    }
+
+    #[test]
+    fn install_code_validates_length_and_crc() {
+        let install_code = InstallCode::try_from(INSTALL_CODE.as_slice()).unwrap();
+        assert_eq!(install_code.code(), &INSTALL_CODE[..16]);
+        assert_eq!(install_code.crc(), 0xB5C3);
+        assert_eq!(install_code.as_bytes(), INSTALL_CODE.as_slice());
+        assert_eq!(
+            install_code.link_key(),
+            [
+                0x66, 0xB6, 0x90, 0x09, 0x81, 0xE1, 0xEE, 0x3C, 0xA4, 0x20, 0x6B, 0x6B, 0x86, 0x1C,
+                0x02, 0xBB,
+            ]
+        );
+
+        assert_eq!(
+            InstallCode::try_from(&INSTALL_CODE[..17]),
+            Err(ValueError::InstallCodeLength(17))
+        );
+
+        let short =
+            InstallCode::try_from([0x83, 0xFE, 0xD3, 0x40, 0x7A, 0x93, 0x2B, 0x70].as_slice())
+                .unwrap();
+        assert_eq!(short.crc(), 0x702B);
+        assert_eq!(
+            short.link_key(),
+            [
+                0xCD, 0x4F, 0xA0, 0x64, 0x77, 0x3F, 0x46, 0x94, 0x1E, 0xC9, 0x86, 0xC0, 0x99, 0x63,
+                0xD1, 0xA8,
+            ]
+        );
+
+        let mut corrupted = INSTALL_CODE;
+        corrupted[0] ^= 1;
+        assert!(matches!(
+            InstallCode::try_from(corrupted.as_slice()),
+            Err(ValueError::InstallCodeCrc { actual: 0xB5C3, .. })
+        ));
+    }
+}
@@ -0,0 +1,495 @@
+//! Interviews of newly joined devices.
+
+use std::collections::BTreeMap;
+use std::future::poll_fn;
+use std::task::Poll;
+use std::time::Duration;
+
+use log::{debug, info, trace, warn};
+use tokio::sync::mpsc::{Receiver, Sender, channel};
+use tokio::time::{Instant, timeout_at};
+
+pub use self::device_description::DeviceDescription;
+pub use self::event::InterviewEvent;
+use self::pending::PendingInterview;
+pub use self::step::InterviewStep;
+use crate::ember::device::Update;
+use crate::ember::{Eui64, NodeId};
+use crate::frame::parameters::networking::handler::Handler as Networking;
+use crate::frame::parameters::trust_center::handler::Handler as TrustCenter;
+use crate::ncp::service::{MESSAGES_CAPACITY, emit};
+use crate::ncp::{Message, Ncp, ZDP};
+use crate::zcl::general::ReadAttributesResponse;
+use crate::zcl::{Frame, basic};
+use crate::zdp::{
+    self, ActiveEndpointsResponse, DeviceAnnounce, NodeDescriptorResponse, SimpleDescriptorResponse,
+};
+use crate::{Callback, DefragmentedMessage, Error};
+
+mod device_description;
+mod event;
+mod pending;
+mod step;
+
+/// Interviews devices that join the network.
+///
+/// Start the interviewer with [`Ncp::serve_interviews`]. A device is
+/// interviewed when the trust center reports its unsecured join or when it
+/// joins the NCP as a child. The interviewer waits briefly for the device's
+/// announcement, then reads its node descriptor, active endpoints, and the
+/// simple descriptor of each endpoint. Finally, it reads the manufacturer name
+/// and model identifier from the first endpoint implementing the Basic cluster
+/// server, provided a local endpoint lists the Basic cluster as an output
+/// cluster.
+///
+/// Requests are sent with APS retries. Sleepy end devices may miss requests
+/// while their parent buffers them, so each step is repeated up to
+/// [`attempts`](Self::attempts) times before the interview fails. An interview
+/// that does not complete within its [`deadline`](Self::deadline) fails as well.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct Interviewer {
+    response_timeout: Duration,
+    attempts: u8,
+    deadline: Duration,
+    announce_timeout: Duration,
+}
+
+impl Interviewer {
+    /// Sets the time to wait for the response to a request before repeating it.
+    #[must_use]
+    pub const fn with_response_timeout(mut self, response_timeout: Duration) -> Self {
+        self.response_timeout = response_timeout;
+        self
+    }
+
+    /// Sets the number of requests sent per interview step.
+    #[must_use]
+    pub const fn with_attempts(mut self, attempts: u8) -> Self {
+        self.attempts = attempts;
+        self
+    }
+
+    /// Sets the time after which an incomplete interview fails.
+    #[must_use]
+    pub const fn with_deadline(mut self, deadline: Duration) -> Self {
+        self.deadline = deadline;
+        self
+    }
+
+    /// Sets the time to wait for a device's announcement before interviewing it anyway.
+    #[must_use]
+    pub const fn with_announce_timeout(mut self, announce_timeout: Duration) -> Self {
+        self.announce_timeout = announce_timeout;
+        self
+    }
+
+    /// Returns the time to wait for the response to a request before repeating it.
+    #[must_use]
+    pub const fn response_timeout(&self) -> Duration {
+        self.response_timeout
+    }
+
+    /// Returns the number of requests sent per interview step.
+    #[must_use]
+    pub const fn attempts(&self) -> u8 {
+        self.attempts
+    }
+
+    /// Returns the time after which an incomplete interview fails.
+    #[must_use]
+    pub const fn deadline(&self) -> Duration {
+        self.deadline
+    }
+
+    /// Returns the time to wait for a device's announcement before interviewing it anyway.
+    #[must_use]
+    pub const fn announce_timeout(&self) -> Duration {
+        self.announce_timeout
+    }
+}
+
+impl Default for Interviewer {
+    fn default() -> Self {
+        Self {
+            response_timeout: Duration::from_secs(10),
+            attempts: 3,
+            deadline: Duration::from_mins(2),
+            announce_timeout: Duration::from_secs(5),
+        }
+    }
+}
+
+impl Ncp {
+    /// Starts interviewing devices that join the network.
+    ///
+    /// The event handler copies all callbacks, device announcements,
+    /// descriptor responses, and Read Attributes Response commands to the
+    /// returned future, which reports the outcome of each interview to
+    /// `events`. Spawn the returned future; it runs until the event handler
+    /// stops.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`Error`] if the subscriptions cannot be registered with the
+    /// event handler.
+    pub async fn serve_interviews(
+        &self,
+        interviewer: Interviewer,
+        events: Sender<InterviewEvent>,
+    ) -> Result<impl Future<Output = ()> + Send + 'static, Error> {
+        let (callback_sender, callbacks) = channel(MESSAGES_CAPACITY);
+        let (sender, messages) = channel(MESSAGES_CAPACITY);
+        self.event_handler_handle
+            .send(Message::Callbacks {
+                sender: callback_sender,
+            })
+            .await?;
+        self.event_handler_handle
+            .send(Message::GlobalCommand {
+                command_id: ReadAttributesResponse::ID,
+                sender: sender.clone(),
+            })
+            .await?;
+
+        for cluster_id in [
+            <DeviceAnnounce as zdp::Command>::CLUSTER_ID,
+            <NodeDescriptorResponse as zdp::Command>::CLUSTER_ID,
+            <ActiveEndpointsResponse as zdp::Command>::CLUSTER_ID,
+            <SimpleDescriptorResponse as zdp::Command>::CLUSTER_ID,
+        ] {
+            self.event_handler_handle
+                .send(Message::Zdp {
+                    cluster_id,
+                    sender: sender.clone(),
+                })
+                .await?;
+        }
+
+        let session = Session {
+            ncp: self.clone(),
+            interviewer,
+            events,
+            interviews: BTreeMap::new(),
+        };
+
+        Ok(session.run(callbacks, messages))
+    }
+}
+
+enum Input {
+    Callback(Callback),
+    Message(DefragmentedMessage),
+}
+
+struct Session {
+    ncp: Ncp,
+    interviewer: Interviewer,
+    events: Sender<InterviewEvent>,
+    interviews: BTreeMap<Eui64, PendingInterview>,
+}
+
+impl Session {
+    async fn run(
+        mut self,
+        mut callbacks: Receiver<Callback>,
+        mut messages: Receiver<DefragmentedMessage>,
+    ) {
+        loop {
+            let next = poll_fn(|context| {
+                if let Poll::Ready(callback) = callbacks.poll_recv(context) {
+                    return Poll::Ready(callback.map(Input::Callback));
+                }
+
+                messages
+                    .poll_recv(context)
+                    .map(|message| message.map(Input::Message))
+            });
+            let deadline = self
+                .interviews
+                .values()
+                .map(PendingInterview::next_deadline)
+                .min();
+            let input = match deadline {
+                Some(deadline) => timeout_at(deadline, next).await,
+                None => Ok(next.await),
+            };
+
+            match input {
+                Ok(Some(Input::Callback(callback))) => self.callback(&callback).await,
+                Ok(Some(Input::Message(message))) => self.handle(&message).await,
+                Ok(None) => break,
+                Err(_) => self.tick().await,
+            }
+        }
+
+        debug!("Interview subscriptions closed. Interviewer terminating.");
+    }
+
+    async fn callback(&mut self, callback: &Callback) {
+        match callback {
+            Callback::TrustCenter(TrustCenter::TrustCenterJoin(join))
+                if join.status() == Ok(Update::StandardSecurityUnsecuredJoin) =>
+            {
+                self.start(join.new_node_id(), join.new_node_eui64()).await;
+            }
+            Callback::Networking(Networking::ChildJoin(child_join)) if child_join.joining() => {
+                self.start(child_join.child_id(), child_join.child_eui64())
+                    .await;
+            }
+            _ => {}
+        }
+    }
+
+    async fn start(&mut self, node_id: NodeId, ieee_address: Eui64) {
+        if let Some(interview) = self.interviews.get_mut(&ieee_address) {
+            trace!("Interview of {ieee_address} already in progress.");
+            interview.set_node_id(node_id);
+            return;
+        }
+
+        info!("Interviewing {ieee_address} ({node_id:#06X})");
+        self.interviews.insert(
+            ieee_address,
+            PendingInterview::new(node_id, ieee_address, &self.interviewer, Instant::now()),
+        );
+        emit(
+            &self.events,
+            InterviewEvent::Started {
+                node_id,
+                ieee_address,
+            },
+        )
+        .await;
+    }
+
+    async fn handle(&mut self, message: &DefragmentedMessage) {
+        let advanced = if message.aps_frame().profile_id() == ZDP {
+            self.zdp(message)
+        } else {
+            self.basic_attributes(message)
+        };
+
+        if let Some(ieee_address) = advanced {
+            self.advance(ieee_address).await;
+        }
+    }
+
+    /// Processes a ZDP message and returns the interview whose step completed.
+    fn zdp(&mut self, message: &DefragmentedMessage) -> Option<Eui64> {
+        let cluster_id = message.aps_frame().cluster_id();
+        let sender = message.sender();
+
+        match cluster_id {
+            <DeviceAnnounce as zdp::Command>::CLUSTER_ID => {
+                let announce = zdp::Frame::<DeviceAnnounce>::parse(cluster_id, message.message())?
+                    .into_command();
+                let interview = self.interviews.get_mut(&announce.ieee_address())?;
+                interview.set_node_id(announce.node_id());
+                (interview.step() == InterviewStep::Announcement).then(|| interview.ieee_address())
+            }
+            <NodeDescriptorResponse as zdp::Command>::CLUSTER_ID => {
+                let frame =
+                    zdp::Frame::<NodeDescriptorResponse>::parse(cluster_id, message.message())?;
+                let interview = self.awaiting(
+                    sender,
+                    frame.transaction_sequence(),
+                    InterviewStep::NodeDescriptor,
+                )?;
+                let response = frame.into_command();
+
+                if response.status() != Ok(zdp::Status::Success) {
+                    debug!(
+                        "Node descriptor of {sender:#06X} unavailable: {:?}",
+                        response.status()
+                    );
+                    return None;
+                }
+
+                interview.set_node_descriptor(*response.descriptor()?);
+                Some(interview.ieee_address())
+            }
+            <ActiveEndpointsResponse as zdp::Command>::CLUSTER_ID => {
+                let frame =
+                    zdp::Frame::<ActiveEndpointsResponse>::parse(cluster_id, message.message())?;
+                let interview = self.awaiting(
+                    sender,
+                    frame.transaction_sequence(),
+                    InterviewStep::ActiveEndpoints,
+                )?;
+                let response = frame.into_command();
+
+                if response.status() != Ok(zdp::Status::Success) {
+                    debug!(
+                        "Active endpoints of {sender:#06X} unavailable: {:?}",
+                        response.status()
+                    );
+                    return None;
+                }
+
+                interview.set_active_endpoints(response.endpoints());
+                Some(interview.ieee_address())
+            }
+            <SimpleDescriptorResponse as zdp::Command>::CLUSTER_ID => {
+                let frame =
+                    zdp::Frame::<SimpleDescriptorResponse>::parse(cluster_id, message.message())?;
+                let transaction_sequence = frame.transaction_sequence();
+                let interview = self.interviews.values_mut().find(|interview| {
+                    matches!(interview.step(), InterviewStep::SimpleDescriptor { .. })
+                        && interview.awaits(sender, transaction_sequence)
+                })?;
+                let response = frame.into_command();
+
+                if response.status() == Ok(zdp::Status::Success)
+                    && let Some(descriptor) = response.into_descriptor()
+                {
+                    interview.add_endpoint(descriptor);
+                } else {
+                    debug!(
+                        "Skipping endpoint of {sender:#06X} without simple descriptor: {}",
+                        interview.step()
+                    );
+                }
+
+                Some(interview.ieee_address())
+            }
+            _ => None,
+        }
+    }
+
+    /// Processes a Read Attributes Response and returns the interview whose step completed.
+    fn basic_attributes(&mut self, message: &DefragmentedMessage) -> Option<Eui64> {
+        if message.aps_frame().cluster_id() != basic::CLUSTER_ID {
+            return None;
+        }
+
+        let frame = Frame::parse(message.message())?;
+
+        if frame.header().command_id() != ReadAttributesResponse::ID {
+            return None;
+        }
+
+        let sender = message.sender();
+        let transaction_sequence = frame.header().transaction_sequence();
+        let interview = self.interviews.values_mut().find(|interview| {
+            matches!(interview.step(), InterviewStep::BasicAttributes { .. })
+                && interview.awaits(sender, transaction_sequence)
+        })?;
+        interview.set_basic_attributes(&frame.parse_payload::<ReadAttributesResponse>()?);
+        Some(interview.ieee_address())
+    }
+
+    fn awaiting(
+        &mut self,
+        node_id: NodeId,
+        transaction_sequence: u8,
+        step: InterviewStep,
+    ) -> Option<&mut PendingInterview> {
+        self.interviews.values_mut().find(|interview| {
+            interview.step() == step && interview.awaits(node_id, transaction_sequence)
+        })
+    }
+
+    /// Moves an interview to its next step and sends the step's request.
+    async fn advance(&mut self, ieee_address: Eui64) {
+        loop {
+            let Some(interview) = self.interviews.get_mut(&ieee_address) else {
+                return;
+            };
+
+            if !interview.advance() {
+                self.complete(ieee_address).await;
+                return;
+            }
+
+            match interview
+                .send(&mut self.ncp, &self.interviewer, Instant::now())
+                .await
+            {
+                Ok(()) => return,
+                Err(Error::NoMatchingSourceEndpoint(cluster_id)) => {
+                    debug!(
+                        "No local endpoint for cluster {cluster_id:#06X}. Skipping {}.",
+                        interview.step()
+                    );
+                }
+                Err(error) => {
+                    warn!("Failed to send interview request to {ieee_address}: {error}");
+                    return;
+                }
+            }
+        }
+    }
+
+    /// Repeats or fails interviews whose responses are overdue.
+    async fn tick(&mut self) {
+        let now = Instant::now();
+        let overdue: Vec<Eui64> = self
+            .interviews
+            .values()
+            .filter(|interview| interview.next_deadline() <= now)
+            .map(PendingInterview::ieee_address)
+            .collect();
+
+        for ieee_address in overdue {
+            let Some(interview) = self.interviews.get_mut(&ieee_address) else {
+                continue;
+            };
+
+            if interview.deadline() <= now {
+                self.fail(ieee_address).await;
+            } else if interview.step() == InterviewStep::Announcement {
+                debug!("No announcement from {ieee_address}. Interviewing anyway.");
+                self.advance(ieee_address).await;
+            } else if interview.attempts() >= self.interviewer.attempts() {
+                self.fail(ieee_address).await;
+            } else {
+                debug!(
+                    "Repeating request for {} of {ieee_address}",
+                    interview.step()
+                );
+
+                if let Err(error) = interview.send(&mut self.ncp, &self.interviewer, now).await {
+                    warn!("Failed to send interview request to {ieee_address}: {error}");
+                }
+            }
+        }
+    }
+
+    async fn complete(&mut self, ieee_address: Eui64) {
+        let Some(interview) = self.interviews.remove(&ieee_address) else {
+            return;
+        };
+
+        match interview.into_description() {
+            Some(description) => {
+                info!("Interview of {ieee_address} completed.");
+                emit(
+                    &self.events,
+                    InterviewEvent::Completed(Box::new(description)),
+                )
+                .await;
+            }
+            None => warn!("Interview of {ieee_address} completed without node descriptor."),
+        }
+    }
+
+    async fn fail(&mut self, ieee_address: Eui64) {
+        let Some(interview) = self.interviews.remove(&ieee_address) else {
+            return;
+        };
+
+        warn!(
+            "Interview of {ieee_address} failed at {}.",
+            interview.step()
+        );
+        emit(
+            &self.events,
+            InterviewEvent::Failed {
+                node_id: interview.node_id(),
+                ieee_address,
+                step: interview.step(),
+            },
+        )
+        .await;
+    }
+}
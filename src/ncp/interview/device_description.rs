@@ -0,0 +1,51 @@
+use crate::ember::{Eui64, NodeId};
+use crate::zdp::{NodeDescriptor, SimpleDescriptor};
+
+/// The description of a device gathered by an interview.
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub struct DeviceDescription {
+    pub(super) node_id: NodeId,
+    pub(super) ieee_address: Eui64,
+    pub(super) node_descriptor: NodeDescriptor,
+    pub(super) endpoints: Box<[SimpleDescriptor]>,
+    pub(super) manufacturer_name: Option<String>,
+    pub(super) model_identifier: Option<String>,
+}
+
+impl DeviceDescription {
+    /// Returns the network address of the device.
+    #[must_use]
+    pub const fn node_id(&self) -> NodeId {
+        self.node_id
+    }
+
+    /// Returns the IEEE address of the device.
+    #[must_use]
+    pub const fn ieee_address(&self) -> Eui64 {
+        self.ieee_address
+    }
+
+    /// Returns the node descriptor.
+    #[must_use]
+    pub const fn node_descriptor(&self) -> &NodeDescriptor {
+        &self.node_descriptor
+    }
+
+    /// Returns the simple descriptors of the device's active endpoints.
+    #[must_use]
+    pub fn endpoints(&self) -> &[SimpleDescriptor] {
+        &self.endpoints
+    }
+
+    /// Returns the manufacturer name from the Basic cluster, if available.
+    #[must_use]
+    pub fn manufacturer_name(&self) -> Option<&str> {
+        self.manufacturer_name.as_deref()
+    }
+
+    /// Returns the model identifier from the Basic cluster, if available.
+    #[must_use]
+    pub fn model_identifier(&self) -> Option<&str> {
+        self.model_identifier.as_deref()
+    }
+}
@@ -0,0 +1,27 @@
+use crate::ember::{Eui64, NodeId};
+use crate::ncp::interview::{DeviceDescription, InterviewStep};
+
+/// Interview progress reported by an [`Interviewer`](crate::Interviewer).
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub enum InterviewEvent {
+    /// A joined device is being interviewed.
+    Started {
+        /// The network address of the device.
+        node_id: NodeId,
+        /// The IEEE address of the device.
+        ieee_address: Eui64,
+    },
+
+    /// A device was interviewed completely.
+    Completed(Box<DeviceDescription>),
+
+    /// A device could not be interviewed before the deadline or within the allowed attempts.
+    Failed {
+        /// The network address of the device.
+        node_id: NodeId,
+        /// The IEEE address of the device.
+        ieee_address: Eui64,
+        /// The step that did not complete.
+        step: InterviewStep,
+    },
+}
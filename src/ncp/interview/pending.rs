@@ -0,0 +1,232 @@
+use std::collections::VecDeque;
+
+use log::trace;
+use tokio::time::Instant;
+
+use crate::Error;
+use crate::ember::aps::Options;
+use crate::ember::{Eui64, NodeId};
+use crate::ncp::Ncp;
+use crate::ncp::interview::{DeviceDescription, InterviewStep, Interviewer};
+use crate::zcl::general::{ReadAttributes, ReadAttributesResponse};
+use crate::zcl::{Direction, Frame, FrameType, HOME_AUTOMATION_PROFILE_ID, Header, Value, basic};
+use crate::zdp::{
+    ActiveEndpointsRequest, NodeDescriptor, NodeDescriptorRequest, SimpleDescriptor,
+    SimpleDescriptorRequest,
+};
+
+/// The progress of a single device interview.
+#[derive(Debug)]
+pub struct PendingInterview {
+    node_id: NodeId,
+    ieee_address: Eui64,
+    step: InterviewStep,
+    attempts: u8,
+    transaction_sequence: Option<u8>,
+    step_deadline: Instant,
+    deadline: Instant,
+    node_descriptor: Option<NodeDescriptor>,
+    pending_endpoints: VecDeque<u8>,
+    endpoints: Vec<SimpleDescriptor>,
+    manufacturer_name: Option<String>,
+    model_identifier: Option<String>,
+}
+
+impl PendingInterview {
+    pub fn new(
+        node_id: NodeId,
+        ieee_address: Eui64,
+        interviewer: &Interviewer,
+        now: Instant,
+    ) -> Self {
+        Self {
+            node_id,
+            ieee_address,
+            step: InterviewStep::Announcement,
+            attempts: 0,
+            transaction_sequence: None,
+            step_deadline: now + interviewer.announce_timeout(),
+            deadline: now + interviewer.deadline(),
+            node_descriptor: None,
+            pending_endpoints: VecDeque::new(),
+            endpoints: Vec::new(),
+            manufacturer_name: None,
+            model_identifier: None,
+        }
+    }
+
+    pub const fn node_id(&self) -> NodeId {
+        self.node_id
+    }
+
+    pub const fn set_node_id(&mut self, node_id: NodeId) {
+        self.node_id = node_id;
+    }
+
+    pub const fn ieee_address(&self) -> Eui64 {
+        self.ieee_address
+    }
+
+    pub const fn step(&self) -> InterviewStep {
+        self.step
+    }
+
+    pub const fn attempts(&self) -> u8 {
+        self.attempts
+    }
+
+    pub const fn deadline(&self) -> Instant {
+        self.deadline
+    }
+
+    /// Returns the instant at which the interview needs attention without a response.
+    pub fn next_deadline(&self) -> Instant {
+        self.step_deadline.min(self.deadline)
+    }
+
+    /// Returns whether a response from `node_id` answers the outstanding request.
+    pub fn awaits(&self, node_id: NodeId, transaction_sequence: u8) -> bool {
+        self.node_id == node_id && self.transaction_sequence == Some(transaction_sequence)
+    }
+
+    pub const fn set_node_descriptor(&mut self, node_descriptor: NodeDescriptor) {
+        self.node_descriptor = Some(node_descriptor);
+    }
+
+    pub fn set_active_endpoints(&mut self, endpoints: &[u8]) {
+        self.pending_endpoints = endpoints.iter().copied().collect();
+    }
+
+    pub fn add_endpoint(&mut self, descriptor: SimpleDescriptor) {
+        self.endpoints.push(descriptor);
+    }
+
+    pub fn set_basic_attributes(&mut self, response: &ReadAttributesResponse) {
+        self.manufacturer_name = character_string(response, basic::MANUFACTURER_NAME);
+        self.model_identifier = character_string(response, basic::MODEL_IDENTIFIER);
+    }
+
+    /// Moves on to the step following the current one.
+    ///
+    /// Returns `false` if the interview is complete.
+    pub fn advance(&mut self) -> bool {
+        let step = match self.step {
+            InterviewStep::Announcement => Some(InterviewStep::NodeDescriptor),
+            InterviewStep::NodeDescriptor => Some(InterviewStep::ActiveEndpoints),
+            InterviewStep::ActiveEndpoints | InterviewStep::SimpleDescriptor { .. } => self
+                .pending_endpoints
+                .pop_front()
+                .map(|endpoint| InterviewStep::SimpleDescriptor { endpoint })
+                .or_else(|| {
+                    self.basic_endpoint()
+                        .map(|descriptor| InterviewStep::BasicAttributes {
+                            endpoint: descriptor.endpoint(),
+                        })
+                }),
+            InterviewStep::BasicAttributes { .. } => None,
+        };
+
+        let Some(step) = step else {
+            return false;
+        };
+
+        trace!("Interview of {} advancing to {step}", self.ieee_address);
+        self.step = step;
+        self.attempts = 0;
+        self.transaction_sequence = None;
+        true
+    }
+
+    /// Sends the request of the current step.
+    pub async fn send(
+        &mut self,
+        ncp: &mut Ncp,
+        interviewer: &Interviewer,
+        now: Instant,
+    ) -> Result<(), Error> {
+        self.attempts = self.attempts.saturating_add(1);
+        self.step_deadline = now + interviewer.response_timeout();
+        let node_id = self.node_id;
+        let (transaction_sequence, stack_response) = match self.step {
+            InterviewStep::Announcement => return Ok(()),
+            InterviewStep::NodeDescriptor => {
+                ncp.zdp_request(node_id, NodeDescriptorRequest::new(node_id), Options::RETRY)
+                    .await?
+            }
+            InterviewStep::ActiveEndpoints => {
+                ncp.zdp_request(
+                    node_id,
+                    ActiveEndpointsRequest::new(node_id),
+                    Options::RETRY,
+                )
+                .await?
+            }
+            InterviewStep::SimpleDescriptor { endpoint } => {
+                ncp.zdp_request(
+                    node_id,
+                    SimpleDescriptorRequest::new(node_id, endpoint),
+                    Options::RETRY,
+                )
+                .await?
+            }
+            InterviewStep::BasicAttributes { endpoint } => {
+                let profile_id = self
+                    .basic_endpoint()
+                    .map_or(HOME_AUTOMATION_PROFILE_ID, SimpleDescriptor::profile_id);
+                let transaction_sequence = ncp.next_transaction_sequence();
+                let frame = Frame::new(
+                    Header::new(
+                        FrameType::Global,
+                        Direction::ClientToServer,
+                        true,
+                        transaction_sequence,
+                        ReadAttributes::ID,
+                    ),
+                    ReadAttributes::new([basic::MANUFACTURER_NAME, basic::MODEL_IDENTIFIER]),
+                );
+                let stack_response = ncp
+                    .unicast(
+                        node_id,
+                        profile_id,
+                        basic::CLUSTER_ID,
+                        endpoint,
+                        frame.to_bytes(),
+                        Options::RETRY,
+                    )
+                    .await?;
+                (transaction_sequence, stack_response)
+            }
+        };
+
+        drop(stack_response);
+        self.transaction_sequence.replace(transaction_sequence);
+        Ok(())
+    }
+
+    /// Returns the description of the interviewed device.
+    ///
+    /// Returns `None` if the node descriptor was not read.
+    pub fn into_description(self) -> Option<DeviceDescription> {
+        Some(DeviceDescription {
+            node_id: self.node_id,
+            ieee_address: self.ieee_address,
+            node_descriptor: self.node_descriptor?,
+            endpoints: self.endpoints.into_boxed_slice(),
+            manufacturer_name: self.manufacturer_name,
+            model_identifier: self.model_identifier,
+        })
+    }
+
+    fn basic_endpoint(&self) -> Option<&SimpleDescriptor> {
+        self.endpoints
+            .iter()
+            .find(|descriptor| descriptor.input_clusters().contains(&basic::CLUSTER_ID))
+    }
+}
+
+fn character_string(response: &ReadAttributesResponse, attribute_id: u16) -> Option<String> {
+    match response.value(attribute_id)? {
+        Value::CharacterString(string) => Some(string.clone()),
+        _ => None,
+    }
+}
@@ -0,0 +1,38 @@
+use std::fmt::Display;
+
+/// A step of a device interview.
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub enum InterviewStep {
+    /// Waiting for the device's announcement after it joined.
+    Announcement,
+    /// Reading the node descriptor.
+    NodeDescriptor,
+    /// Reading the active endpoints.
+    ActiveEndpoints,
+    /// Reading the simple descriptor of an endpoint.
+    SimpleDescriptor {
+        /// The endpoint.
+        endpoint: u8,
+    },
+    /// Reading the manufacturer name and model identifier from the Basic cluster.
+    BasicAttributes {
+        /// The endpoint implementing the Basic cluster server.
+        endpoint: u8,
+    },
+}
+
+impl Display for InterviewStep {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Announcement => write!(f, "device announcement"),
+            Self::NodeDescriptor => write!(f, "node descriptor"),
+            Self::ActiveEndpoints => write!(f, "active endpoints"),
+            Self::SimpleDescriptor { endpoint } => {
+                write!(f, "simple descriptor of endpoint {endpoint}")
+            }
+            Self::BasicAttributes { endpoint } => {
+                write!(f, "basic attributes of endpoint {endpoint}")
+            }
+        }
+    }
+}
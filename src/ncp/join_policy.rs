@@ -0,0 +1,167 @@
+//! Trust center join policies.
+
+use log::{debug, info, warn};
+use tokio::sync::mpsc::{Receiver, Sender, channel};
+
+pub use self::event::JoinPolicyEvent;
+pub use self::join_rules::JoinRules;
+pub use self::rejection::JoinRejection;
+use crate::ember::device::Update;
+use crate::ember::{Eui64, NodeId};
+use crate::frame::parameters::trust_center::handler::Handler as TrustCenterHandler;
+use crate::ncp::service::{MESSAGES_CAPACITY, emit};
+use crate::ncp::{Message, Ncp};
+use crate::parameters::trust_center::handler::TrustCenterJoin;
+use crate::{Callback, Error, TrustCenter, Utilities};
+
+mod event;
+mod join_rules;
+mod rejection;
+
+/// The network address of the coordinator, which is the trust center.
+const COORDINATOR: NodeId = 0x0000;
+
+/// Decides whether a device may stay on the network after joining.
+///
+/// The policy is consulted for every `trustCenterJoin` callback reporting a
+/// join or rejoin. Returning a [`JoinRejection`] removes the device from the
+/// network. Closures with a matching signature implement the trait, and
+/// [`JoinRules`] provides the common rules.
+pub trait JoinPolicy {
+    /// Returns whether the device reported by `join` is accepted.
+    ///
+    /// # Errors
+    ///
+    /// Returns the reason if the device is rejected.
+    fn evaluate(&mut self, join: &TrustCenterJoin) -> Result<(), JoinRejection>;
+}
+
+impl<F> JoinPolicy for F
+where
+    F: FnMut(&TrustCenterJoin) -> Result<(), JoinRejection>,
+{
+    fn evaluate(&mut self, join: &TrustCenterJoin) -> Result<(), JoinRejection> {
+        self(join)
+    }
+}
+
+impl Ncp {
+    /// Starts enforcing a join policy.
+    ///
+    /// The event handler copies all callbacks to the returned future, which
+    /// consults `policy` on every trust center join and rejoin. Rejected
+    /// devices are removed with [`TrustCenter::remove_device`] via their
+    /// parent, whose EUI64 is looked up in the [device registry](Self::devices).
+    /// Each decision is reported to `events`. The future completes when the
+    /// event handler stops.
+    ///
+    /// The NCP still decides how a device joins, so the device may briefly be
+    /// on the network before it is removed.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`Error`] if the NCP's EUI64 cannot be read or the callback
+    /// subscription cannot be registered with the event handler.
+    pub async fn serve_join_policy<P>(
+        &mut self,
+        policy: P,
+        events: Sender<JoinPolicyEvent>,
+    ) -> Result<impl Future<Output = ()> + Send + 'static, Error>
+    where
+        P: JoinPolicy + Send + 'static,
+    {
+        let eui64 = self.connection.get_eui64().await?;
+        let (sender, callbacks) = channel(MESSAGES_CAPACITY);
+        self.event_handler_handle
+            .send(Message::Callbacks { sender })
+            .await?;
+
+        let session = Session {
+            ncp: self.clone(),
+            eui64,
+            policy,
+            events,
+        };
+
+        Ok(session.run(callbacks))
+    }
+}
+
+struct Session<P> {
+    ncp: Ncp,
+    eui64: Eui64,
+    policy: P,
+    events: Sender<JoinPolicyEvent>,
+}
+
+impl<P> Session<P>
+where
+    P: JoinPolicy,
+{
+    async fn run(mut self, mut callbacks: Receiver<Callback>) {
+        while let Some(callback) = callbacks.recv().await {
+            if let Callback::TrustCenter(TrustCenterHandler::TrustCenterJoin(join)) = callback
+                && join.status() != Ok(Update::DeviceLeft)
+            {
+                self.decide(&join).await;
+            }
+        }
+
+        debug!("Callback subscription closed. Join policy terminating.");
+    }
+
+    async fn decide(&mut self, join: &TrustCenterJoin) {
+        let node_id = join.new_node_id();
+        let ieee_address = join.new_node_eui64();
+
+        match self.policy.evaluate(join) {
+            Ok(()) => {
+                debug!("Accepted device {ieee_address} ({node_id:#06X}).");
+                emit(
+                    &self.events,
+                    JoinPolicyEvent::Accepted {
+                        node_id,
+                        ieee_address,
+                    },
+                )
+                .await;
+            }
+            Err(reason) => {
+                info!("Rejecting device {ieee_address} ({node_id:#06X}): {reason}");
+                self.remove(join.parent_of_new_node_id(), ieee_address)
+                    .await;
+                emit(
+                    &self.events,
+                    JoinPolicyEvent::Rejected {
+                        node_id,
+                        ieee_address,
+                        reason,
+                    },
+                )
+                .await;
+            }
+        }
+    }
+
+    async fn remove(&mut self, parent: NodeId, ieee_address: Eui64) {
+        let parent_eui64 = if parent == COORDINATOR {
+            Some(self.eui64)
+        } else {
+            self.ncp.devices().ieee_address(parent)
+        };
+
+        let Some(parent_eui64) = parent_eui64 else {
+            warn!("Cannot remove {ieee_address}: unknown parent {parent:#06X}");
+            return;
+        };
+
+        if let Err(error) = self
+            .ncp
+            .connection
+            .remove_device(parent, parent_eui64, ieee_address)
+            .await
+        {
+            warn!("Failed to remove {ieee_address}: {error}");
+        }
+    }
+}
@@ -0,0 +1,24 @@
+use crate::ember::{Eui64, NodeId};
+use crate::ncp::join_policy::JoinRejection;
+
+/// Join decisions reported by [`Ncp::serve_join_policy`](crate::Ncp::serve_join_policy).
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum JoinPolicyEvent {
+    /// A device was accepted.
+    Accepted {
+        /// The network address of the device.
+        node_id: NodeId,
+        /// The IEEE address of the device.
+        ieee_address: Eui64,
+    },
+
+    /// A device was rejected and removed from the network.
+    Rejected {
+        /// The network address of the device.
+        node_id: NodeId,
+        /// The IEEE address of the device.
+        ieee_address: Eui64,
+        /// The reason for the rejection.
+        reason: JoinRejection,
+    },
+}
@@ -0,0 +1,283 @@
+use std::collections::BTreeSet;
+use std::sync::{Arc, Mutex, MutexGuard};
+
+use log::warn;
+use tokio::time::Instant;
+
+use crate::ember::device::Update;
+use crate::ember::{Eui64, NodeId};
+use crate::ncp::Ncp;
+use crate::ncp::join_policy::{JoinPolicy, JoinRejection};
+use crate::ncp::join_window::OpenWindows;
+use crate::ncp::service::lock;
+use crate::parameters::trust_center::handler::TrustCenterJoin;
+
+/// Length of an organizationally unique identifier.
+const OUI_LENGTH: usize = 3;
+
+/// Configurable join rules.
+///
+/// Each rule is disabled until it is configured with the corresponding `with_`
+/// method, and a device is accepted only if all configured rules accept it.
+/// The rules are checked in this order:
+///
+/// 1. the deny-list,
+/// 2. the allow-list,
+/// 3. the manufacturer filter, matching the organizationally unique
+///    identifier in the first three bytes of the device's EUI64,
+/// 4. install-code-only, accepting devices registered with
+///    [`add_install_code_device`](Self::add_install_code_device), and
+/// 5. join-window-only, accepting new joins only while a window opened with
+///    [`Ncp::open_join_window`] or [`Ncp::commission_install_code`] lasts.
+///    A window on a single router admits only devices joining through that
+///    router. Rejoins are not affected by the join window.
+///
+/// Clones share the same rules, so the lists can be updated while the policy
+/// is being served.
+#[derive(Clone, Debug, Default)]
+pub struct JoinRules {
+    rules: Arc<Mutex<Rules>>,
+}
+
+impl JoinRules {
+    /// Rejects the given devices.
+    #[must_use]
+    pub fn with_deny_list(self, devices: impl IntoIterator<Item = Eui64>) -> Self {
+        self.lock().denied.extend(devices);
+        self
+    }
+
+    /// Accepts only the given devices.
+    #[must_use]
+    pub fn with_allow_list(self, devices: impl IntoIterator<Item = Eui64>) -> Self {
+        self.lock().allowed.get_or_insert_default().extend(devices);
+        self
+    }
+
+    /// Accepts only devices whose EUI64 starts with one of the given organizationally unique identifiers.
+    #[must_use]
+    pub fn with_manufacturers(self, ouis: impl IntoIterator<Item = [u8; OUI_LENGTH]>) -> Self {
+        self.lock()
+            .manufacturers
+            .get_or_insert_default()
+            .extend(ouis);
+        self
+    }
+
+    /// Accepts only the given devices commissioned with an install code.
+    #[must_use]
+    pub fn with_install_code_only(self, devices: impl IntoIterator<Item = Eui64>) -> Self {
+        self.lock()
+            .install_codes
+            .get_or_insert_default()
+            .extend(devices);
+        self
+    }
+
+    /// Accepts new joins only while a join window of `ncp` is open.
+    ///
+    /// The rule consults the windows opened with [`Ncp::open_join_window`] or
+    /// [`Ncp::commission_install_code`] on any clone of `ncp`.
+    #[must_use]
+    pub fn with_join_window_only(self, ncp: &Ncp) -> Self {
+        self.lock().join_window = Some(ncp.join_windows.clone());
+        self
+    }
+
+    /// Adds a device to the deny-list.
+    pub fn deny(&self, ieee_address: Eui64) {
+        self.lock().denied.insert(ieee_address);
+    }
+
+    /// Adds a device to the allow-list, enabling the allow-list if necessary.
+    pub fn allow(&self, ieee_address: Eui64) {
+        let mut rules = self.lock();
+        rules.denied.remove(&ieee_address);
+        rules.allowed.get_or_insert_default().insert(ieee_address);
+    }
+
+    /// Registers a device commissioned with an install code.
+    ///
+    /// This has no effect on the decision unless install-code-only is
+    /// configured, which is logged.
+    pub fn add_install_code_device(&self, ieee_address: Eui64) {
+        if let Some(install_codes) = &mut self.lock().install_codes {
+            install_codes.insert(ieee_address);
+        } else {
+            warn!(
+                "Install-code-only is not configured. Ignoring install-code device {ieee_address}."
+            );
+        }
+    }
+
+    fn lock(&self) -> MutexGuard<'_, Rules> {
+        lock(&self.rules)
+    }
+}
+
+impl JoinPolicy for JoinRules {
+    fn evaluate(&mut self, join: &TrustCenterJoin) -> Result<(), JoinRejection> {
+        self.lock().evaluate(
+            join.new_node_eui64(),
+            join.parent_of_new_node_id(),
+            join.status() == Ok(Update::StandardSecurityUnsecuredJoin),
+            Instant::now(),
+        )
+    }
+}
+
+#[derive(Debug, Default)]
+struct Rules {
+    denied: BTreeSet<Eui64>,
+    allowed: Option<BTreeSet<Eui64>>,
+    manufacturers: Option<BTreeSet<[u8; OUI_LENGTH]>>,
+    install_codes: Option<BTreeSet<Eui64>>,
+    join_window: Option<OpenWindows>,
+}
+
+impl Rules {
+    fn evaluate(
+        &self,
+        ieee_address: Eui64,
+        parent: NodeId,
+        new_join: bool,
+        now: Instant,
+    ) -> Result<(), JoinRejection> {
+        if self.denied.contains(&ieee_address) {
+            return Err(JoinRejection::Denied);
+        }
+
+        if let Some(allowed) = &self.allowed
+            && !allowed.contains(&ieee_address)
+        {
+            return Err(JoinRejection::NotAllowed);
+        }
+
+        if let Some(manufacturers) = &self.manufacturers
+            && !manufacturers
+                .iter()
+                .any(|oui| ieee_address.as_bytes().starts_with(oui))
+        {
+            return Err(JoinRejection::Manufacturer);
+        }
+
+        if let Some(install_codes) = &self.install_codes
+            && !install_codes.contains(&ieee_address)
+        {
+            return Err(JoinRejection::InstallCodeRequired);
+        }
+
+        if new_join
+            && let Some(join_window) = &self.join_window
+            && !join_window.admits(parent, now)
+        {
+            return Err(JoinRejection::JoinWindowClosed);
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use tokio::time::Instant;
+
+    use super::JoinRules;
+    use crate::ember::{Eui64, NodeId};
+    use crate::ncp::join_policy::JoinRejection;
+    use crate::ncp::join_window::{JoinWindowTarget, OpenWindows};
+
+    const DEVICE: Eui64 = Eui64::new(0x00, 0x0B, 0x57, 0x01, 0x02, 0x03, 0x04, 0x05);
+    const OTHER: Eui64 = Eui64::new(0x00, 0x17, 0x88, 0x01, 0x02, 0x03, 0x04, 0x05);
+    const COORDINATOR: NodeId = 0x0000;
+    const ROUTER: NodeId = 0x1234;
+
+    #[test]
+    fn rules_are_checked_in_order() {
+        let rules = JoinRules::default()
+            .with_allow_list([DEVICE])
+            .with_manufacturers([[0x00, 0x0B, 0x57]]);
+        let now = Instant::now();
+        assert_eq!(
+            rules.lock().evaluate(DEVICE, COORDINATOR, true, now),
+            Ok(())
+        );
+        assert_eq!(
+            rules.lock().evaluate(OTHER, COORDINATOR, true, now),
+            Err(JoinRejection::NotAllowed)
+        );
+
+        rules.allow(OTHER);
+        assert_eq!(
+            rules.lock().evaluate(OTHER, COORDINATOR, true, now),
+            Err(JoinRejection::Manufacturer)
+        );
+
+        rules.deny(DEVICE);
+        assert_eq!(
+            rules.lock().evaluate(DEVICE, COORDINATOR, true, now),
+            Err(JoinRejection::Denied)
+        );
+    }
+
+    #[test]
+    fn join_window_applies_to_new_joins() {
+        let windows = OpenWindows::default();
+        let rules = JoinRules::default();
+        rules.lock().join_window = Some(windows.clone());
+        let now = Instant::now();
+        assert_eq!(
+            rules.lock().evaluate(DEVICE, COORDINATOR, true, now),
+            Err(JoinRejection::JoinWindowClosed)
+        );
+        assert_eq!(
+            rules.lock().evaluate(DEVICE, COORDINATOR, false, now),
+            Ok(())
+        );
+
+        let id = windows.open(JoinWindowTarget::Network, now + Duration::from_mins(1), now);
+        assert_eq!(
+            rules
+                .lock()
+                .evaluate(DEVICE, COORDINATOR, true, Instant::now()),
+            Ok(())
+        );
+        assert_eq!(
+            rules.lock().evaluate(
+                DEVICE,
+                COORDINATOR,
+                true,
+                Instant::now() + Duration::from_mins(2)
+            ),
+            Err(JoinRejection::JoinWindowClosed)
+        );
+
+        windows.close(id);
+        assert_eq!(
+            rules
+                .lock()
+                .evaluate(DEVICE, COORDINATOR, true, Instant::now()),
+            Err(JoinRejection::JoinWindowClosed)
+        );
+    }
+
+    #[test]
+    fn router_window_admits_only_its_children() {
+        let windows = OpenWindows::default();
+        let rules = JoinRules::default();
+        rules.lock().join_window = Some(windows.clone());
+        let now = Instant::now();
+        windows.open(
+            JoinWindowTarget::Router(ROUTER),
+            now + Duration::from_mins(1),
+            now,
+        );
+        assert_eq!(rules.lock().evaluate(DEVICE, ROUTER, true, now), Ok(()));
+        assert_eq!(
+            rules.lock().evaluate(DEVICE, COORDINATOR, true, now),
+            Err(JoinRejection::JoinWindowClosed)
+        );
+    }
+}
@@ -0,0 +1,31 @@
+use std::fmt::Display;
+
+/// The reason a [`JoinPolicy`](crate::JoinPolicy) rejected a device.
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub enum JoinRejection {
+    /// The device is on the deny-list.
+    Denied,
+    /// The device is not on the allow-list.
+    NotAllowed,
+    /// The organizationally unique identifier of the device's EUI64 is not allowed.
+    Manufacturer,
+    /// The device was not commissioned with an install code.
+    InstallCodeRequired,
+    /// The device joined while no join window was open.
+    JoinWindowClosed,
+    /// A custom policy rejected the device.
+    Custom(&'static str),
+}
+
+impl Display for JoinRejection {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Denied => write!(f, "device is denied"),
+            Self::NotAllowed => write!(f, "device is not allowed"),
+            Self::Manufacturer => write!(f, "manufacturer is not allowed"),
+            Self::InstallCodeRequired => write!(f, "install code required"),
+            Self::JoinWindowClosed => write!(f, "join window closed"),
+            Self::Custom(reason) => write!(f, "{reason}"),
+        }
+    }
+}
@@ -0,0 +1,213 @@
+//! Network-wide join windows.
+
+use std::time::Duration;
+
+use log::{debug, info, warn};
+use tokio::sync::mpsc::Sender;
+use tokio::sync::oneshot::{self, Receiver};
+use tokio::time::{Instant, timeout_at};
+
+pub use self::event::JoinWindowEvent;
+pub use self::open_windows::OpenWindows;
+pub use self::target::JoinWindowTarget;
+use crate::ember::NodeId;
+use crate::ember::aps::Options;
+use crate::ncp::Ncp;
+use crate::ncp::service::emit;
+use crate::zdp::MgmtPermitJoiningRequest;
+use crate::{Error, Networking};
+
+mod event;
+mod open_windows;
+mod target;
+
+/// The longest permit duration that does not open joining indefinitely.
+const MAX_PERMIT_DURATION: u8 = 0xFE;
+/// Time before a permit expires at which it is renewed.
+const RENEWAL_MARGIN: Duration = Duration::from_secs(10);
+/// Broadcast address of the coordinator and all routers.
+const ROUTERS: NodeId = 0xFFFC;
+/// Broadcast radius of zero, which the stack replaces with its maximum radius.
+const MAX_RADIUS: u8 = 0;
+
+/// Handle of an open join window.
+///
+/// Dropping the handle closes the window like [`close`](Self::close).
+#[derive(Debug)]
+pub struct JoinWindow {
+    close: oneshot::Sender<()>,
+}
+
+impl JoinWindow {
+    /// Closes the join window before its duration elapses.
+    pub fn close(self) {
+        // The window is already closed if the receiver is gone.
+        self.close.send(()).ok();
+    }
+}
+
+impl Ncp {
+    /// Permits devices to join for the given duration.
+    ///
+    /// Joining is opened with a ZDO Mgmt Permit Joining Request, which is
+    /// broadcast to all routers together with a local
+    /// [`Networking::permit_joining`] for [`JoinWindowTarget::Network`], or
+    /// sent to a single router for [`JoinWindowTarget::Router`]. Each request
+    /// permits joining for at most 254 seconds, so the returned future renews
+    /// the permit shortly before it expires until `duration` has elapsed.
+    ///
+    /// The returned [`JoinWindow`] closes the window early, in which case the
+    /// future permits joining for zero seconds. Opening and closing are
+    /// reported to `events`, and the future completes when the window closes.
+    /// While the window is open, [`is_join_window_open`](Self::is_join_window_open)
+    /// returns `true` and [`JoinRules`](crate::JoinRules) configured with
+    /// [`with_join_window_only`](crate::JoinRules::with_join_window_only)
+    /// accept new joins through the window's target.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`Error`] if joining cannot be permitted initially. Failed
+    /// renewals are logged.
+    pub async fn open_join_window(
+        &mut self,
+        target: JoinWindowTarget,
+        duration: Duration,
+        events: Sender<JoinWindowEvent>,
+    ) -> Result<(JoinWindow, impl Future<Output = ()> + Send + 'static), Error> {
+        let opened = Instant::now();
+        let mut session = Session {
+            ncp: self.clone(),
+            target,
+            events,
+            opened,
+            end: opened + duration,
+            id: None,
+        };
+        session.permit(permit_duration(duration)).await?;
+        session.id = Some(self.join_windows.open(target, session.end, opened));
+        info!("Permitted joining on {target:?} for {duration:?}.");
+        emit(&session.events, JoinWindowEvent::Opened { duration }).await;
+
+        let (close, closed) = oneshot::channel();
+        Ok((JoinWindow { close }, session.run(closed)))
+    }
+
+    /// Returns whether a window opened with [`open_join_window`](Self::open_join_window) is open.
+    #[must_use]
+    pub fn is_join_window_open(&self) -> bool {
+        self.join_windows.is_open(Instant::now())
+    }
+}
+
+struct Session {
+    ncp: Ncp,
+    target: JoinWindowTarget,
+    events: Sender<JoinWindowEvent>,
+    opened: Instant,
+    end: Instant,
+    id: Option<u64>,
+}
+
+impl Session {
+    async fn run(mut self, mut closed: Receiver<()>) {
+        loop {
+            let renewal = self.renewal(Instant::now());
+
+            if timeout_at(renewal, &mut closed).await.is_ok() {
+                debug!("Closing join window on {:?} early.", self.target);
+
+                if let Err(error) = self.permit(0).await {
+                    warn!("Failed to close join window: {error}");
+                }
+
+                return self.close(true).await;
+            }
+
+            if renewal >= self.end {
+                return self.close(false).await;
+            }
+
+            let remaining = self.end.saturating_duration_since(Instant::now());
+            debug!("Renewing join window on {:?}.", self.target);
+
+            if let Err(error) = self.permit(permit_duration(remaining)).await {
+                warn!("Failed to renew join window: {error}");
+            }
+        }
+    }
+
+    /// Returns when the current permit must be renewed or the window ends.
+    fn renewal(&self, now: Instant) -> Instant {
+        let permit = Duration::from_secs(MAX_PERMIT_DURATION.into());
+
+        if self.end.saturating_duration_since(now) > permit {
+            now + permit - RENEWAL_MARGIN
+        } else {
+            self.end
+        }
+    }
+
+    async fn permit(&mut self, seconds: u8) -> Result<(), Error> {
+        let request = MgmtPermitJoiningRequest::new(seconds);
+
+        let (_, stack_response) = match self.target {
+            JoinWindowTarget::Network => {
+                self.ncp.connection.permit_joining(seconds.into()).await?;
+                self.ncp
+                    .zdp_broadcast(ROUTERS, MAX_RADIUS, request, Options::NONE)
+                    .await?
+            }
+            JoinWindowTarget::Router(node_id) => {
+                self.ncp
+                    .zdp_request(node_id, request, Options::RETRY)
+                    .await?
+            }
+        };
+
+        stack_response.await
+    }
+
+    async fn close(self, early: bool) {
+        if let Some(id) = self.id {
+            self.ncp.join_windows.close(id);
+        }
+
+        info!("Join window on {:?} closed.", self.target);
+        emit(
+            &self.events,
+            JoinWindowEvent::Closed {
+                elapsed: self.opened.elapsed(),
+                early,
+            },
+        )
+        .await;
+    }
+}
+
+/// Returns the permit duration in seconds, limited to a finite permit.
+fn permit_duration(duration: Duration) -> u8 {
+    u8::try_from(duration.as_secs()).map_or(MAX_PERMIT_DURATION, |seconds| {
+        seconds.min(MAX_PERMIT_DURATION)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::{MAX_PERMIT_DURATION, permit_duration};
+
+    #[test]
+    fn permit_duration_is_finite() {
+        assert_eq!(permit_duration(Duration::from_millis(1500)), 1);
+        assert_eq!(permit_duration(Duration::from_secs(254)), 254);
+        assert_eq!(
+            permit_duration(Duration::from_secs(255)),
+            MAX_PERMIT_DURATION
+        );
+        assert_eq!(
+            permit_duration(Duration::from_hours(1)),
+            MAX_PERMIT_DURATION
+        );
+    }
+}
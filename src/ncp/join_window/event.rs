@@ -0,0 +1,19 @@
+use std::time::Duration;
+
+/// Join window changes reported by [`Ncp::open_join_window`](crate::Ncp::open_join_window).
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum JoinWindowEvent {
+    /// Joining was permitted.
+    Opened {
+        /// The time for which joining is permitted.
+        duration: Duration,
+    },
+
+    /// Joining is no longer permitted.
+    Closed {
+        /// The time for which joining was permitted.
+        elapsed: Duration,
+        /// Whether the window was closed before its duration elapsed.
+        early: bool,
+    },
+}
@@ -0,0 +1,70 @@
+use std::collections::BTreeMap;
+use std::sync::{Arc, Mutex, MutexGuard};
+
+use tokio::time::Instant;
+
+use crate::ember::NodeId;
+use crate::ncp::join_window::JoinWindowTarget;
+use crate::ncp::service::lock;
+
+/// The join windows opened with [`Ncp::open_join_window`](crate::Ncp::open_join_window).
+///
+/// Each window is registered with its target and end until it closes. Clones
+/// share the registered windows, so every `Ncp` clone and the
+/// [`JoinRules`](crate::JoinRules) consulting them see the same state.
+#[derive(Clone, Debug, Default)]
+pub struct OpenWindows {
+    windows: Arc<Mutex<Windows>>,
+}
+
+impl OpenWindows {
+    /// Registers a window on `target` lasting until `end` and returns its ID.
+    pub fn open(&self, target: JoinWindowTarget, end: Instant, now: Instant) -> u64 {
+        let mut windows = self.lock();
+        windows.open.retain(|_, window| window.end > now);
+        let id = windows.next_id;
+        windows.next_id = id.wrapping_add(1);
+        windows.open.insert(id, Window { target, end });
+        id
+    }
+
+    /// Removes the window with the given ID.
+    pub fn close(&self, id: u64) {
+        self.lock().open.remove(&id);
+    }
+
+    /// Returns whether a window is open at `now`.
+    pub fn is_open(&self, now: Instant) -> bool {
+        self.lock().open.values().any(|window| window.end > now)
+    }
+
+    /// Returns whether a window open at `now` admits devices joining through `parent`.
+    ///
+    /// Network-wide windows admit any parent, while a window on a single
+    /// router admits only devices joining through that router.
+    pub fn admits(&self, parent: NodeId, now: Instant) -> bool {
+        self.lock().open.values().any(|window| {
+            window.end > now
+                && match window.target {
+                    JoinWindowTarget::Network => true,
+                    JoinWindowTarget::Router(node_id) => node_id == parent,
+                }
+        })
+    }
+
+    fn lock(&self) -> MutexGuard<'_, Windows> {
+        lock(&self.windows)
+    }
+}
+
+#[derive(Debug, Default)]
+struct Windows {
+    next_id: u64,
+    open: BTreeMap<u64, Window>,
+}
+
+#[derive(Debug)]
+struct Window {
+    target: JoinWindowTarget,
+    end: Instant,
+}
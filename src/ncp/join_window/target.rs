@@ -0,0 +1,11 @@
+use crate::ember::NodeId;
+
+/// The devices a join window is opened on.
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+pub enum JoinWindowTarget {
+    /// The coordinator and all routers.
+    #[default]
+    Network,
+    /// A single router, which new devices must join through.
+    Router(NodeId),
+}
@@ -0,0 +1,389 @@
+//! Minimal JSON reader and writer for the backup and topology exports.
+
+use std::fmt::{self, Display, Formatter, Write};
+use std::io::{self, ErrorKind};
+use std::iter::Peekable;
+use std::str::Chars;
+
+/// Maximum nesting depth of arrays and objects.
+const MAX_DEPTH: usize = 32;
+
+/// A parsed JSON value.
+///
+/// Numbers keep their textual representation and are converted on access.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Json {
+    Null,
+    Bool(bool),
+    Number(String),
+    String(String),
+    Array(Vec<Self>),
+    Object(Vec<(String, Self)>),
+}
+
+impl Json {
+    /// Parses a JSON document.
+    pub fn parse(text: &str) -> io::Result<Self> {
+        let mut parser = Parser {
+            chars: text.chars().peekable(),
+        };
+        let value = parser.value(0)?;
+        parser.skip_whitespace();
+
+        if parser.chars.next().is_some() {
+            return Err(invalid("trailing characters after JSON document"));
+        }
+
+        Ok(value)
+    }
+
+    /// Returns the member `key` of an object.
+    pub fn get(&self, key: &str) -> Option<&Self> {
+        match self {
+            Self::Object(members) => members
+                .iter()
+                .find(|(name, _)| name == key)
+                .map(|(_, value)| value),
+            _ => None,
+        }
+    }
+
+    /// Returns the value of a string.
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            Self::String(string) => Some(string),
+            _ => None,
+        }
+    }
+
+    /// Returns the value of a non-negative integer.
+    pub fn as_u64(&self) -> Option<u64> {
+        match self {
+            Self::Number(number) => number.parse().ok(),
+            _ => None,
+        }
+    }
+
+    /// Returns the value of a boolean.
+    pub const fn as_bool(&self) -> Option<bool> {
+        match self {
+            Self::Bool(boolean) => Some(*boolean),
+            _ => None,
+        }
+    }
+
+    /// Returns the elements of an array.
+    pub fn as_array(&self) -> Option<&[Self]> {
+        match self {
+            Self::Array(elements) => Some(elements),
+            _ => None,
+        }
+    }
+}
+
+struct Parser<'a> {
+    chars: Peekable<Chars<'a>>,
+}
+
+impl Parser<'_> {
+    fn value(&mut self, depth: usize) -> io::Result<Json> {
+        if depth > MAX_DEPTH {
+            return Err(invalid("JSON document nested too deeply"));
+        }
+
+        self.skip_whitespace();
+
+        match self.chars.peek() {
+            Some('{') => self.object(depth),
+            Some('[') => self.array(depth),
+            Some('"') => self.string().map(Json::String),
+            Some('t') => self.literal("true", Json::Bool(true)),
+            Some('f') => self.literal("false", Json::Bool(false)),
+            Some('n') => self.literal("null", Json::Null),
+            Some('-' | '0'..='9') => Ok(self.number()),
+            Some(other) => Err(invalid(format!("unexpected character {other:?}"))),
+            None => Err(invalid("unexpected end of JSON document")),
+        }
+    }
+
+    fn object(&mut self, depth: usize) -> io::Result<Json> {
+        self.chars.next();
+        let mut members = Vec::new();
+        self.skip_whitespace();
+
+        if self.chars.next_if_eq(&'}').is_some() {
+            return Ok(Json::Object(members));
+        }
+
+        loop {
+            self.skip_whitespace();
+            let name = self.string()?;
+            self.skip_whitespace();
+            self.expect(':')?;
+            members.push((name, self.value(depth + 1)?));
+            self.skip_whitespace();
+
+            match self.chars.next() {
+                Some(',') => {}
+                Some('}') => return Ok(Json::Object(members)),
+                _ => return Err(invalid("expected ',' or '}' in object")),
+            }
+        }
+    }
+
+    fn array(&mut self, depth: usize) -> io::Result<Json> {
+        self.chars.next();
+        let mut elements = Vec::new();
+        self.skip_whitespace();
+
+        if self.chars.next_if_eq(&']').is_some() {
+            return Ok(Json::Array(elements));
+        }
+
+        loop {
+            elements.push(self.value(depth + 1)?);
+            self.skip_whitespace();
+
+            match self.chars.next() {
+                Some(',') => {}
+                Some(']') => return Ok(Json::Array(elements)),
+                _ => return Err(invalid("expected ',' or ']' in array")),
+            }
+        }
+    }
+
+    fn string(&mut self) -> io::Result<String> {
+        self.expect('"')?;
+        let mut string = String::new();
+
+        loop {
+            match self.chars.next() {
+                Some('"') => return Ok(string),
+                Some('\\') => string.push(self.escape()?),
+                Some(character) if u32::from(character) >= 0x20 => string.push(character),
+                Some(_) => return Err(invalid("control character in string")),
+                None => return Err(invalid("unterminated string")),
+            }
+        }
+    }
+
+    fn escape(&mut self) -> io::Result<char> {
+        match self.chars.next() {
+            Some('"') => Ok('"'),
+            Some('\\') => Ok('\\'),
+            Some('/') => Ok('/'),
+            Some('b') => Ok('\u{8}'),
+            Some('f') => Ok('\u{c}'),
+            Some('n') => Ok('\n'),
+            Some('r') => Ok('\r'),
+            Some('t') => Ok('\t'),
+            Some('u') => {
+                let high = self.code_unit()?;
+
+                if (0xD800..0xDC00).contains(&high) {
+                    self.expect('\\')?;
+                    self.expect('u')?;
+                    let low = self.code_unit()?;
+                    char::decode_utf16([high, low])
+                        .next()
+                        .and_then(Result::ok)
+                        .ok_or_else(|| invalid("invalid surrogate pair"))
+                } else {
+                    char::from_u32(high.into()).ok_or_else(|| invalid("invalid unicode escape"))
+                }
+            }
+            _ => Err(invalid("invalid escape sequence")),
+        }
+    }
+
+    fn code_unit(&mut self) -> io::Result<u16> {
+        let digits: String = self.chars.by_ref().take(4).collect();
+
+        if digits.len() != 4 {
+            return Err(invalid("truncated unicode escape"));
+        }
+
+        u16::from_str_radix(&digits, 16).map_err(|_| invalid("invalid unicode escape"))
+    }
+
+    fn number(&mut self) -> Json {
+        let mut number = String::new();
+
+        while let Some(character) = self
+            .chars
+            .next_if(|character| matches!(character, '-' | '+' | '.' | 'e' | 'E' | '0'..='9'))
+        {
+            number.push(character);
+        }
+
+        Json::Number(number)
+    }
+
+    fn literal(&mut self, literal: &str, value: Json) -> io::Result<Json> {
+        for expected in literal.chars() {
+            self.expect(expected)?;
+        }
+
+        Ok(value)
+    }
+
+    fn expect(&mut self, expected: char) -> io::Result<()> {
+        match self.chars.next() {
+            Some(character) if character == expected => Ok(()),
+            _ => Err(invalid(format!("expected {expected:?}"))),
+        }
+    }
+
+    fn skip_whitespace(&mut self) {
+        while self
+            .chars
+            .next_if(|character| matches!(character, ' ' | '\t' | '\n' | '\r'))
+            .is_some()
+        {}
+    }
+}
+
+/// Creates an error describing an invalid JSON document.
+pub fn invalid(message: impl Into<String>) -> io::Error {
+    io::Error::new(ErrorKind::InvalidData, message.into())
+}
+
+/// Writes the comma preceding the array element or object member at `index`.
+pub fn separator(f: &mut Formatter<'_>, index: usize) -> fmt::Result {
+    if index == 0 {
+        Ok(())
+    } else {
+        f.write_char(',')
+    }
+}
+
+/// Writes a value as a JSON string, escaping it as necessary.
+pub struct Quoted<T>(pub T);
+
+impl<T> Display for Quoted<T>
+where
+    T: Display,
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.write_char('"')?;
+        write!(Escaped(f), "{}", self.0)?;
+        f.write_char('"')
+    }
+}
+
+/// Writes an optional value, or `null` if it is absent.
+pub struct Optional<T>(pub Option<T>);
+
+impl<T> Display for Optional<T>
+where
+    T: Display,
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match &self.0 {
+            Some(value) => value.fmt(f),
+            None => f.write_str("null"),
+        }
+    }
+}
+
+/// Escapes the characters of a JSON string written through it.
+struct Escaped<'a, 'f>(&'a mut Formatter<'f>);
+
+impl Write for Escaped<'_, '_> {
+    fn write_str(&mut self, string: &str) -> fmt::Result {
+        for character in string.chars() {
+            match character {
+                '"' => self.0.write_str("\\\"")?,
+                '\\' => self.0.write_str("\\\\")?,
+                '\n' => self.0.write_str("\\n")?,
+                '\r' => self.0.write_str("\\r")?,
+                '\t' => self.0.write_str("\\t")?,
+                control if u32::from(control) < 0x20 => {
+                    write!(self.0, "\\u{:04x}", u32::from(control))?;
+                }
+                other => self.0.write_char(other)?,
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fmt::{self, Display, Formatter};
+
+    use super::{Json, Optional, Quoted, separator};
+
+    struct Array<'a>(&'a [Option<&'a str>]);
+
+    impl Display for Array<'_> {
+        fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+            write!(f, "[")?;
+
+            for (index, element) in self.0.iter().enumerate() {
+                separator(f, index)?;
+                write!(f, "{}", Optional(element.map(Quoted)))?;
+            }
+
+            write!(f, "]")
+        }
+    }
+
+    #[test]
+    fn writes_escaped_strings() {
+        assert_eq!(
+            Quoted("say \"hi\"\\\n\u{1}").to_string(),
+            r#""say \"hi\"\\\n\u0001""#
+        );
+    }
+
+    #[test]
+    fn writes_arrays_with_null() {
+        assert_eq!(Array(&[]).to_string(), "[]");
+        assert_eq!(
+            Array(&[Some("a"), None, Some("c")]).to_string(),
+            r#"["a",null,"c"]"#
+        );
+    }
+
+    #[test]
+    fn written_strings_parse_back() {
+        let text = "co\u{f6}rdinator \"1\"\t\\";
+        let written = Quoted(text).to_string();
+        assert_eq!(
+            Json::parse(&written).ok().as_ref().and_then(Json::as_str),
+            Some(text)
+        );
+    }
+
+    #[test]
+    fn parses_nested_document() {
+        let json = Json::parse(
+            r#"{"name": "co\u00f6rdinator\n", "values": [1, 2, true, null], "empty": {}}"#,
+        )
+        .expect("document should parse");
+
+        assert_eq!(
+            json.get("name").and_then(Json::as_str),
+            Some("coördinator\n")
+        );
+        assert_eq!(
+            json.get("values")
+                .and_then(Json::as_array)
+                .map(|values| values.iter().filter_map(Json::as_u64).sum::<u64>()),
+            Some(3)
+        );
+        assert_eq!(json.get("empty"), Some(&Json::Object(Vec::new())));
+    }
+
+    #[test]
+    fn rejects_malformed_documents() {
+        for document in [r#"{"a": 1"#, r#"{"a" 1}"#, "[1,]", "\"\\x\"", "{} {}"] {
+            assert!(
+                Json::parse(document).is_err(),
+                "{document} should not parse"
+            );
+        }
+    }
+}
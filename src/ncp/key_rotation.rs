@@ -0,0 +1,207 @@
+//! Network key rotation.
+
+use std::time::Duration;
+
+use log::{debug, info, warn};
+use rand::RngExt;
+use tokio::sync::mpsc::{Receiver, Sender, channel};
+use tokio::time::{Instant, sleep, timeout_at};
+
+pub use self::event::KeyRotationEvent;
+use crate::ember::key::Data;
+use crate::frame::parameters::security::handler::Handler as SecurityHandler;
+use crate::ncp::service::{MESSAGES_CAPACITY, emit};
+use crate::ncp::{Message, Ncp};
+use crate::{Callback, Error, Security, TrustCenter, ValueError};
+
+mod event;
+
+/// Timing of a network key rotation.
+///
+/// Start a rotation with [`Ncp::rotate_network_key`]. The trust center first
+/// broadcasts a new random network key, which devices store as their
+/// alternate key. After the [`dwell`](Self::dwell) period, it broadcasts the
+/// switch to the new key. Sleepy end devices receive both messages from their
+/// parents when they poll, so the dwell period should exceed the longest poll
+/// interval in the network.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct KeyRotation {
+    dwell: Duration,
+    switch_timeout: Duration,
+}
+
+impl KeyRotation {
+    /// Sets the time between distributing the next network key and switching to it.
+    #[must_use]
+    pub const fn with_dwell(mut self, dwell: Duration) -> Self {
+        self.dwell = dwell;
+        self
+    }
+
+    /// Sets the time to wait for the NCP to report the key switch.
+    #[must_use]
+    pub const fn with_switch_timeout(mut self, switch_timeout: Duration) -> Self {
+        self.switch_timeout = switch_timeout;
+        self
+    }
+
+    /// Returns the time between distributing the next network key and switching to it.
+    #[must_use]
+    pub const fn dwell(&self) -> Duration {
+        self.dwell
+    }
+
+    /// Returns the time to wait for the NCP to report the key switch.
+    #[must_use]
+    pub const fn switch_timeout(&self) -> Duration {
+        self.switch_timeout
+    }
+}
+
+impl Default for KeyRotation {
+    fn default() -> Self {
+        Self {
+            dwell: Duration::from_mins(1),
+            switch_timeout: Duration::from_secs(30),
+        }
+    }
+}
+
+impl Ncp {
+    /// Replaces the network key with a new random key.
+    ///
+    /// The key is generated by the host's thread-local, cryptographically
+    /// secure random-number generator and broadcast with
+    /// [`TrustCenter::broadcast_next_network_key`]. After the rotation's dwell
+    /// period, the switch is broadcast with
+    /// [`TrustCenter::broadcast_network_key_switch`]. The rotation then waits
+    /// for the NCP's `switchNetworkKey` callback and verifies the active key's
+    /// sequence number with [`Security::get_network_key_info`].
+    /// A missing callback is logged, since the key information is authoritative.
+    ///
+    /// Progress is reported to `events`. Returns the sequence number of the new
+    /// network key.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`Error`] if the NCP rejects a command, the callback
+    /// subscription cannot be registered, or the NCP does not report the
+    /// expected key sequence number after distributing or switching the key.
+    pub async fn rotate_network_key(
+        &mut self,
+        rotation: KeyRotation,
+        events: Sender<KeyRotationEvent>,
+    ) -> Result<u8, Error> {
+        let info = self.connection.get_network_key_info().await?;
+        let sequence_number = info.network_key_sequence_number().wrapping_add(1);
+
+        info!("Distributing network key with sequence number {sequence_number}.");
+        self.connection
+            .broadcast_next_network_key(random_key())
+            .await?;
+        let info = self.connection.get_network_key_info().await?;
+        verify(
+            sequence_number,
+            info.alternate_network_key_set()
+                .then(|| info.alt_network_key_sequence_number()),
+        )?;
+        emit(&events, KeyRotationEvent::Distributed { sequence_number }).await;
+
+        debug!(
+            "Waiting {:?} before switching the network key.",
+            rotation.dwell
+        );
+        sleep(rotation.dwell).await;
+
+        // Subscribe only now, so that callbacks do not queue up during the dwell period.
+        let (sender, mut callbacks) = channel(MESSAGES_CAPACITY);
+        self.event_handler_handle
+            .send(Message::Callbacks { sender })
+            .await?;
+        self.connection.broadcast_network_key_switch().await?;
+        emit(
+            &events,
+            KeyRotationEvent::SwitchBroadcast { sequence_number },
+        )
+        .await;
+
+        if await_switch(&mut callbacks, sequence_number, rotation.switch_timeout).await {
+            emit(&events, KeyRotationEvent::Switched { sequence_number }).await;
+        } else {
+            warn!("NCP did not report switching to network key {sequence_number}.");
+        }
+
+        let info = self.connection.get_network_key_info().await?;
+        verify(
+            sequence_number,
+            info.network_key_set()
+                .then(|| info.network_key_sequence_number()),
+        )?;
+        info!("Switched to network key with sequence number {sequence_number}.");
+        emit(&events, KeyRotationEvent::Completed { sequence_number }).await;
+        Ok(sequence_number)
+    }
+}
+
+/// Generates a network key.
+///
+/// The generator is not held across an await point, since it is not `Send`.
+fn random_key() -> Data {
+    rand::rng().random()
+}
+
+/// Waits for the `switchNetworkKey` callback with the given sequence number.
+async fn await_switch(
+    callbacks: &mut Receiver<Callback>,
+    sequence_number: u8,
+    timeout: Duration,
+) -> bool {
+    let deadline = Instant::now() + timeout;
+
+    while let Ok(Some(callback)) = timeout_at(deadline, callbacks.recv()).await {
+        if let Callback::Security(SecurityHandler::SwitchNetworkKey(switch)) = callback {
+            if switch.sequence_number() == sequence_number {
+                return true;
+            }
+
+            debug!(
+                "Ignoring switch to network key {}.",
+                switch.sequence_number()
+            );
+        }
+    }
+
+    false
+}
+
+const fn verify(expected: u8, actual: Option<u8>) -> Result<(), ValueError> {
+    match actual {
+        Some(actual) if actual == expected => Ok(()),
+        _ => Err(ValueError::NetworkKeySequenceNumber { expected, actual }),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::verify;
+    use crate::ValueError;
+
+    #[test]
+    fn verify_requires_expected_sequence_number() {
+        assert_eq!(verify(3, Some(3)), Ok(()));
+        assert_eq!(
+            verify(3, Some(2)),
+            Err(ValueError::NetworkKeySequenceNumber {
+                expected: 3,
+                actual: Some(2),
+            })
+        );
+        assert_eq!(
+            verify(0, None),
+            Err(ValueError::NetworkKeySequenceNumber {
+                expected: 0,
+                actual: None,
+            })
+        );
+    }
+}
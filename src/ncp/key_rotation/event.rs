@@ -0,0 +1,27 @@
+/// Network key rotation progress reported by [`Ncp::rotate_network_key`](crate::Ncp::rotate_network_key).
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum KeyRotationEvent {
+    /// The next network key was broadcast and stored as the alternate key.
+    Distributed {
+        /// The sequence number of the next network key.
+        sequence_number: u8,
+    },
+
+    /// The dwell period elapsed and the key switch was broadcast.
+    SwitchBroadcast {
+        /// The sequence number of the next network key.
+        sequence_number: u8,
+    },
+
+    /// The NCP reported switching to the next network key.
+    Switched {
+        /// The sequence number of the active network key.
+        sequence_number: u8,
+    },
+
+    /// The NCP reports the next network key as its active key.
+    Completed {
+        /// The sequence number of the active network key.
+        sequence_number: u8,
+    },
+}
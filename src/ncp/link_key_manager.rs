@@ -0,0 +1,237 @@
+//! Application link key management.
+
+use std::collections::BTreeMap;
+
+use log::debug;
+use silizium::zigbee::security::man::{
+    ApsKeyMetadata, Context, DerivedKeyType, Flags, Key, KeyType,
+};
+
+pub use self::link_key::LinkKey;
+use crate::ember::{Eui64, Status};
+use crate::ezsp::config;
+use crate::ncp::Ncp;
+use crate::{Configuration, Error, Security, TablesExt};
+
+mod link_key;
+
+/// Index returned by `findKeyTableEntry` if no entry matches.
+const NOT_FOUND: u8 = 0xFF;
+
+/// Manages the application link keys of the NCP's key table.
+///
+/// Obtain a manager with [`Ncp::link_key_manager`]. Keys are addressed by the
+/// IEEE address of their partner device, and the table index of a new key is
+/// chosen by the manager. [`export`](Self::export) and
+/// [`import`](Self::import) move the whole key table to another NCP.
+#[derive(Clone, Debug)]
+pub struct LinkKeyManager {
+    ncp: Ncp,
+}
+
+impl LinkKeyManager {
+    /// Returns the link keys of the key table keyed by their table index.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`Error`] if the key table cannot be read.
+    pub async fn entries(&mut self) -> Result<BTreeMap<u8, LinkKey>, Error> {
+        Ok(self
+            .ncp
+            .connection
+            .key_table()
+            .await?
+            .collect()
+            .await?
+            .into_iter()
+            .map(|(index, entry)| (index, entry.into()))
+            .collect())
+    }
+
+    /// Returns the table index of the link key shared with a device.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`Error`] if the key table cannot be searched.
+    pub async fn find(&mut self, ieee_address: Eui64) -> Result<Option<u8>, Error> {
+        let index = self
+            .ncp
+            .connection
+            .find_key_table_entry(ieee_address, true)
+            .await?;
+        Ok(table_index(index))
+    }
+
+    /// Returns the link key shared with a device.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`Error`] if the key table cannot be searched or the key
+    /// cannot be exported.
+    pub async fn get(&mut self, ieee_address: Eui64) -> Result<Option<LinkKey>, Error> {
+        if self.find(ieee_address).await?.is_none() {
+            return Ok(None);
+        }
+
+        let payload = self
+            .ncp
+            .connection
+            .export_link_key_by_eui(ieee_address)
+            .await?;
+        Ok(Some(LinkKey::new(
+            ieee_address,
+            *payload.plaintext_key(),
+            payload.key_data().clone(),
+        )))
+    }
+
+    /// Returns the frame counters, flags and lifetime of the link key shared with a device.
+    ///
+    /// The key itself is not exported.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`Error`] if the NCP has no link key for the device.
+    pub async fn info(&mut self, ieee_address: Eui64) -> Result<ApsKeyMetadata, Error> {
+        let info = self
+            .ncp
+            .connection
+            .get_aps_key_info(Context::new(
+                KeyType::AppLink,
+                0,
+                DerivedKeyType::None,
+                ieee_address,
+                0,
+                Flags::EUI_IS_VALID,
+                0,
+            ))
+            .await?;
+        Ok(info.key_data().clone())
+    }
+
+    /// Stores a link key shared with a device and returns its table index.
+    ///
+    /// An existing key of the device is replaced in place. Otherwise the key
+    /// is written to the first free slot of the key table.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`Error`] with [`Status::TableFull`] if there is no free
+    /// slot, or if the key table cannot be read or the key cannot be imported.
+    pub async fn insert(&mut self, ieee_address: Eui64, key: Key) -> Result<u8, Error> {
+        let index = match self.find(ieee_address).await? {
+            Some(index) => index,
+            None => self.free_index().await?.ok_or(Status::TableFull)?,
+        };
+
+        debug!("Importing link key of {ieee_address} at index {index}.");
+        self.ncp
+            .connection
+            .import_link_key(index, ieee_address, key)
+            .await?;
+        Ok(index)
+    }
+
+    /// Erases the link key shared with a device and returns its former index.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`Error`] if the key table cannot be searched or the entry
+    /// cannot be erased.
+    pub async fn remove(&mut self, ieee_address: Eui64) -> Result<Option<u8>, Error> {
+        let Some(index) = self.find(ieee_address).await? else {
+            return Ok(None);
+        };
+
+        debug!("Erasing link key of {ieee_address} at index {index}.");
+        self.ncp.connection.erase_key_table_entry(index).await?;
+        Ok(Some(index))
+    }
+
+    /// Returns all link keys for migration to another NCP.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`Error`] if the key table cannot be read.
+    pub async fn export(&mut self) -> Result<Vec<LinkKey>, Error> {
+        Ok(self.entries().await?.into_values().collect())
+    }
+
+    /// Stores link keys exported from another NCP.
+    ///
+    /// Only the keys are imported. Importing resets the frame counters of the
+    /// keys to zero, and the counters in the exported metadata are not restored.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`Error`] if a key cannot be stored as with
+    /// [`insert`](Self::insert).
+    pub async fn import(&mut self, keys: &[LinkKey]) -> Result<(), Error> {
+        for key in keys {
+            self.insert(key.ieee_address(), *key.key()).await?;
+        }
+
+        Ok(())
+    }
+
+    /// Returns the first unused index of the key table.
+    ///
+    /// Slots the NCP reports as empty are unused. Any other error reading the
+    /// key table is returned rather than treating the slot as free.
+    async fn free_index(&mut self) -> Result<Option<u8>, Error> {
+        let size = self
+            .ncp
+            .connection
+            .get_configuration_value(config::Id::KeyTableSize)
+            .await?;
+        let used = self.ncp.connection.key_table().await?.collect().await?;
+        Ok(free_index(size, &used))
+    }
+}
+
+/// Maps an index returned by `findKeyTableEntry` to a table index.
+fn table_index(index: u8) -> Option<u8> {
+    (index != NOT_FOUND).then_some(index)
+}
+
+/// Returns the first index of a key table of `size` slots that is not `used`.
+fn free_index<T>(size: u16, used: &BTreeMap<u8, T>) -> Option<u8> {
+    (0..u8::try_from(size).unwrap_or(u8::MAX)).find(|index| !used.contains_key(index))
+}
+
+impl Ncp {
+    /// Creates a [`LinkKeyManager`] for the NCP's key table.
+    #[must_use]
+    pub fn link_key_manager(&self) -> LinkKeyManager {
+        LinkKeyManager { ncp: self.clone() }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use super::{NOT_FOUND, free_index, table_index};
+
+    #[test]
+    fn maps_not_found_index() {
+        assert_eq!(table_index(NOT_FOUND), None);
+        assert_eq!(table_index(0), Some(0));
+        assert_eq!(table_index(12), Some(12));
+    }
+
+    #[test]
+    fn selects_first_free_index() {
+        let used: BTreeMap<u8, u8> = [(0, 0), (1, 0), (3, 0)].into_iter().collect();
+        assert_eq!(free_index(6, &used), Some(2));
+        assert_eq!(free_index(2, &used), None);
+        assert_eq!(free_index(0, &BTreeMap::<u8, u8>::new()), None);
+        assert_eq!(free_index(4, &BTreeMap::<u8, u8>::new()), Some(0));
+    }
+
+    #[test]
+    fn limits_free_index_to_index_range() {
+        let used: BTreeMap<u8, u8> = (0..u8::MAX).map(|index| (index, index)).collect();
+        assert_eq!(free_index(u16::MAX, &used), None);
+    }
+}
@@ -0,0 +1,52 @@
+use silizium::zigbee::security::man::{ApsKeyMetadata, Key};
+
+use crate::ember::Eui64;
+use crate::parameters::security::export_link_key_by_index;
+
+/// An application link key of the NCP's key table.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct LinkKey {
+    ieee_address: Eui64,
+    key: Key,
+    metadata: ApsKeyMetadata,
+}
+
+impl LinkKey {
+    /// Creates a link key shared with the device with the given IEEE address.
+    #[must_use]
+    pub const fn new(ieee_address: Eui64, key: Key, metadata: ApsKeyMetadata) -> Self {
+        Self {
+            ieee_address,
+            key,
+            metadata,
+        }
+    }
+
+    /// Returns the IEEE address of the partner device.
+    #[must_use]
+    pub const fn ieee_address(&self) -> Eui64 {
+        self.ieee_address
+    }
+
+    /// Returns the plaintext key.
+    #[must_use]
+    pub const fn key(&self) -> &Key {
+        &self.key
+    }
+
+    /// Returns the key's frame counters, flags and lifetime.
+    #[must_use]
+    pub const fn metadata(&self) -> &ApsKeyMetadata {
+        &self.metadata
+    }
+}
+
+impl From<export_link_key_by_index::Payload> for LinkKey {
+    fn from(payload: export_link_key_by_index::Payload) -> Self {
+        Self::new(
+            payload.eui(),
+            *payload.plaintext_key(),
+            payload.key_data().clone(),
+        )
+    }
+}
@@ -1,14 +1,17 @@
+use tokio::sync::mpsc;
 use tokio::sync::oneshot::Sender;
 
-use crate::Callback;
 use crate::ember::Status;
 use crate::parameters::networking::handler::{EnergyScanResult, NetworkFound};
+use crate::{Callback, DefragmentedMessage};
 
 /// Messages exchanged with the NCP event handler.
 ///
 /// The event handler receives raw EZSP callbacks, one-shot registration
-/// requests for scans and outgoing message confirmations, and a termination
-/// signal used by [`Ncp::terminate`](crate::Ncp::terminate).
+/// requests for scans and outgoing message confirmations, routes for
+/// service-owned clusters, subscriptions to callbacks, ZDP messages, and global
+/// ZCL commands, and a termination signal used by
+/// [`Ncp::terminate`](crate::Ncp::terminate).
 #[derive(Debug)]
 pub enum Message {
     /// An incoming callback.
@@ -28,6 +31,54 @@ pub enum Message {
         sender: Sender<Result<Status, u8>>,
     },
 
+    /// Routes complete incoming messages of a non-ZDP cluster to a service.
+    ///
+    /// Routed messages are not translated into application events. A later
+    /// route for the same cluster replaces the earlier one, and a route whose
+    /// receiver was dropped is removed when the next message arrives.
+    Cluster {
+        /// The cluster ID.
+        cluster_id: u16,
+        /// The sender receiving the cluster's messages.
+        sender: mpsc::Sender<DefragmentedMessage>,
+    },
+
+    /// Subscribes a service to complete incoming ZDP messages of a cluster.
+    ///
+    /// Subscribers receive copies of the messages, which are still translated
+    /// into application events. A subscription whose receiver was dropped is
+    /// removed when the next message arrives or the next subscription of the
+    /// cluster is registered.
+    Zdp {
+        /// The ZDP cluster ID.
+        cluster_id: u16,
+        /// The sender receiving copies of the cluster's messages.
+        sender: mpsc::Sender<DefragmentedMessage>,
+    },
+
+    /// Subscribes a service to complete incoming global ZCL commands of any cluster.
+    ///
+    /// Subscribers receive copies of the messages carrying the global command,
+    /// which are still routed to the owning service or translated into
+    /// application events.
+    GlobalCommand {
+        /// The global ZCL command ID.
+        command_id: u8,
+        /// The sender receiving copies of the messages.
+        sender: mpsc::Sender<DefragmentedMessage>,
+    },
+
+    /// Subscribes a service to copies of all incoming callbacks.
+    ///
+    /// Subscribers receive each callback before the event handler processes
+    /// it. The callbacks are still handled and translated into application
+    /// events. A subscription whose receiver was dropped is removed when the
+    /// next callback arrives.
+    Callbacks {
+        /// The sender receiving copies of the callbacks.
+        sender: mpsc::Sender<Callback>,
+    },
+
     /// Stops the event handler.
     Terminate,
 }
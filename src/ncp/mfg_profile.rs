@@ -0,0 +1,131 @@
+//! Manufacturing profile of the NCP.
+
+use std::fmt::{self, Display, Formatter};
+
+use enum_iterator::all;
+
+use crate::ember::Eui64;
+use crate::ezsp::mfg_token::{Id, Mfg, Stack, Token};
+use crate::ncp::{Ncp, WriteOnceAcknowledgement};
+use crate::{Error, Utilities, ValueError};
+
+/// The EUI64 and the decoded manufacturing tokens of an NCP.
+///
+/// The [`Display`] implementation renders one line per token for factory
+/// quality assurance. Erased tokens are reported as such, and tokens whose
+/// length does not match their type are reported with their error instead of
+/// failing the whole profile. Secret keys are omitted.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct MfgProfile {
+    eui64: Eui64,
+    tokens: Vec<(Id, Result<Option<Token>, ValueError>)>,
+}
+
+impl MfgProfile {
+    /// Returns the EUI64 the NCP operates with.
+    #[must_use]
+    pub const fn eui64(&self) -> Eui64 {
+        self.eui64
+    }
+
+    /// Returns the decoded tokens ordered by their ID.
+    pub fn tokens(&self) -> &[(Id, Result<Option<Token>, ValueError>)] {
+        &self.tokens
+    }
+
+    /// Returns the decoded token `id`, or `None` if it is erased or invalid.
+    #[must_use]
+    pub fn get(&self, id: Id) -> Option<&Token> {
+        self.tokens
+            .iter()
+            .find(|(token_id, _)| *token_id == id)
+            .and_then(|(_, token)| token.as_ref().ok())
+            .and_then(Option::as_ref)
+    }
+}
+
+impl Display for MfgProfile {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        writeln!(f, "EUI64: {}", self.eui64)?;
+
+        for (id, token) in &self.tokens {
+            match id {
+                Id::Mfg(mfg) => write!(f, "{mfg:?}: ")?,
+                Id::Stack(stack) => write!(f, "{stack:?}: ")?,
+            }
+
+            match token {
+                Ok(Some(token)) => writeln!(f, "{token}")?,
+                Ok(None) => writeln!(f, "erased")?,
+                Err(error) => writeln!(f, "{error}")?,
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl Ncp {
+    /// Reads and decodes a manufacturing token.
+    ///
+    /// Returns `None` if the token is erased.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`Error`] if the token cannot be read or has an unexpected length.
+    pub async fn mfg_token(&mut self, id: Id) -> Result<Option<Token>, Error> {
+        let bytes = self.connection.get_mfg_token(id).await?;
+        Ok(Token::decode(id, &bytes)?)
+    }
+
+    /// Encodes and writes a manufacturing token.
+    ///
+    /// Manufacturing tokens can be written only once. A custom EUI64 token is
+    /// written with [`Ncp::write_custom_eui64`], which validates the address,
+    /// refuses to overwrite another custom EUI64 and verifies the write.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`Error`] if the NCP rejects the token, or if a custom EUI64
+    /// is rejected by [`Ncp::write_custom_eui64`].
+    pub async fn write_mfg_token(
+        &mut self,
+        token: &Token,
+        acknowledgement: WriteOnceAcknowledgement,
+    ) -> Result<(), Error> {
+        if let Token::CustomEui64(eui64) = token {
+            return self
+                .write_custom_eui64(*eui64, acknowledgement)
+                .await
+                .map(drop);
+        }
+
+        self.connection
+            .set_mfg_token(token.id(), token.to_bytes())
+            .await
+    }
+
+    /// Reads the EUI64 and all manufacturing tokens of the NCP.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`Error`] if the EUI64 or a token cannot be read.
+    pub async fn mfg_profile(&mut self) -> Result<MfgProfile, Error> {
+        let mut ids: Vec<Id> = all::<Mfg>()
+            .map(Id::Mfg)
+            .chain(all::<Stack>().map(Id::Stack))
+            .collect();
+        ids.sort_by_key(|&id| u8::from(id));
+        let mut tokens = Vec::with_capacity(ids.len());
+
+        for id in ids {
+            let bytes = self.connection.get_mfg_token(id).await?;
+            tokens.push((id, Token::decode(id, &bytes)));
+        }
+
+        Ok(MfgProfile {
+            eui64: self.connection.get_eui64().await?,
+            tokens,
+        })
+    }
+}
@@ -0,0 +1,633 @@
+//! ZCL OTA Upgrade cluster server.
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::io;
+use std::path::Path;
+use std::time::Duration;
+
+use le_stream::ToLeStream;
+use log::{debug, info, trace, warn};
+use tokio::sync::mpsc::{Sender, channel};
+use tokio::time::{Instant, timeout_at};
+
+pub use self::event::OtaEvent;
+use crate::ember::aps::Options;
+use crate::ember::{Eui64, NodeId};
+use crate::ncp::service::{MESSAGES_CAPACITY, emit};
+use crate::ncp::{Message, Ncp, message_too_long};
+use crate::types::ByteSizedVec;
+use crate::zcl::general::DefaultResponse;
+use crate::zcl::ota::{
+    self, Image, ImageBlockRequest, ImageBlockResponse, ImagePageRequest, QueryNextImageRequest,
+    QueryNextImageResponse, UpgradeEndRequest, UpgradeEndResponse,
+};
+use crate::zcl::{Command, Direction, Frame, FrameType, Header, Status};
+use crate::{DefragmentedMessage, Error, Messaging};
+
+mod event;
+
+/// Length of the ZCL header of a response that is not manufacturer-specific.
+const ZCL_HEADER_LENGTH: usize = 3;
+/// Fields of a successful image block response preceding the image data.
+const IMAGE_BLOCK_RESPONSE_OVERHEAD: usize = 14;
+
+/// Serves Zigbee OTA upgrade images to client devices.
+///
+/// The server holds a set of validated [`Image`]s, typically loaded from a
+/// directory with [`OtaServer::from_directory`]. Start it with
+/// [`Ncp::serve_ota`], which routes all incoming OTA Upgrade cluster messages
+/// to the server instead of the application event channel.
+///
+/// A Query Next Image Request is answered with the newest image matching the
+/// client's manufacturer code and image type that is newer than the client's
+/// current version, supports its hardware version, and is either generic or
+/// destined for the client's IEEE address. Image block and page requests are
+/// served from the exact requested image version. Blocks never exceed the
+/// client's maximum data size or the space left in a single APS frame as
+/// reported by `maximumPayloadLength`, so responses are never fragmented.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct OtaServer {
+    images: Vec<Image>,
+    upgrade_time: u32,
+}
+
+impl OtaServer {
+    /// Creates a server offering the given images.
+    #[must_use]
+    pub fn new(images: impl IntoIterator<Item = Image>) -> Self {
+        Self {
+            images: images.into_iter().collect(),
+            upgrade_time: 0,
+        }
+    }
+
+    /// Creates a server offering all valid OTA files in a directory.
+    ///
+    /// Subdirectories are ignored. Files that are not valid OTA upgrade files
+    /// are logged and skipped.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`io::Error`] if the directory or one of its files cannot be read.
+    pub fn from_directory(directory: impl AsRef<Path>) -> io::Result<Self> {
+        let mut images = Vec::new();
+
+        for entry in fs::read_dir(directory)? {
+            let path = entry?.path();
+
+            if !path.is_file() {
+                continue;
+            }
+
+            match Image::parse(fs::read(&path)?) {
+                Ok(image) => {
+                    debug!(
+                        "Loaded OTA image {}: manufacturer: {:#06X}, type: {:#06X}, version: {:#010X}",
+                        path.display(),
+                        image.header().manufacturer_code(),
+                        image.header().image_type(),
+                        image.header().file_version(),
+                    );
+                    images.push(image);
+                }
+                Err(error) => {
+                    warn!("Skipping invalid OTA file {}: {error}", path.display());
+                }
+            }
+        }
+
+        Ok(Self::new(images))
+    }
+
+    /// Sets the delay in seconds after which clients apply a downloaded image.
+    ///
+    /// The default of zero tells clients to upgrade immediately.
+    #[must_use]
+    pub const fn with_upgrade_time(mut self, upgrade_time: u32) -> Self {
+        self.upgrade_time = upgrade_time;
+        self
+    }
+
+    /// Returns the images offered by the server.
+    #[must_use]
+    pub fn images(&self) -> &[Image] {
+        &self.images
+    }
+
+    /// Returns the newest image the client described by `request` should upgrade to.
+    #[must_use]
+    pub fn next_image(
+        &self,
+        request: &QueryNextImageRequest,
+        eui64: Option<Eui64>,
+    ) -> Option<&Image> {
+        let current_file_version = request.current_file_version();
+        self.images
+            .iter()
+            .filter(|image| {
+                let header = image.header();
+                header.manufacturer_code() == request.manufacturer_code()
+                    && header.image_type() == request.image_type()
+                    && header.file_version() > current_file_version
+                    && header.supports_hardware_version(request.hardware_version())
+                    && header
+                        .upgrade_file_destination()
+                        .is_none_or(|destination| Some(destination) == eui64)
+            })
+            .max_by_key(|image| image.header().file_version())
+    }
+
+    /// Returns the image with exactly the given identification.
+    #[must_use]
+    pub fn image(
+        &self,
+        manufacturer_code: u16,
+        image_type: u16,
+        file_version: u32,
+    ) -> Option<&Image> {
+        self.images.iter().find(|image| {
+            let header = image.header();
+            header.manufacturer_code() == manufacturer_code
+                && header.image_type() == image_type
+                && header.file_version() == file_version
+        })
+    }
+
+    fn has_device_specific_images(&self) -> bool {
+        self.images
+            .iter()
+            .any(|image| image.header().upgrade_file_destination().is_some())
+    }
+}
+
+impl Ncp {
+    /// Starts serving OTA upgrade images and returns the server future.
+    ///
+    /// The event handler forwards all incoming OTA Upgrade cluster messages to
+    /// the returned future, which answers them and reports per-device progress
+    /// to `events`. The block size limit is derived from the NCP's maximum APS
+    /// payload length when the server starts. Spawn the returned future; it
+    /// runs until the event handler stops.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`Error`] if the maximum payload length cannot be read, an
+    /// APS frame has no room for image data, or the cluster route cannot be
+    /// registered with the event handler.
+    pub async fn serve_ota(
+        &self,
+        server: OtaServer,
+        events: Sender<OtaEvent>,
+    ) -> Result<impl Future<Output = ()> + Send + 'static, Error> {
+        let mut ncp = self.clone();
+        let maximum_payload_length = usize::from(ncp.connection.maximum_payload_length().await?);
+        let block_size = maximum_payload_length
+            .saturating_sub(ZCL_HEADER_LENGTH + IMAGE_BLOCK_RESPONSE_OVERHEAD);
+
+        if block_size == 0 {
+            return Err(message_too_long());
+        }
+
+        let (sender, mut messages) = channel(MESSAGES_CAPACITY);
+        ncp.event_handler_handle
+            .send(Message::Cluster {
+                cluster_id: ota::CLUSTER_ID,
+                sender,
+            })
+            .await?;
+
+        let mut session = Session {
+            ncp,
+            server,
+            events,
+            block_size,
+            pages: BTreeMap::new(),
+        };
+
+        Ok(async move {
+            loop {
+                session.send_due_pages().await;
+
+                let message = match session.next_page_due() {
+                    Some(due) => match timeout_at(due, messages.recv()).await {
+                        Ok(message) => message,
+                        Err(_) => continue,
+                    },
+                    None => messages.recv().await,
+                };
+
+                let Some(message) = message else {
+                    break;
+                };
+
+                if let Err(error) = session.handle(&message).await {
+                    warn!(
+                        "Failed to answer OTA request from {:#06X}: {error}",
+                        message.sender()
+                    );
+                }
+            }
+
+            debug!("OTA message route closed. OTA server terminating.");
+        })
+    }
+}
+
+struct Session {
+    ncp: Ncp,
+    server: OtaServer,
+    events: Sender<OtaEvent>,
+    block_size: usize,
+    pages: BTreeMap<NodeId, PageTransfer>,
+}
+
+/// An image page being sent to a client as a series of block responses.
+struct PageTransfer {
+    message: DefragmentedMessage,
+    header: Header,
+    request: ImagePageRequest,
+    offset: u32,
+    end: u32,
+    due: Instant,
+}
+
+impl Session {
+    async fn handle(&mut self, message: &DefragmentedMessage) -> Result<(), Error> {
+        let Some(frame) = Frame::parse(message.message()) else {
+            warn!(
+                "Received malformed OTA frame from {:#06X}",
+                message.sender()
+            );
+            return Ok(());
+        };
+        let header = *frame.header();
+
+        if header.frame_type() != Ok(FrameType::ClusterSpecific)
+            || header.direction() != Direction::ClientToServer
+        {
+            trace!("Ignoring OTA frame: {header:?}");
+            return Ok(());
+        }
+
+        match header.command_id() {
+            QueryNextImageRequest::ID => match frame.command() {
+                Some(request) => self.query_next_image(message, &header, request).await,
+                None => {
+                    self.default_response(message, &header, Status::MalformedCommand)
+                        .await
+                }
+            },
+            ImageBlockRequest::ID => match frame.command::<ImageBlockRequest>() {
+                Some(request) => self
+                    .image_block(
+                        message,
+                        &header,
+                        (
+                            request.manufacturer_code(),
+                            request.image_type(),
+                            request.file_version(),
+                        ),
+                        request.file_offset(),
+                        request.maximum_data_size(),
+                    )
+                    .await
+                    .map(drop),
+                None => {
+                    self.default_response(message, &header, Status::MalformedCommand)
+                        .await
+                }
+            },
+            ImagePageRequest::ID => match frame.command() {
+                Some(request) => {
+                    self.image_page(message, header, request);
+                    Ok(())
+                }
+                None => {
+                    self.default_response(message, &header, Status::MalformedCommand)
+                        .await
+                }
+            },
+            UpgradeEndRequest::ID => match frame.command() {
+                Some(request) => self.upgrade_end(message, &header, request).await,
+                None => {
+                    self.default_response(message, &header, Status::MalformedCommand)
+                        .await
+                }
+            },
+            _ => {
+                self.default_response(message, &header, Status::UnsupportedClusterCommand)
+                    .await
+            }
+        }
+    }
+
+    async fn query_next_image(
+        &mut self,
+        message: &DefragmentedMessage,
+        header: &Header,
+        request: QueryNextImageRequest,
+    ) -> Result<(), Error> {
+        let node_id = message.sender();
+        let eui64 = if self.server.has_device_specific_images() {
+            self.ncp
+                .connection
+                .lookup_eui64_by_node_id(node_id)
+                .await
+                .ok()
+        } else {
+            None
+        };
+
+        let (response, event) = self.server.next_image(&request, eui64).map_or_else(
+            || {
+                (
+                    QueryNextImageResponse::no_image_available(),
+                    OtaEvent::NoImageAvailable {
+                        node_id,
+                        manufacturer_code: request.manufacturer_code(),
+                        image_type: request.image_type(),
+                        current_file_version: request.current_file_version(),
+                    },
+                )
+            },
+            |image| {
+                let header = image.header();
+                info!(
+                    "Offering OTA image {:#010X} to {node_id:#06X} running {:#010X}",
+                    header.file_version(),
+                    request.current_file_version()
+                );
+                (
+                    QueryNextImageResponse::available(
+                        header.manufacturer_code(),
+                        header.image_type(),
+                        header.file_version(),
+                        header.total_image_size(),
+                    ),
+                    OtaEvent::ImageOffered {
+                        node_id,
+                        manufacturer_code: header.manufacturer_code(),
+                        image_type: header.image_type(),
+                        file_version: header.file_version(),
+                        image_size: header.total_image_size(),
+                    },
+                )
+            },
+        );
+
+        self.respond(message, header, response).await?;
+        emit(&self.events, event).await;
+        Ok(())
+    }
+
+    async fn image_block(
+        &mut self,
+        message: &DefragmentedMessage,
+        header: &Header,
+        (manufacturer_code, image_type, file_version): (u16, u16, u32),
+        file_offset: u32,
+        maximum_data_size: u8,
+    ) -> Result<Option<u32>, Error> {
+        let node_id = message.sender();
+        let length = usize::from(maximum_data_size).min(self.block_size);
+        let block = self
+            .server
+            .image(manufacturer_code, image_type, file_version)
+            .and_then(|image| {
+                let offset = usize::try_from(file_offset).ok()?;
+                let data = image.block(offset, length);
+                (!data.is_empty()).then(|| (image.header().total_image_size(), data))
+            })
+            .and_then(|(image_size, data)| {
+                Some((image_size, ByteSizedVec::from_slice(data).ok()?))
+            });
+
+        let Some((image_size, data)) = block else {
+            debug!("Aborting OTA download of {file_version:#010X} by {node_id:#06X}");
+            self.respond(message, header, ImageBlockResponse::abort())
+                .await?;
+            emit(
+                &self.events,
+                OtaEvent::Aborted {
+                    node_id,
+                    file_version,
+                },
+            )
+            .await;
+            return Ok(None);
+        };
+
+        let offset = file_offset.saturating_add(data.len().try_into().unwrap_or(u32::MAX));
+        self.respond(
+            message,
+            header,
+            ImageBlockResponse::success(
+                manufacturer_code,
+                image_type,
+                file_version,
+                file_offset,
+                data,
+            ),
+        )
+        .await?;
+        emit(
+            &self.events,
+            OtaEvent::Progress {
+                node_id,
+                file_version,
+                offset,
+                image_size,
+            },
+        )
+        .await;
+        Ok(Some(offset))
+    }
+
+    fn image_page(
+        &mut self,
+        message: &DefragmentedMessage,
+        header: Header,
+        request: ImagePageRequest,
+    ) {
+        let image_size = self
+            .server
+            .image(
+                request.manufacturer_code(),
+                request.image_type(),
+                request.file_version(),
+            )
+            .map_or(0, |image| image.header().total_image_size());
+        let end = request
+            .file_offset()
+            .saturating_add(u32::from(request.page_size()))
+            .min(image_size);
+
+        // A new page request supersedes the client's previous one.
+        self.pages.insert(
+            message.sender(),
+            PageTransfer {
+                message: message.clone(),
+                header,
+                request,
+                offset: request.file_offset(),
+                end,
+                due: Instant::now(),
+            },
+        );
+    }
+
+    /// Returns when the next block of an image page is due.
+    fn next_page_due(&self) -> Option<Instant> {
+        self.pages.values().map(|transfer| transfer.due).min()
+    }
+
+    /// Sends the next block of every image page that is due.
+    async fn send_due_pages(&mut self) {
+        let now = Instant::now();
+        let due: Vec<NodeId> = self
+            .pages
+            .iter()
+            .filter(|(_, transfer)| transfer.due <= now)
+            .map(|(node_id, _)| *node_id)
+            .collect();
+
+        for node_id in due {
+            if let Err(error) = self.send_page_block(node_id).await {
+                warn!("Failed to send OTA image page to {node_id:#06X}: {error}");
+            }
+        }
+    }
+
+    async fn send_page_block(&mut self, node_id: NodeId) -> Result<(), Error> {
+        let Some(transfer) = self.pages.remove(&node_id) else {
+            return Ok(());
+        };
+        let request = transfer.request;
+        let remaining =
+            u8::try_from(transfer.end.saturating_sub(transfer.offset)).unwrap_or(u8::MAX);
+
+        let Some(offset) = self
+            .image_block(
+                &transfer.message,
+                &transfer.header,
+                (
+                    request.manufacturer_code(),
+                    request.image_type(),
+                    request.file_version(),
+                ),
+                transfer.offset,
+                request.maximum_data_size().min(remaining),
+            )
+            .await?
+        else {
+            return Ok(());
+        };
+
+        if offset < transfer.end {
+            self.pages.insert(
+                node_id,
+                PageTransfer {
+                    offset,
+                    due: Instant::now()
+                        + Duration::from_millis(u64::from(request.response_spacing())),
+                    ..transfer
+                },
+            );
+        }
+
+        Ok(())
+    }
+
+    async fn upgrade_end(
+        &mut self,
+        message: &DefragmentedMessage,
+        header: &Header,
+        request: UpgradeEndRequest,
+    ) -> Result<(), Error> {
+        let status = request.status();
+        info!(
+            "OTA download of {:#010X} by {:#06X} ended with status: {status:?}",
+            request.file_version(),
+            message.sender()
+        );
+
+        if status == Ok(Status::Success) {
+            self.respond(
+                message,
+                header,
+                UpgradeEndResponse::new(
+                    request.manufacturer_code(),
+                    request.image_type(),
+                    request.file_version(),
+                    0,
+                    self.server.upgrade_time,
+                ),
+            )
+            .await?;
+        } else {
+            self.default_response(message, header, Status::Success)
+                .await?;
+        }
+
+        emit(
+            &self.events,
+            OtaEvent::UpgradeEnd {
+                node_id: message.sender(),
+                file_version: request.file_version(),
+                status,
+            },
+        )
+        .await;
+        Ok(())
+    }
+
+    async fn respond<T>(
+        &mut self,
+        message: &DefragmentedMessage,
+        header: &Header,
+        response: T,
+    ) -> Result<(), Error>
+    where
+        T: Command + ToLeStream,
+    {
+        let frame = Frame::from_command(header.transaction_sequence(), true, response);
+        self.send(message, &frame).await
+    }
+
+    async fn default_response(
+        &mut self,
+        message: &DefragmentedMessage,
+        header: &Header,
+        status: Status,
+    ) -> Result<(), Error> {
+        if header.disable_default_response() && status == Status::Success {
+            return Ok(());
+        }
+
+        self.send(message, &DefaultResponse::reply_to(header, status))
+            .await
+    }
+
+    async fn send(&mut self, message: &DefragmentedMessage, frame: &Frame) -> Result<(), Error> {
+        // Clients retry lost requests, so the server does not track the
+        // `messageSent` confirmation of each response.
+        self.ncp
+            .reply_unconfirmed(message, &frame.to_bytes(), Options::NONE)
+            .await
+    }
+}
+
+impl OtaEvent {
+    /// Returns the device the event refers to.
+    #[must_use]
+    pub const fn node_id(&self) -> NodeId {
+        match self {
+            Self::ImageOffered { node_id, .. }
+            | Self::NoImageAvailable { node_id, .. }
+            | Self::Progress { node_id, .. }
+            | Self::Aborted { node_id, .. }
+            | Self::UpgradeEnd { node_id, .. } => *node_id,
+        }
+    }
+}
@@ -0,0 +1,62 @@
+use crate::ember::NodeId;
+use crate::zcl::Status;
+
+/// Per-device progress reported by an [`OtaServer`](crate::OtaServer).
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum OtaEvent {
+    /// An image was offered in response to a Query Next Image Request.
+    ImageOffered {
+        /// The querying device.
+        node_id: NodeId,
+        /// The manufacturer code of the offered image.
+        manufacturer_code: u16,
+        /// The image type of the offered image.
+        image_type: u16,
+        /// The file version of the offered image.
+        file_version: u32,
+        /// The size of the offered image file in bytes.
+        image_size: u32,
+    },
+
+    /// No image matched a Query Next Image Request.
+    NoImageAvailable {
+        /// The querying device.
+        node_id: NodeId,
+        /// The device's manufacturer code.
+        manufacturer_code: u16,
+        /// The device's image type.
+        image_type: u16,
+        /// The file version currently running on the device.
+        current_file_version: u32,
+    },
+
+    /// An image block was sent to a device.
+    Progress {
+        /// The downloading device.
+        node_id: NodeId,
+        /// The file version being downloaded.
+        file_version: u32,
+        /// The number of bytes transferred up to the end of the sent block.
+        offset: u32,
+        /// The size of the image file in bytes.
+        image_size: u32,
+    },
+
+    /// A device requested a block of an image that the server does not have.
+    Aborted {
+        /// The downloading device.
+        node_id: NodeId,
+        /// The requested file version.
+        file_version: u32,
+    },
+
+    /// A device reported the end of its download with an Upgrade End Request.
+    UpgradeEnd {
+        /// The device that finished downloading.
+        node_id: NodeId,
+        /// The downloaded file version.
+        file_version: u32,
+        /// The download status reported by the device, or its raw value if unknown.
+        status: Result<Status, u8>,
+    },
+}
@@ -0,0 +1,413 @@
+//! PAN ID conflict resolution.
+
+use std::time::Duration;
+
+use log::{debug, info, trace, warn};
+use tokio::sync::mpsc::{Receiver, Sender, channel};
+use tokio::time::{Instant, timeout_at};
+
+pub use self::event::PanIdConflictEvent;
+use crate::ember::PanId;
+use crate::ember::network::Parameters;
+use crate::ember::zigbee::Network;
+use crate::frame::parameters::networking::handler::{Handler as NetworkingHandler, NetworkFound};
+use crate::ncp::service::{MESSAGES_CAPACITY, emit};
+use crate::ncp::{Message, Ncp};
+use crate::{Callback, Configuration, Error, Networking};
+
+mod event;
+
+/// Settings of the PAN ID conflict resolution.
+///
+/// Serve the resolution with [`Ncp::serve_pan_id_conflicts`]. The NCP scans
+/// its current channel for conflicting beacons every
+/// [`scan_interval`](Self::scan_interval). Both this scan and the search for
+/// an unused PAN ID last [`scan_duration`](Self::scan_duration), and the
+/// search result is awaited up to [`search_timeout`](Self::search_timeout).
+/// Further conflicts within [`hold_off`](Self::hold_off) of a resolution are
+/// ignored, since devices keep using the old PAN ID until they moved.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct PanIdResolution {
+    scan_interval: Duration,
+    scan_duration: u8,
+    search_timeout: Duration,
+    hold_off: Duration,
+}
+
+impl PanIdResolution {
+    /// Sets the time between scans for conflicting beacons.
+    #[must_use]
+    pub const fn with_scan_interval(mut self, scan_interval: Duration) -> Self {
+        self.scan_interval = scan_interval;
+        self
+    }
+
+    /// Sets the exponent of the scan duration.
+    #[must_use]
+    pub const fn with_scan_duration(mut self, scan_duration: u8) -> Self {
+        self.scan_duration = scan_duration;
+        self
+    }
+
+    /// Sets the time to wait for the NCP to report an unused PAN ID.
+    #[must_use]
+    pub const fn with_search_timeout(mut self, search_timeout: Duration) -> Self {
+        self.search_timeout = search_timeout;
+        self
+    }
+
+    /// Sets the time after a resolution during which conflicts are ignored.
+    #[must_use]
+    pub const fn with_hold_off(mut self, hold_off: Duration) -> Self {
+        self.hold_off = hold_off;
+        self
+    }
+
+    /// Returns the time between scans for conflicting beacons.
+    #[must_use]
+    pub const fn scan_interval(&self) -> Duration {
+        self.scan_interval
+    }
+
+    /// Returns the exponent of the scan duration.
+    #[must_use]
+    pub const fn scan_duration(&self) -> u8 {
+        self.scan_duration
+    }
+
+    /// Returns the time to wait for the NCP to report an unused PAN ID.
+    #[must_use]
+    pub const fn search_timeout(&self) -> Duration {
+        self.search_timeout
+    }
+
+    /// Returns the time after a resolution during which conflicts are ignored.
+    #[must_use]
+    pub const fn hold_off(&self) -> Duration {
+        self.hold_off
+    }
+}
+
+impl Default for PanIdResolution {
+    fn default() -> Self {
+        Self {
+            scan_interval: Duration::from_mins(15),
+            scan_duration: 3,
+            search_timeout: Duration::from_secs(30),
+            hold_off: Duration::from_mins(5),
+        }
+    }
+}
+
+impl Ncp {
+    /// Starts resolving PAN ID conflicts.
+    ///
+    /// A PAN ID conflict is a beacon on the network's channel that carries its
+    /// PAN ID but another extended PAN ID. The event handler copies all
+    /// callbacks to the returned future, which checks every `networkFound`
+    /// callback for such a beacon. Every
+    /// [`scan_interval`](PanIdResolution::scan_interval), it actively scans
+    /// the current channel and checks the beacons of its neighbours found by
+    /// the scan.
+    ///
+    /// On a conflict, it searches the current channel with
+    /// [`Networking::find_unused_pan_id`], waits for the `unusedPanIdFound`
+    /// callback and moves the network to the found PAN ID with
+    /// [`Configuration::send_pan_id_update`]. Detected and resolved conflicts
+    /// are reported to `events`. The future completes when the event handler
+    /// stops.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`Error`] if the callback subscription cannot be registered
+    /// with the event handler. Failed scans and resolutions are logged.
+    pub async fn serve_pan_id_conflicts(
+        &mut self,
+        resolution: PanIdResolution,
+        events: Sender<PanIdConflictEvent>,
+    ) -> Result<impl Future<Output = ()> + Send + 'static, Error> {
+        let (sender, callbacks) = channel(MESSAGES_CAPACITY);
+        self.event_handler_handle
+            .send(Message::Callbacks { sender })
+            .await?;
+
+        let session = Session {
+            ncp: self.clone(),
+            resolution,
+            events,
+            hold_off: HoldOff::default(),
+        };
+
+        Ok(session.run(callbacks))
+    }
+}
+
+struct Session {
+    ncp: Ncp,
+    resolution: PanIdResolution,
+    events: Sender<PanIdConflictEvent>,
+    hold_off: HoldOff,
+}
+
+impl Session {
+    async fn run(mut self, mut callbacks: Receiver<Callback>) {
+        let mut next_scan = Instant::now() + self.resolution.scan_interval;
+
+        loop {
+            match timeout_at(next_scan, callbacks.recv()).await {
+                Ok(Some(Callback::Networking(NetworkingHandler::NetworkFound(found)))) => {
+                    self.network_found(found.network_found(), &mut callbacks)
+                        .await;
+                }
+                Ok(Some(_)) => {}
+                Ok(None) => break,
+                Err(_) => {
+                    self.scan(&mut callbacks).await;
+                    next_scan = Instant::now() + self.resolution.scan_interval;
+                }
+            }
+        }
+
+        debug!("Callback subscription closed. PAN ID conflict resolution terminating.");
+    }
+
+    /// Scans the current channel and resolves a conflict with a found beacon.
+    async fn scan(&mut self, callbacks: &mut Receiver<Callback>) {
+        if self.hold_off.is_active(Instant::now()) {
+            return;
+        }
+
+        let parameters = match self.ncp.connection.get_network_parameters().await {
+            Ok((_, parameters)) => parameters,
+            Err(error) => {
+                warn!("Failed to read the network parameters: {error}");
+                return;
+            }
+        };
+
+        let networks = match self
+            .ncp
+            .scan_networks(
+                1 << parameters.radio_channel(),
+                self.resolution.scan_duration,
+            )
+            .await
+        {
+            Ok(networks) => networks,
+            Err(error) => {
+                warn!("Failed to scan for conflicting networks: {error}");
+                return;
+            }
+        };
+
+        trace!("Scan found {} beacons.", networks.len());
+
+        if let Some(network) = networks
+            .iter()
+            .map(NetworkFound::network_found)
+            .find(|network| is_conflict(&parameters, network))
+        {
+            self.resolve_conflict(&parameters, network, callbacks).await;
+        }
+    }
+
+    async fn network_found(&mut self, network: &Network, callbacks: &mut Receiver<Callback>) {
+        if self.hold_off.is_active(Instant::now()) {
+            return;
+        }
+
+        let parameters = match self.ncp.connection.get_network_parameters().await {
+            Ok((_, parameters)) => parameters,
+            Err(error) => {
+                warn!("Failed to read the network parameters: {error}");
+                return;
+            }
+        };
+
+        if is_conflict(&parameters, network) {
+            self.resolve_conflict(&parameters, network, callbacks).await;
+        }
+    }
+
+    async fn resolve_conflict(
+        &mut self,
+        parameters: &Parameters,
+        network: &Network,
+        callbacks: &mut Receiver<Callback>,
+    ) {
+        let (pan_id, extended_pan_id) = (network.pan_id(), network.extended_pan_id());
+        info!(
+            "Network {extended_pan_id} uses PAN ID {pan_id:#06X}. Searching for an unused PAN ID."
+        );
+        emit(
+            &self.events,
+            PanIdConflictEvent::Detected {
+                pan_id,
+                extended_pan_id,
+            },
+        )
+        .await;
+
+        match self.resolve(parameters, callbacks).await {
+            Ok(Some(new)) => {
+                let old = parameters.pan_id();
+                info!("Moving network from PAN ID {old:#06X} to {new:#06X}.");
+                self.hold_off
+                    .start(Instant::now(), self.resolution.hold_off);
+                emit(&self.events, PanIdConflictEvent::Resolved { old, new }).await;
+            }
+            Ok(None) => warn!("No unused PAN ID found."),
+            Err(error) => warn!("Failed to resolve PAN ID conflict: {error}"),
+        }
+    }
+
+    /// Moves the network to an unused PAN ID and returns the new PAN ID.
+    async fn resolve(
+        &mut self,
+        parameters: &Parameters,
+        callbacks: &mut Receiver<Callback>,
+    ) -> Result<Option<PanId>, Error> {
+        self.ncp
+            .connection
+            .find_unused_pan_id(
+                1 << parameters.radio_channel(),
+                self.resolution.scan_duration,
+            )
+            .await?;
+
+        let Some(new) = self.unused_pan_id(callbacks).await? else {
+            return Ok(None);
+        };
+
+        if !self.ncp.connection.send_pan_id_update(new).await? {
+            warn!("NCP refused to send the PAN ID update.");
+            return Ok(None);
+        }
+
+        Ok(Some(new))
+    }
+
+    /// Waits for the `unusedPanIdFound` callback.
+    async fn unused_pan_id(
+        &self,
+        callbacks: &mut Receiver<Callback>,
+    ) -> Result<Option<PanId>, Error> {
+        let deadline = Instant::now() + self.resolution.search_timeout;
+
+        while let Ok(Some(callback)) = timeout_at(deadline, callbacks.recv()).await {
+            if let Some(result) = search_result(&callback) {
+                return result.map(Some);
+            }
+        }
+
+        Ok(None)
+    }
+}
+
+/// The period after a resolution during which conflicts are ignored.
+#[derive(Debug, Default)]
+struct HoldOff {
+    until: Option<Instant>,
+}
+
+impl HoldOff {
+    fn start(&mut self, now: Instant, duration: Duration) {
+        self.until = Some(now + duration);
+    }
+
+    fn is_active(&self, now: Instant) -> bool {
+        self.until.is_some_and(|until| now < until)
+    }
+}
+
+/// Returns whether `network` conflicts with the network described by `parameters`.
+fn is_conflict(parameters: &Parameters, network: &Network) -> bool {
+    (network.channel(), network.pan_id()) == (parameters.radio_channel(), parameters.pan_id())
+        && network.extended_pan_id() != parameters.extended_pan_id()
+}
+
+/// Returns the outcome of the unused PAN ID search reported by `callback`, if any.
+fn search_result(callback: &Callback) -> Option<Result<PanId, Error>> {
+    match callback {
+        Callback::Networking(NetworkingHandler::UnusedPanIdFound(found)) => {
+            Some(Ok(found.pan_id()))
+        }
+        Callback::Networking(NetworkingHandler::ScanComplete(complete)) => {
+            complete.status().err().map(Err)
+        }
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use le_stream::FromLeStream;
+    use macaddr::MacAddr8;
+    use tokio::time::Instant;
+
+    use super::{HoldOff, is_conflict, search_result};
+    use crate::Callback;
+    use crate::ember::join::Method;
+    use crate::ember::network::Parameters;
+    use crate::ember::zigbee::Network;
+    use crate::frame::parameters::networking::handler::{
+        Handler as NetworkingHandler, ScanComplete, UnusedPanIdFound,
+    };
+
+    const OWN: MacAddr8 = MacAddr8::new(0, 0, 0, 0, 0, 0, 0, 1);
+    const OTHER: MacAddr8 = MacAddr8::new(0, 0, 0, 0, 0, 0, 0, 2);
+
+    fn parameters() -> Parameters {
+        Parameters::new(OWN, 0x1234, 8, 15, Method::MacAssociation, 0, 0, 0)
+    }
+
+    #[test]
+    fn detects_conflicting_beacons() {
+        let parameters = parameters();
+        assert!(is_conflict(
+            &parameters,
+            &Network::new(15, 0x1234, OTHER, false, 2, 0)
+        ));
+        assert!(!is_conflict(
+            &parameters,
+            &Network::new(15, 0x1234, OWN, true, 2, 0)
+        ));
+        assert!(!is_conflict(
+            &parameters,
+            &Network::new(15, 0x4321, OTHER, false, 2, 0)
+        ));
+        assert!(!is_conflict(
+            &parameters,
+            &Network::new(20, 0x1234, OTHER, false, 2, 0)
+        ));
+    }
+
+    #[test]
+    fn holds_off_after_resolution() {
+        let now = Instant::now();
+        let mut hold_off = HoldOff::default();
+        assert!(!hold_off.is_active(now));
+
+        hold_off.start(now, Duration::from_mins(5));
+        assert!(hold_off.is_active(now + Duration::from_mins(4)));
+        assert!(!hold_off.is_active(now + Duration::from_mins(5)));
+    }
+
+    #[test]
+    fn maps_search_callbacks() {
+        let found =
+            UnusedPanIdFound::from_le_stream([0x21, 0x43, 15].into_iter()).expect("valid handler");
+        let found = Callback::Networking(NetworkingHandler::UnusedPanIdFound(Box::new(found)));
+        assert_eq!(search_result(&found).and_then(Result::ok), Some(0x4321));
+
+        let complete = ScanComplete::from_le_stream([15, 0x00].into_iter()).expect("valid handler");
+        let complete = Callback::Networking(NetworkingHandler::ScanComplete(Box::new(complete)));
+        assert!(search_result(&complete).is_none());
+
+        let failed = ScanComplete::from_le_stream([15, 0x01].into_iter()).expect("valid handler");
+        let failed = Callback::Networking(NetworkingHandler::ScanComplete(Box::new(failed)));
+        assert!(matches!(search_result(&failed), Some(Err(_))));
+    }
+}
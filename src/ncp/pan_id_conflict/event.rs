@@ -0,0 +1,23 @@
+use macaddr::MacAddr8;
+
+use crate::ember::PanId;
+
+/// PAN ID conflict handling reported by [`Ncp::serve_pan_id_conflicts`](crate::Ncp::serve_pan_id_conflicts).
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum PanIdConflictEvent {
+    /// A conflicting beacon was received, and an unused PAN ID is being searched.
+    Detected {
+        /// The PAN ID shared with the conflicting network.
+        pan_id: PanId,
+        /// The extended PAN ID of the conflicting network.
+        extended_pan_id: MacAddr8,
+    },
+
+    /// The network was told to move to an unused PAN ID.
+    Resolved {
+        /// The PAN ID in use when the conflict was detected.
+        old: PanId,
+        /// The unused PAN ID the network moves to.
+        new: PanId,
+    },
+}
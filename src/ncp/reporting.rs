@@ -0,0 +1,474 @@
+//! Attribute reporting configuration and attribute cache.
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::time::{Duration, Instant};
+
+use log::{debug, trace, warn};
+use tokio::sync::mpsc::{Receiver, Sender, channel};
+use tokio::time::timeout_at;
+
+pub use self::attribute_cache::{AttributeAddress, AttributeCache, CachedAttribute};
+pub use self::event::ReportingEvent;
+use crate::ember::aps::Options;
+use crate::ember::{Eui64, NodeId};
+use crate::error::ValueError;
+use crate::ncp::service::{emit, lock};
+use crate::ncp::{Message, Ncp, StackResponse, ZDP};
+use crate::zcl::general::{
+    ConfigureReporting, ConfigureReportingResponse, ReportAttributes, ReportingConfiguration,
+};
+use crate::zcl::{Direction, Frame, FrameType, HOME_AUTOMATION_PROFILE_ID, Header, Status};
+use crate::zdp::{self, DeviceAnnounce};
+use crate::{DefragmentedMessage, Error, Messaging};
+
+mod attribute_cache;
+mod event;
+
+const MESSAGES_CAPACITY: usize = 32;
+/// Maximum interval value disabling reporting of an attribute.
+const REPORTING_DISABLED: u16 = 0xFFFF;
+
+/// Configures attribute reporting and caches reported attribute values.
+///
+/// Obtain a manager with [`Ncp::reporting`] and spawn the returned future. The
+/// manager sends Configure Reporting commands through [`configure`](Self::configure)
+/// and tracks the intervals a device accepted per IEEE address, endpoint,
+/// cluster, and attribute. The future copies every incoming Report Attributes
+/// command of any cluster into an [`AttributeCache`] and flags configured
+/// attributes whose reports did not arrive within their maximum reporting
+/// interval plus a grace period.
+///
+/// Devices send reports to their bound destinations, so the reported clusters
+/// usually need to be bound to the coordinator as well. Reports and responses
+/// are observed without consuming them: they still reach the application or the
+/// service owning their cluster. Clones of the manager share their state.
+#[derive(Clone, Debug)]
+pub struct ReportingManager {
+    ncp: Ncp,
+    state: Arc<Mutex<State>>,
+}
+
+impl ReportingManager {
+    /// Sends a Configure Reporting command to a device endpoint.
+    ///
+    /// The command uses the Home Automation profile and is sent from the first
+    /// local endpoint that lists `cluster_id` as an output cluster. Accepted
+    /// configurations are tracked once the device responds.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`Error`] if the device's IEEE address is unknown, a
+    /// reportable change does not match its attribute's data type, or sending
+    /// fails as for [`Ncp::unicast`].
+    pub async fn configure(
+        &mut self,
+        node_id: NodeId,
+        endpoint: u8,
+        cluster_id: u16,
+        configurations: Vec<ReportingConfiguration>,
+    ) -> Result<StackResponse, Error> {
+        let ieee_address = self.ieee_address(node_id).await?;
+        let command =
+            ConfigureReporting::new(&configurations).map_err(ValueError::ReportableChange)?;
+        let transaction_sequence = self.ncp.next_transaction_sequence();
+        let frame = Frame::new(
+            Header::new(
+                FrameType::Global,
+                Direction::ClientToServer,
+                true,
+                transaction_sequence,
+                ConfigureReporting::ID,
+            ),
+            command,
+        );
+
+        self.lock().pending.insert(
+            (node_id, transaction_sequence),
+            PendingConfiguration {
+                ieee_address,
+                endpoint,
+                cluster_id,
+                configurations,
+            },
+        );
+
+        self.ncp
+            .unicast(
+                node_id,
+                HOME_AUTOMATION_PROFILE_ID,
+                cluster_id,
+                endpoint,
+                frame.to_bytes(),
+                Options::NONE,
+            )
+            .await
+    }
+
+    /// Returns the latest value of an attribute.
+    #[must_use]
+    pub fn attribute(&self, address: &AttributeAddress) -> Option<CachedAttribute> {
+        self.lock().cache.get(address).cloned()
+    }
+
+    /// Returns a snapshot of the attribute cache.
+    #[must_use]
+    pub fn cache(&self) -> AttributeCache {
+        self.lock().cache.clone()
+    }
+
+    /// Returns the reporting configuration a device accepted for an attribute.
+    #[must_use]
+    pub fn configuration(&self, address: &AttributeAddress) -> Option<ReportingConfiguration> {
+        self.lock()
+            .configurations
+            .get(address)
+            .map(|tracked| tracked.configuration.clone())
+    }
+
+    /// Returns the devices with at least one overdue attribute.
+    #[must_use]
+    pub fn overdue_devices(&self) -> BTreeSet<Eui64> {
+        self.lock()
+            .configurations
+            .iter()
+            .filter(|(_, tracked)| tracked.overdue)
+            .map(|(address, _)| address.ieee_address())
+            .collect()
+    }
+
+    /// Removes the cached values and tracked configurations of a device.
+    pub fn forget(&self, ieee_address: Eui64) {
+        let mut state = self.lock();
+        state.cache.remove_device(ieee_address);
+        state
+            .configurations
+            .retain(|address, _| address.ieee_address() != ieee_address);
+        state
+            .addresses
+            .retain(|_, address| *address != ieee_address);
+    }
+
+    async fn ieee_address(&mut self, node_id: NodeId) -> Result<Eui64, Error> {
+        if let Some(ieee_address) = self.lock().addresses.get(&node_id) {
+            return Ok(*ieee_address);
+        }
+
+        let ieee_address = self.ncp.connection.lookup_eui64_by_node_id(node_id).await?;
+        self.lock().addresses.insert(node_id, ieee_address);
+        Ok(ieee_address)
+    }
+
+    fn lock(&self) -> MutexGuard<'_, State> {
+        lock(&self.state)
+    }
+}
+
+impl Ncp {
+    /// Creates a [`ReportingManager`] and returns it with its future.
+    ///
+    /// The event handler copies device announcements and all incoming Report
+    /// Attributes and Configure Reporting Response commands to the returned
+    /// future, which updates the manager's state and reports to `events`.
+    /// Configured attributes are reported as overdue if no report arrives
+    /// within their maximum reporting interval plus `grace_period`. Spawn the
+    /// returned future; it runs until the event handler stops.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`Error`] if the subscriptions cannot be registered with the
+    /// event handler.
+    pub async fn reporting(
+        &self,
+        grace_period: Duration,
+        events: Sender<ReportingEvent>,
+    ) -> Result<(ReportingManager, impl Future<Output = ()> + Send + 'static), Error> {
+        let (sender, messages) = channel(MESSAGES_CAPACITY);
+
+        for message in [
+            Message::GlobalCommand {
+                command_id: ReportAttributes::ID,
+                sender: sender.clone(),
+            },
+            Message::GlobalCommand {
+                command_id: ConfigureReportingResponse::ID,
+                sender: sender.clone(),
+            },
+            Message::Zdp {
+                cluster_id: <DeviceAnnounce as zdp::Command>::CLUSTER_ID,
+                sender,
+            },
+        ] {
+            self.event_handler_handle.send(message).await?;
+        }
+
+        let manager = ReportingManager {
+            ncp: self.clone(),
+            state: Arc::default(),
+        };
+        let session = Session {
+            manager: manager.clone(),
+            grace_period,
+            events,
+        };
+
+        Ok((manager, session.run(messages)))
+    }
+}
+
+#[derive(Debug, Default)]
+struct State {
+    addresses: BTreeMap<NodeId, Eui64>,
+    pending: BTreeMap<(NodeId, u8), PendingConfiguration>,
+    configurations: BTreeMap<AttributeAddress, TrackedConfiguration>,
+    cache: AttributeCache,
+}
+
+impl State {
+    fn deadline(&self, grace_period: Duration) -> Option<Instant> {
+        self.configurations
+            .values()
+            .filter(|tracked| !tracked.overdue)
+            .map(|tracked| tracked.deadline(grace_period))
+            .min()
+    }
+
+    fn track(
+        &mut self,
+        pending: PendingConfiguration,
+        response: &ConfigureReportingResponse,
+        now: Instant,
+    ) -> Vec<ReportingEvent> {
+        let mut events = Vec::with_capacity(pending.configurations.len());
+
+        for configuration in pending.configurations {
+            let address = AttributeAddress::new(
+                pending.ieee_address,
+                pending.endpoint,
+                pending.cluster_id,
+                configuration.attribute_id(),
+            );
+            let status = response.status_of(configuration.attribute_id());
+
+            if status == Ok(Status::Success) {
+                if matches!(configuration.maximum_interval(), 0 | REPORTING_DISABLED) {
+                    self.configurations.remove(&address);
+                } else {
+                    self.configurations.insert(
+                        address,
+                        TrackedConfiguration {
+                            configuration,
+                            last_report: now,
+                            overdue: false,
+                        },
+                    );
+                }
+            }
+
+            events.push(ReportingEvent::Configured { address, status });
+        }
+
+        events
+    }
+}
+
+#[derive(Debug)]
+struct PendingConfiguration {
+    ieee_address: Eui64,
+    endpoint: u8,
+    cluster_id: u16,
+    configurations: Vec<ReportingConfiguration>,
+}
+
+#[derive(Debug)]
+struct TrackedConfiguration {
+    configuration: ReportingConfiguration,
+    last_report: Instant,
+    overdue: bool,
+}
+
+impl TrackedConfiguration {
+    fn deadline(&self, grace_period: Duration) -> Instant {
+        self.last_report
+            + Duration::from_secs(self.configuration.maximum_interval().into())
+            + grace_period
+    }
+}
+
+struct Session {
+    manager: ReportingManager,
+    grace_period: Duration,
+    events: Sender<ReportingEvent>,
+}
+
+impl Session {
+    async fn run(mut self, mut messages: Receiver<DefragmentedMessage>) {
+        loop {
+            let deadline = self.manager.lock().deadline(self.grace_period);
+            let message = match deadline {
+                Some(deadline) => timeout_at(deadline.into(), messages.recv()).await,
+                None => Ok(messages.recv().await),
+            };
+
+            match message {
+                Ok(Some(message)) => {
+                    if let Err(error) = self.handle(&message).await {
+                        warn!(
+                            "Failed to handle reporting message from {:#06X}: {error}",
+                            message.sender()
+                        );
+                    }
+                }
+                Ok(None) => break,
+                Err(_) => self.flag_overdue().await,
+            }
+        }
+
+        debug!("Reporting subscriptions closed. Reporting manager terminating.");
+    }
+
+    async fn handle(&mut self, message: &DefragmentedMessage) -> Result<(), Error> {
+        if message.aps_frame().profile_id() == ZDP {
+            if let Some(frame) = zdp::Frame::<DeviceAnnounce>::parse(
+                message.aps_frame().cluster_id(),
+                message.message(),
+            ) {
+                let announce = frame.into_command();
+                let mut state = self.manager.lock();
+                state
+                    .addresses
+                    .retain(|_, address| *address != announce.ieee_address());
+                state
+                    .addresses
+                    .insert(announce.node_id(), announce.ieee_address());
+            }
+
+            return Ok(());
+        }
+
+        let Some(frame) = Frame::parse(message.message()) else {
+            return Ok(());
+        };
+
+        match frame.header().command_id() {
+            ReportAttributes::ID => {
+                if let Some(report) = frame.parse_payload::<ReportAttributes>() {
+                    self.reported(message, report).await?;
+                }
+            }
+            ConfigureReportingResponse::ID => {
+                if let Some(response) = frame.parse_payload::<ConfigureReportingResponse>() {
+                    self.configured(message, frame.header(), &response).await;
+                }
+            }
+            _ => {}
+        }
+
+        Ok(())
+    }
+
+    async fn reported(
+        &mut self,
+        message: &DefragmentedMessage,
+        report: ReportAttributes,
+    ) -> Result<(), Error> {
+        let node_id = message.sender();
+        let endpoint = message.aps_frame().source_endpoint();
+        let cluster_id = message.aps_frame().cluster_id();
+        let ieee_address = self.manager.ieee_address(node_id).await?;
+        let now = Instant::now();
+        let mut resumed = Vec::new();
+
+        {
+            let mut state = self.manager.lock();
+
+            for attribute in report.reports() {
+                let address = AttributeAddress::new(
+                    ieee_address,
+                    endpoint,
+                    cluster_id,
+                    attribute.attribute_id(),
+                );
+                state.cache.insert(
+                    address,
+                    attribute.data_type(),
+                    attribute.value().clone(),
+                    now,
+                );
+
+                if let Some(tracked) = state.configurations.get_mut(&address) {
+                    tracked.last_report = now;
+
+                    if tracked.overdue {
+                        tracked.overdue = false;
+                        resumed.push(address);
+                    }
+                }
+            }
+        }
+
+        trace!(
+            "Cached {} attributes of {ieee_address}",
+            report.reports().len()
+        );
+
+        for address in resumed {
+            emit(&self.events, ReportingEvent::Resumed { address }).await;
+        }
+
+        emit(
+            &self.events,
+            ReportingEvent::Reported {
+                node_id,
+                ieee_address,
+                endpoint,
+                cluster_id,
+                reports: report.into_reports(),
+            },
+        )
+        .await;
+        Ok(())
+    }
+
+    async fn configured(
+        &self,
+        message: &DefragmentedMessage,
+        header: &Header,
+        response: &ConfigureReportingResponse,
+    ) {
+        let Some(pending) = self
+            .manager
+            .lock()
+            .pending
+            .remove(&(message.sender(), header.transaction_sequence()))
+        else {
+            trace!("Ignoring configure reporting response not requested by the manager.");
+            return;
+        };
+
+        let events = self.manager.lock().track(pending, response, Instant::now());
+
+        for event in events {
+            emit(&self.events, event).await;
+        }
+    }
+
+    async fn flag_overdue(&self) {
+        let now = Instant::now();
+        let mut overdue = Vec::new();
+
+        for (address, tracked) in &mut self.manager.lock().configurations {
+            if !tracked.overdue && tracked.deadline(self.grace_period) <= now {
+                tracked.overdue = true;
+                overdue.push(ReportingEvent::Overdue {
+                    address: *address,
+                    last_report: tracked.last_report,
+                });
+            }
+        }
+
+        for event in overdue {
+            emit(&self.events, event).await;
+        }
+    }
+}
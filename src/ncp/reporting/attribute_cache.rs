@@ -0,0 +1,153 @@
+use std::collections::BTreeMap;
+use std::time::Instant;
+
+use crate::ember::Eui64;
+use crate::zcl::{DataType, Value};
+
+/// Identifies an attribute of a cluster on a device endpoint.
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub struct AttributeAddress {
+    ieee_address: Eui64,
+    endpoint: u8,
+    cluster_id: u16,
+    attribute_id: u16,
+}
+
+impl AttributeAddress {
+    /// Creates a new attribute address.
+    #[must_use]
+    pub const fn new(
+        ieee_address: Eui64,
+        endpoint: u8,
+        cluster_id: u16,
+        attribute_id: u16,
+    ) -> Self {
+        Self {
+            ieee_address,
+            endpoint,
+            cluster_id,
+            attribute_id,
+        }
+    }
+
+    /// Returns the IEEE address of the device.
+    #[must_use]
+    pub const fn ieee_address(&self) -> Eui64 {
+        self.ieee_address
+    }
+
+    /// Returns the endpoint.
+    #[must_use]
+    pub const fn endpoint(&self) -> u8 {
+        self.endpoint
+    }
+
+    /// Returns the cluster ID.
+    #[must_use]
+    pub const fn cluster_id(&self) -> u16 {
+        self.cluster_id
+    }
+
+    /// Returns the attribute ID.
+    #[must_use]
+    pub const fn attribute_id(&self) -> u16 {
+        self.attribute_id
+    }
+}
+
+/// The latest known value of an attribute.
+#[derive(Clone, Debug, PartialEq)]
+pub struct CachedAttribute {
+    data_type: DataType,
+    value: Value,
+    updated: Instant,
+}
+
+impl CachedAttribute {
+    /// Returns the data type of the value.
+    #[must_use]
+    pub const fn data_type(&self) -> DataType {
+        self.data_type
+    }
+
+    /// Returns the value.
+    #[must_use]
+    pub const fn value(&self) -> &Value {
+        &self.value
+    }
+
+    /// Returns when the value was received.
+    #[must_use]
+    pub const fn updated(&self) -> Instant {
+        self.updated
+    }
+}
+
+/// Latest attribute values keyed by device IEEE address, endpoint, cluster, and attribute.
+///
+/// Keying by IEEE address keeps the values of a device when its network
+/// address changes after a rejoin.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct AttributeCache {
+    attributes: BTreeMap<AttributeAddress, CachedAttribute>,
+}
+
+impl AttributeCache {
+    /// Stores a value, replacing any previous value of the attribute.
+    pub fn insert(
+        &mut self,
+        address: AttributeAddress,
+        data_type: DataType,
+        value: Value,
+        updated: Instant,
+    ) {
+        self.attributes.insert(
+            address,
+            CachedAttribute {
+                data_type,
+                value,
+                updated,
+            },
+        );
+    }
+
+    /// Returns the latest value of an attribute.
+    #[must_use]
+    pub fn get(&self, address: &AttributeAddress) -> Option<&CachedAttribute> {
+        self.attributes.get(address)
+    }
+
+    /// Returns the latest values of all attributes of a device.
+    pub fn device(
+        &self,
+        ieee_address: Eui64,
+    ) -> impl Iterator<Item = (&AttributeAddress, &CachedAttribute)> {
+        self.attributes.range(
+            AttributeAddress::new(ieee_address, u8::MIN, u16::MIN, u16::MIN)
+                ..=AttributeAddress::new(ieee_address, u8::MAX, u16::MAX, u16::MAX),
+        )
+    }
+
+    /// Removes all values of a device.
+    pub fn remove_device(&mut self, ieee_address: Eui64) {
+        self.attributes
+            .retain(|address, _| address.ieee_address != ieee_address);
+    }
+
+    /// Returns an iterator over all cached values.
+    pub fn iter(&self) -> impl Iterator<Item = (&AttributeAddress, &CachedAttribute)> {
+        self.attributes.iter()
+    }
+
+    /// Returns the number of cached values.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.attributes.len()
+    }
+
+    /// Returns whether the cache is empty.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.attributes.is_empty()
+    }
+}
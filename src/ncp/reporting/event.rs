@@ -0,0 +1,47 @@
+use std::time::Instant;
+
+use crate::ember::{Eui64, NodeId};
+use crate::ncp::reporting::AttributeAddress;
+use crate::zcl::Status;
+use crate::zcl::general::AttributeReport;
+
+/// Reporting configuration and attribute report events reported by a
+/// [`ReportingManager`](crate::ReportingManager).
+#[derive(Clone, Debug, PartialEq)]
+pub enum ReportingEvent {
+    /// A device answered a reporting configuration of an attribute.
+    Configured {
+        /// The configured attribute.
+        address: AttributeAddress,
+        /// The configuration status reported by the device.
+        status: Result<Status, u8>,
+    },
+
+    /// A device reported attribute values.
+    Reported {
+        /// The reporting device.
+        node_id: NodeId,
+        /// The IEEE address of the reporting device.
+        ieee_address: Eui64,
+        /// The reporting endpoint.
+        endpoint: u8,
+        /// The cluster of the reported attributes.
+        cluster_id: u16,
+        /// The reported attributes.
+        reports: Box<[AttributeReport]>,
+    },
+
+    /// No report of an attribute arrived within its maximum reporting interval.
+    Overdue {
+        /// The overdue attribute.
+        address: AttributeAddress,
+        /// When the attribute was last reported or configured.
+        last_report: Instant,
+    },
+
+    /// An overdue attribute was reported again.
+    Resumed {
+        /// The attribute.
+        address: AttributeAddress,
+    },
+}
@@ -0,0 +1,342 @@
+//! Periodic many-to-one route requests.
+
+use std::time::Duration;
+
+use log::{debug, info, trace, warn};
+use tokio::sync::mpsc::{Receiver, Sender, channel};
+use tokio::time::{Instant, timeout_at};
+
+pub use self::event::RouteRefreshEvent;
+use crate::ember::{Status, concentrator};
+use crate::ezsp::config;
+use crate::frame::parameters::messaging::handler::Handler as MessagingHandler;
+use crate::ncp::service::{MESSAGES_CAPACITY, emit};
+use crate::ncp::{Message, Ncp};
+use crate::{Callback, Configuration, Error, Messaging, ValueError};
+
+mod event;
+
+/// Schedule of many-to-one route requests.
+///
+/// Serve the schedule with [`Ncp::serve_route_refresh`]. A request is sent
+/// every [`interval`](Self::interval), limited to the concentrator's
+/// [`min_time`](Self::min_time) and [`max_time`](Self::max_time). Once route
+/// errors or failed deliveries since the previous request reach their
+/// threshold, the next request is sent as soon as the minimum time allows.
+/// A threshold of zero disables the corresponding trigger.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct RouteRefresh {
+    concentrator_type: concentrator::Type,
+    radius: u8,
+    interval: Duration,
+    min_time: Duration,
+    max_time: Duration,
+    route_error_threshold: u8,
+    delivery_failure_threshold: u8,
+}
+
+impl RouteRefresh {
+    /// Takes the type, times, thresholds and radius from concentrator parameters.
+    #[must_use]
+    pub const fn with_concentrator(mut self, parameters: &concentrator::Parameters) -> Self {
+        if let Ok(concentrator_type) = parameters.concentrator_type() {
+            self.concentrator_type = concentrator_type;
+        }
+
+        self.radius = parameters.max_hops();
+        self.min_time = parameters.min_time();
+        self.max_time = parameters.max_time();
+        self.route_error_threshold = parameters.route_error_threshold();
+        self.delivery_failure_threshold = parameters.delivery_failure_threshold();
+        self
+    }
+
+    /// Sets the concentrator type announced by the requests.
+    #[must_use]
+    pub const fn with_concentrator_type(mut self, concentrator_type: concentrator::Type) -> Self {
+        self.concentrator_type = concentrator_type;
+        self
+    }
+
+    /// Sets the radius of the requests.
+    ///
+    /// A radius of zero uses the NCP's configured maximum hops.
+    #[must_use]
+    pub const fn with_radius(mut self, radius: u8) -> Self {
+        self.radius = radius;
+        self
+    }
+
+    /// Sets the time between scheduled requests.
+    #[must_use]
+    pub const fn with_interval(mut self, interval: Duration) -> Self {
+        self.interval = interval;
+        self
+    }
+
+    /// Sets the minimum time between two requests.
+    #[must_use]
+    pub const fn with_min_time(mut self, min_time: Duration) -> Self {
+        self.min_time = min_time;
+        self
+    }
+
+    /// Sets the maximum time between two requests.
+    #[must_use]
+    pub const fn with_max_time(mut self, max_time: Duration) -> Self {
+        self.max_time = max_time;
+        self
+    }
+
+    /// Sets the number of route errors that trigger an early request.
+    #[must_use]
+    pub const fn with_route_error_threshold(mut self, route_error_threshold: u8) -> Self {
+        self.route_error_threshold = route_error_threshold;
+        self
+    }
+
+    /// Sets the number of delivery failures that trigger an early request.
+    #[must_use]
+    pub const fn with_delivery_failure_threshold(mut self, delivery_failure_threshold: u8) -> Self {
+        self.delivery_failure_threshold = delivery_failure_threshold;
+        self
+    }
+
+    /// Returns the concentrator type announced by the requests.
+    #[must_use]
+    pub const fn concentrator_type(&self) -> concentrator::Type {
+        self.concentrator_type
+    }
+
+    /// Returns the radius of the requests.
+    #[must_use]
+    pub const fn radius(&self) -> u8 {
+        self.radius
+    }
+
+    /// Returns the time between scheduled requests.
+    #[must_use]
+    pub const fn interval(&self) -> Duration {
+        self.interval
+    }
+
+    /// Returns the minimum time between two requests.
+    #[must_use]
+    pub const fn min_time(&self) -> Duration {
+        self.min_time
+    }
+
+    /// Returns the maximum time between two requests.
+    #[must_use]
+    pub const fn max_time(&self) -> Duration {
+        self.max_time
+    }
+
+    /// Returns the number of route errors that trigger an early request.
+    #[must_use]
+    pub const fn route_error_threshold(&self) -> u8 {
+        self.route_error_threshold
+    }
+
+    /// Returns the number of delivery failures that trigger an early request.
+    #[must_use]
+    pub const fn delivery_failure_threshold(&self) -> u8 {
+        self.delivery_failure_threshold
+    }
+
+    /// Returns the interval limited to the minimum and maximum time.
+    fn period(&self) -> Duration {
+        self.interval.min(self.max_time).max(self.min_time)
+    }
+}
+
+impl Default for RouteRefresh {
+    fn default() -> Self {
+        Self {
+            concentrator_type: concentrator::Type::HighRam,
+            radius: 0,
+            interval: Duration::from_mins(1),
+            min_time: Duration::from_secs(10),
+            max_time: Duration::from_mins(1),
+            route_error_threshold: 3,
+            delivery_failure_threshold: 1,
+        }
+    }
+}
+
+impl Ncp {
+    /// Starts refreshing many-to-one routes.
+    ///
+    /// The event handler copies all callbacks to the returned future, which
+    /// sends [`Messaging::send_many_to_one_route_request`] on the schedule of
+    /// `refresh`. `incomingRouteError` and `incomingNetworkStatus` callbacks
+    /// count as route errors, and `messageSent` callbacks with a failed status
+    /// count as delivery failures. Each request is reported to `events`
+    /// together with its trigger. The future completes when the event handler
+    /// stops.
+    ///
+    /// The first request is scheduled one period after the call, since
+    /// [`Builder::start`](crate::Builder::start) already sends one.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`Error`] if the NCP's maximum hops cannot be read for a
+    /// radius of zero, or the callback subscription cannot be registered with
+    /// the event handler.
+    pub async fn serve_route_refresh(
+        &mut self,
+        mut refresh: RouteRefresh,
+        events: Sender<RouteRefreshEvent>,
+    ) -> Result<impl Future<Output = ()> + Send + 'static, Error> {
+        if refresh.radius == 0 {
+            let max_hops = self
+                .connection
+                .get_configuration_value(config::Id::MaxHops)
+                .await?;
+            refresh.radius = max_hops
+                .try_into()
+                .map_err(|_| ValueError::InvalidRouteRadius(max_hops))?;
+        }
+
+        let (sender, callbacks) = channel(MESSAGES_CAPACITY);
+        self.event_handler_handle
+            .send(Message::Callbacks { sender })
+            .await?;
+
+        let session = Session {
+            ncp: self.clone(),
+            refresh,
+            events,
+            last_request: Instant::now(),
+            route_errors: 0,
+            delivery_failures: 0,
+        };
+
+        Ok(session.run(callbacks))
+    }
+}
+
+struct Session {
+    ncp: Ncp,
+    refresh: RouteRefresh,
+    events: Sender<RouteRefreshEvent>,
+    last_request: Instant,
+    route_errors: u8,
+    delivery_failures: u8,
+}
+
+impl Session {
+    async fn run(mut self, mut callbacks: Receiver<Callback>) {
+        loop {
+            match timeout_at(self.deadline(), callbacks.recv()).await {
+                Ok(Some(callback)) => self.count(&callback),
+                Ok(None) => break,
+                Err(_) => self.request().await,
+            }
+        }
+
+        debug!("Callback subscription closed. Route refresh terminating.");
+    }
+
+    fn count(&mut self, callback: &Callback) {
+        let Callback::Messaging(messaging) = callback else {
+            return;
+        };
+
+        match messaging {
+            MessagingHandler::IncomingRouteError(error) => {
+                trace!("Route error for {:#06X}.", error.target());
+                self.route_errors = self.route_errors.saturating_add(1);
+            }
+            MessagingHandler::IncomingNetworkStatus(status) => {
+                trace!(
+                    "Network status {:#04X} for {:#06X}.",
+                    status.error_code(),
+                    status.target()
+                );
+                self.route_errors = self.route_errors.saturating_add(1);
+            }
+            MessagingHandler::MessageSent(sent) if sent.status() != Ok(Status::Success) => {
+                trace!("Delivery to {:#06X} failed.", sent.index_or_destination());
+                self.delivery_failures = self.delivery_failures.saturating_add(1);
+            }
+            _ => {}
+        }
+    }
+
+    /// Returns the trigger of an early request, if any.
+    const fn trigger(&self) -> Option<RouteRefreshEvent> {
+        let route_error_threshold = self.refresh.route_error_threshold;
+        let delivery_failure_threshold = self.refresh.delivery_failure_threshold;
+
+        if route_error_threshold > 0 && self.route_errors >= route_error_threshold {
+            Some(RouteRefreshEvent::RouteErrors {
+                count: self.route_errors,
+            })
+        } else if delivery_failure_threshold > 0
+            && self.delivery_failures >= delivery_failure_threshold
+        {
+            Some(RouteRefreshEvent::DeliveryFailures {
+                count: self.delivery_failures,
+            })
+        } else {
+            None
+        }
+    }
+
+    /// Returns when the next request is due.
+    fn deadline(&self) -> Instant {
+        if self.trigger().is_some() {
+            self.last_request + self.refresh.min_time
+        } else {
+            self.last_request + self.refresh.period()
+        }
+    }
+
+    async fn request(&mut self) {
+        let event = self.trigger().unwrap_or(RouteRefreshEvent::Scheduled);
+        self.last_request = Instant::now();
+        self.route_errors = 0;
+        self.delivery_failures = 0;
+
+        info!("Sending many-to-one route request: {event:?}");
+
+        if let Err(error) = self
+            .ncp
+            .connection
+            .send_many_to_one_route_request(self.refresh.concentrator_type, self.refresh.radius)
+            .await
+        {
+            warn!("Failed to send many-to-one route request: {error}");
+            return;
+        }
+
+        emit(&self.events, event).await;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::RouteRefresh;
+
+    #[test]
+    fn period_is_limited_to_concentrator_times() {
+        let refresh = RouteRefresh::default()
+            .with_min_time(Duration::from_secs(10))
+            .with_max_time(Duration::from_mins(1));
+        assert_eq!(
+            refresh.with_interval(Duration::from_secs(30)).period(),
+            Duration::from_secs(30)
+        );
+        assert_eq!(
+            refresh.with_interval(Duration::from_secs(1)).period(),
+            Duration::from_secs(10)
+        );
+        assert_eq!(
+            refresh.with_interval(Duration::from_hours(1)).period(),
+            Duration::from_mins(1)
+        );
+    }
+}
@@ -0,0 +1,18 @@
+/// Many-to-one route requests sent by [`Ncp::serve_route_refresh`](crate::Ncp::serve_route_refresh).
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum RouteRefreshEvent {
+    /// The refresh interval elapsed.
+    Scheduled,
+
+    /// Route errors reached the threshold.
+    RouteErrors {
+        /// The number of route errors since the previous request.
+        count: u8,
+    },
+
+    /// Delivery failures reached the threshold.
+    DeliveryFailures {
+        /// The number of delivery failures since the previous request.
+        count: u8,
+    },
+}
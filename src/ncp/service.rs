@@ -0,0 +1,82 @@
+//! Helpers shared by the services of the NCP.
+
+use std::any::type_name;
+use std::fs::{self, File};
+use std::io::{self, BufWriter};
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, MutexGuard, PoisonError};
+
+use log::trace;
+use tokio::sync::mpsc::Sender;
+
+/// Capacity of the channels a service subscribes to the event handler with.
+pub const MESSAGES_CAPACITY: usize = 16;
+
+/// Locks shared service state.
+///
+/// A poisoned lock is recovered, since the state stays consistent between
+/// the short critical sections of the services.
+pub fn lock<T>(mutex: &Mutex<T>) -> MutexGuard<'_, T> {
+    mutex.lock().unwrap_or_else(PoisonError::into_inner)
+}
+
+/// Sends an event to the application, which may have dropped its receiver.
+pub async fn emit<T>(events: &Sender<T>, event: T)
+where
+    T: Send,
+{
+    if let Err(error) = events.send(event).await {
+        trace!("Receiver of {} dropped: {error}", type_name::<T>());
+    }
+}
+
+/// Writes the file at `path` through a temporary file next to it.
+///
+/// The temporary file is synced and then renamed over `path`, so an
+/// interrupted write keeps the previous contents. Its name appends `.tmp` to
+/// the full file name, so files differing only in their extension do not
+/// share a temporary file.
+///
+/// # Errors
+///
+/// Returns an [`io::Error`] if `write` fails or the file cannot be created,
+/// synced, or renamed.
+pub fn write_atomically(
+    path: &Path,
+    write: impl FnOnce(&mut BufWriter<File>) -> io::Result<()>,
+) -> io::Result<()> {
+    let temporary = temporary_path(path);
+    let mut writer = BufWriter::new(File::create(&temporary)?);
+    write(&mut writer)?;
+    writer
+        .into_inner()
+        .map_err(io::IntoInnerError::into_error)?
+        .sync_all()?;
+    fs::rename(temporary, path)
+}
+
+/// Returns the path of the temporary file used to write `path`.
+fn temporary_path(path: &Path) -> PathBuf {
+    let mut temporary = path.as_os_str().to_owned();
+    temporary.push(".tmp");
+    temporary.into()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    use super::temporary_path;
+
+    #[test]
+    fn temporary_path_appends_to_file_name() {
+        assert_eq!(
+            temporary_path(Path::new("/var/lib/zigbee/devices.txt")),
+            Path::new("/var/lib/zigbee/devices.txt.tmp")
+        );
+        assert_ne!(
+            temporary_path(Path::new("network.devices")),
+            temporary_path(Path::new("network.groups"))
+        );
+    }
+}
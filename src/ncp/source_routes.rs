@@ -0,0 +1,232 @@
+//! Host-side source route table.
+
+use std::collections::BTreeMap;
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::time::SystemTime;
+
+use log::{debug, info, trace, warn};
+use tokio::sync::mpsc::{Receiver, channel};
+
+pub use self::source_route::SourceRoute;
+use crate::ember::NodeId;
+use crate::frame::parameters::messaging::handler::{
+    Handler as MessagingHandler, IncomingRouteError, IncomingRouteRecord,
+};
+use crate::ncp::service::{MESSAGES_CAPACITY, lock};
+use crate::ncp::{Message, Ncp};
+use crate::types::SourceRouteDiscoveryMode;
+use crate::{Callback, Error, Messaging, Networking};
+
+mod source_route;
+
+/// Source routes kept by the host, keyed by destination.
+///
+/// [`Ncp::serve_source_routes`] records the route of every
+/// `incomingRouteRecord` callback and expires the routes leading to or
+/// through the target of an `incomingRouteError` callback. The table is not
+/// limited by the size of the NCP's source route table, so it shows the
+/// routes of the whole network for diagnostics. Clones share the same routes.
+#[derive(Clone, Debug, Default)]
+pub struct SourceRouteTable {
+    routes: Arc<Mutex<BTreeMap<NodeId, SourceRoute>>>,
+}
+
+impl SourceRouteTable {
+    /// Returns the route to `destination`.
+    #[must_use]
+    pub fn get(&self, destination: NodeId) -> Option<SourceRoute> {
+        self.lock().get(&destination).cloned()
+    }
+
+    /// Returns all routes ordered by destination.
+    #[must_use]
+    pub fn routes(&self) -> Vec<SourceRoute> {
+        self.lock().values().cloned().collect()
+    }
+
+    /// Returns the number of routes.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.lock().len()
+    }
+
+    /// Returns whether the table contains no routes.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.lock().is_empty()
+    }
+
+    /// Removes the route to `destination` and returns it.
+    #[must_use]
+    pub fn remove(&self, destination: NodeId) -> Option<SourceRoute> {
+        self.lock().remove(&destination)
+    }
+
+    /// Removes all routes.
+    pub fn clear(&self) {
+        self.lock().clear();
+    }
+
+    fn insert(&self, route: SourceRoute) {
+        self.lock().insert(route.destination(), route);
+    }
+
+    /// Removes the routes leading to or through `node_id` and returns their number.
+    fn expire(&self, node_id: NodeId) -> usize {
+        let mut routes = self.lock();
+        let before = routes.len();
+        routes.retain(|_, route| !route.uses(node_id));
+        before - routes.len()
+    }
+
+    fn lock(&self) -> MutexGuard<'_, BTreeMap<NodeId, SourceRoute>> {
+        lock(&self.routes)
+    }
+}
+
+impl Ncp {
+    /// Starts maintaining a host-side source route table.
+    ///
+    /// The event handler copies all callbacks to the returned future, which
+    /// keeps `table` up to date from route records and route errors. The
+    /// future completes when the event handler stops.
+    ///
+    /// A high-RAM concentrator's devices stop sending route records once one
+    /// was delivered, so routes the NCP drops from its full source route table
+    /// are not learned again by themselves. Whenever a route error expires
+    /// routes while the host table holds more routes than the NCP's table, the
+    /// future therefore reschedules the NCP's next many-to-one route request
+    /// with [`Messaging::set_source_route_discovery_mode`], so that devices
+    /// send fresh route records.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`Error`] if the size of the NCP's source route table cannot
+    /// be read or the callback subscription cannot be registered with the
+    /// event handler.
+    pub async fn serve_source_routes(
+        &mut self,
+        table: SourceRouteTable,
+    ) -> Result<impl Future<Output = ()> + Send + 'static, Error> {
+        let ncp_table_size = self.connection.get_source_route_table_total_size().await?;
+        debug!("NCP source route table holds {ncp_table_size} routes.");
+
+        let (sender, callbacks) = channel(MESSAGES_CAPACITY);
+        self.event_handler_handle
+            .send(Message::Callbacks { sender })
+            .await?;
+
+        let session = Session {
+            ncp: self.clone(),
+            table,
+            ncp_table_size: ncp_table_size.into(),
+        };
+
+        Ok(session.run(callbacks))
+    }
+}
+
+struct Session {
+    ncp: Ncp,
+    table: SourceRouteTable,
+    ncp_table_size: usize,
+}
+
+impl Session {
+    async fn run(mut self, mut callbacks: Receiver<Callback>) {
+        while let Some(callback) = callbacks.recv().await {
+            match callback {
+                Callback::Messaging(MessagingHandler::IncomingRouteRecord(record)) => {
+                    self.record(&record);
+                }
+                Callback::Messaging(MessagingHandler::IncomingRouteError(error)) => {
+                    self.expire(&error).await;
+                }
+                _ => {}
+            }
+        }
+
+        debug!("Callback subscription closed. Source route table terminating.");
+    }
+
+    fn record(&self, record: &IncomingRouteRecord) {
+        trace!(
+            "Route record from {:#06X} via {:04X?}.",
+            record.source(),
+            record.relays()
+        );
+        self.table.insert(SourceRoute::new(
+            record.source(),
+            record.source_eui(),
+            record.relays().into(),
+            record.last_hop_lqi(),
+            record.last_hop_rssi(),
+            SystemTime::now(),
+        ));
+    }
+
+    async fn expire(&mut self, error: &IncomingRouteError) {
+        let target = error.target();
+        let expired = self.table.expire(target);
+
+        if expired == 0 {
+            return;
+        }
+
+        debug!("Route error for {target:#06X} expired {expired} routes.");
+
+        if self.table.len() + expired <= self.ncp_table_size {
+            return;
+        }
+
+        match self
+            .ncp
+            .connection
+            .get_source_route_table_filled_size()
+            .await
+        {
+            Ok(filled) if usize::from(filled) < self.ncp_table_size => return,
+            Ok(_) => info!("NCP source route table is full. Rescheduling route discovery."),
+            Err(error) => warn!("Failed to read NCP source route table size: {error}"),
+        }
+
+        if let Err(error) = self
+            .ncp
+            .connection
+            .set_source_route_discovery_mode(SourceRouteDiscoveryMode::Reschedule)
+            .await
+        {
+            warn!("Failed to reschedule route discovery: {error}");
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::SystemTime;
+
+    use super::{SourceRoute, SourceRouteTable};
+    use crate::ember::Eui64;
+
+    #[test]
+    fn route_errors_expire_routes_through_target() {
+        let table = SourceRouteTable::default();
+        let now = SystemTime::now();
+
+        for (destination, relays) in [(0x1111, vec![0x2222]), (0x3333, vec![0x4444])] {
+            table.insert(SourceRoute::new(
+                destination,
+                Eui64::nil(),
+                relays.into(),
+                255,
+                -40,
+                now,
+            ));
+        }
+
+        assert_eq!(table.expire(0x2222), 1);
+        assert!(table.get(0x1111).is_none());
+        assert_eq!(table.expire(0x3333), 1);
+        assert!(table.is_empty());
+    }
+}
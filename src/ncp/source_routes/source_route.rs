@@ -0,0 +1,76 @@
+use std::time::SystemTime;
+
+use crate::ember::{Eui64, NodeId};
+
+/// A source route learned from a route record.
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub struct SourceRoute {
+    destination: NodeId,
+    ieee_address: Eui64,
+    relays: Box<[NodeId]>,
+    last_hop_lqi: u8,
+    last_hop_rssi: i8,
+    recorded: SystemTime,
+}
+
+impl SourceRoute {
+    pub(crate) const fn new(
+        destination: NodeId,
+        ieee_address: Eui64,
+        relays: Box<[NodeId]>,
+        last_hop_lqi: u8,
+        last_hop_rssi: i8,
+        recorded: SystemTime,
+    ) -> Self {
+        Self {
+            destination,
+            ieee_address,
+            relays,
+            last_hop_lqi,
+            last_hop_rssi,
+            recorded,
+        }
+    }
+
+    /// Returns the network address of the destination.
+    #[must_use]
+    pub const fn destination(&self) -> NodeId {
+        self.destination
+    }
+
+    /// Returns the IEEE address of the destination.
+    #[must_use]
+    pub const fn ieee_address(&self) -> Eui64 {
+        self.ieee_address
+    }
+
+    /// Returns the network addresses of the relays in the order of the route record.
+    #[must_use]
+    pub fn relays(&self) -> &[NodeId] {
+        &self.relays
+    }
+
+    /// Returns the link quality of the route record's last hop.
+    #[must_use]
+    pub const fn last_hop_lqi(&self) -> u8 {
+        self.last_hop_lqi
+    }
+
+    /// Returns the signal strength of the route record's last hop.
+    #[must_use]
+    pub const fn last_hop_rssi(&self) -> i8 {
+        self.last_hop_rssi
+    }
+
+    /// Returns when the route record was received.
+    #[must_use]
+    pub const fn recorded(&self) -> SystemTime {
+        self.recorded
+    }
+
+    /// Returns whether the route leads to or through `node_id`.
+    #[must_use]
+    pub fn uses(&self, node_id: NodeId) -> bool {
+        self.destination == node_id || self.relays.contains(&node_id)
+    }
+}
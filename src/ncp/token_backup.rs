@@ -0,0 +1,303 @@
+//! Dump and restore of the NCP's NVM3 tokens.
+
+use std::collections::BTreeMap;
+use std::fmt::{self, Display, Formatter, Write};
+use std::io::{self, ErrorKind};
+
+use log::{debug, info, warn};
+
+pub use self::nvm_token::NvmToken;
+pub use self::token_restore::TokenRestore;
+use crate::ember::token::{Data, Info};
+use crate::ncp::Ncp;
+use crate::{Error, TokenInterface, ValueError};
+
+mod nvm_token;
+mod token_restore;
+
+/// Capacity of the data field of an EZSP token.
+const DATA_SIZE: usize = 64;
+
+/// The NVM3 tokens of an NCP.
+///
+/// Create a dump with [`Ncp::backup_tokens`] and write it to another NCP with
+/// [`Ncp::restore_tokens`], for example when replacing the hardware or before
+/// a firmware upgrade that erases the NVM. The dump is stored as text with one
+/// token per line holding the hexadecimal NVM3 key, the element size, the
+/// number of elements, and the hexadecimal data of each element separated by
+/// whitespace.
+///
+/// The tokens include the network and link keys. Store dumps using
+/// protections appropriate for network credentials.
+#[derive(Clone, Debug, Default, Eq, Hash, PartialEq)]
+pub struct TokenBackup {
+    tokens: Vec<NvmToken>,
+}
+
+impl TokenBackup {
+    /// Creates a dump of the given tokens.
+    #[must_use]
+    pub const fn new(tokens: Vec<NvmToken>) -> Self {
+        Self { tokens }
+    }
+
+    /// Returns the tokens.
+    #[must_use]
+    pub fn tokens(&self) -> &[NvmToken] {
+        &self.tokens
+    }
+
+    /// Returns the token with the given NVM3 key.
+    #[must_use]
+    pub fn get(&self, key: u32) -> Option<&NvmToken> {
+        self.tokens.iter().find(|token| token.key() == key)
+    }
+
+    /// Renders the dump in its text format.
+    #[must_use]
+    pub fn to_text(&self) -> String {
+        self.to_string()
+    }
+
+    /// Parses a dump from its text format.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`io::Error`] of kind [`ErrorKind::InvalidData`] if a line
+    /// is malformed or its data does not match the declared sizes.
+    pub fn from_text(text: &str) -> io::Result<Self> {
+        text.lines()
+            .filter(|line| !line.trim().is_empty())
+            .map(parse_token)
+            .collect::<io::Result<_>>()
+            .map(Self::new)
+    }
+}
+
+impl Display for TokenBackup {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        for token in &self.tokens {
+            write!(
+                f,
+                "{:#010X} {} {}",
+                token.key(),
+                token.size(),
+                token.array_size()
+            )?;
+
+            for element in token.elements() {
+                f.write_char(' ')?;
+
+                for byte in element {
+                    write!(f, "{byte:02X}")?;
+                }
+            }
+
+            writeln!(f)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl Ncp {
+    /// Reads all NVM3 tokens of the NCP.
+    ///
+    /// The tokens are enumerated with [`TokenInterface::get_token_info`] and
+    /// every element is read with [`TokenInterface::get_token_data`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`Error`] if the token table or a token cannot be read.
+    pub async fn backup_tokens(&mut self) -> Result<TokenBackup, Error> {
+        let mut tokens = Vec::new();
+
+        for info in self.token_infos().await?.into_values() {
+            let mut elements = Vec::new();
+
+            for index in 0..element_count(&info) {
+                let data = self
+                    .connection
+                    .get_token_data(info.nvm3_key(), index.into())
+                    .await?;
+                elements.push(payload(&data, info.size()).into());
+            }
+
+            tokens.push(NvmToken::new(info.nvm3_key(), info.size(), elements));
+        }
+
+        info!("Backed up {} tokens.", tokens.len());
+        Ok(TokenBackup::new(tokens))
+    }
+
+    /// Writes the tokens of a dump to the NCP and returns their number.
+    ///
+    /// Only tokens the NCP knows with the same element size and number of
+    /// elements are written, so dumps of other firmware versions are restored
+    /// as far as they match. Tokens excluded by `restore` are skipped. Each
+    /// element is read back after writing it. Reset the NCP afterwards so
+    /// that the stack loads the restored tokens.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`Error`] if the token table cannot be read, a token cannot
+    /// be written, or a written token reads back differently.
+    pub async fn restore_tokens(
+        &mut self,
+        backup: &TokenBackup,
+        restore: &TokenRestore,
+    ) -> Result<usize, Error> {
+        let infos = self.token_infos().await?;
+        let mut restored = 0;
+
+        for token in backup.tokens() {
+            let Some(info) = infos.get(&token.key()) else {
+                warn!("Skipping unknown token {:#010X}.", token.key());
+                continue;
+            };
+
+            if !restore.counters() && info.is_cnt() {
+                debug!("Skipping counter token {:#010X}.", token.key());
+                continue;
+            }
+
+            if restore.excluded().contains(&token.key()) {
+                debug!("Skipping excluded token {:#010X}.", token.key());
+                continue;
+            }
+
+            if info.size() != token.size() || usize::from(element_count(info)) != token.array_size()
+            {
+                warn!(
+                    "Skipping token {:#010X} with mismatching size {}x{} (expected {}x{}).",
+                    token.key(),
+                    token.array_size(),
+                    token.size(),
+                    element_count(info),
+                    info.size()
+                );
+                continue;
+            }
+
+            self.restore_token(token).await?;
+            restored += 1;
+        }
+
+        info!("Restored {restored} of {} tokens.", backup.tokens().len());
+        Ok(restored)
+    }
+
+    /// Returns the NCP's token information keyed by NVM3 key.
+    async fn token_infos(&mut self) -> Result<BTreeMap<u32, Info>, Error> {
+        let mut infos = BTreeMap::new();
+
+        for index in 0..self.connection.get_token_count().await? {
+            let info = self.connection.get_token_info(index).await?;
+            infos.insert(info.nvm3_key(), info);
+        }
+
+        Ok(infos)
+    }
+
+    /// Writes and verifies the elements of a token.
+    async fn restore_token(&mut self, token: &NvmToken) -> Result<(), Error> {
+        for (index, element) in (0..=u8::MAX).zip(token.elements()) {
+            let mut data = [0; DATA_SIZE];
+            data[..element.len()].copy_from_slice(element);
+            self.connection
+                .set_token_data(
+                    token.key(),
+                    index.into(),
+                    Data::new(token.size().into(), data),
+                )
+                .await?;
+
+            let written = self
+                .connection
+                .get_token_data(token.key(), index.into())
+                .await?;
+
+            if payload(&written, token.size()) != &**element {
+                return Err(ValueError::TokenVerification {
+                    key: token.key(),
+                    index,
+                }
+                .into());
+            }
+        }
+
+        debug!("Restored token {:#010X}.", token.key());
+        Ok(())
+    }
+}
+
+/// Returns the number of elements of a token.
+const fn element_count(info: &Info) -> u8 {
+    if info.is_idx() { info.array_size() } else { 1 }
+}
+
+/// Returns the bytes of a token element.
+fn payload(data: &Data, size: u8) -> &[u8] {
+    &data.data()[..usize::from(size).min(DATA_SIZE)]
+}
+
+fn parse_token(line: &str) -> io::Result<NvmToken> {
+    let mut fields = line.split_whitespace();
+    let (Some(key), Some(size), Some(array_size)) = (fields.next(), fields.next(), fields.next())
+    else {
+        return Err(invalid(line));
+    };
+
+    let key = key
+        .strip_prefix("0x")
+        .and_then(|hex| u32::from_str_radix(hex, 16).ok())
+        .ok_or_else(|| invalid(line))?;
+    let size: u8 = size.parse().map_err(|_| invalid(line))?;
+    let array_size: usize = array_size.parse().map_err(|_| invalid(line))?;
+
+    if usize::from(size) > DATA_SIZE {
+        return Err(invalid(line));
+    }
+
+    let elements = fields
+        .map(|element| parse_hex(element).filter(|bytes| bytes.len() == usize::from(size)))
+        .collect::<Option<Vec<_>>>()
+        .filter(|elements| elements.len() == array_size)
+        .ok_or_else(|| invalid(line))?;
+    Ok(NvmToken::new(key, size, elements))
+}
+
+fn parse_hex(hex: &str) -> Option<Box<[u8]>> {
+    if !hex.len().is_multiple_of(2) {
+        return None;
+    }
+
+    (0..hex.len())
+        .step_by(2)
+        .map(|start| {
+            hex.get(start..start + 2)
+                .and_then(|byte| u8::from_str_radix(byte, 16).ok())
+        })
+        .collect()
+}
+
+fn invalid(line: &str) -> io::Error {
+    io::Error::new(ErrorKind::InvalidData, format!("Invalid token: {line}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{NvmToken, TokenBackup};
+
+    #[test]
+    fn text_format_round_trips() {
+        let backup = TokenBackup::new(vec![
+            NvmToken::new(0x0001_E10A, 2, vec![[0x12, 0x34].into()]),
+            NvmToken::new(0x0004_E200, 1, vec![[0xAB].into(), [0xCD].into()]),
+        ]);
+        let text = backup.to_text();
+        assert_eq!(text, "0x0001E10A 2 1 1234\n0x0004E200 1 2 AB CD\n");
+        assert_eq!(TokenBackup::from_text(&text).expect("valid dump"), backup);
+        assert!(TokenBackup::from_text("0x0001E10A 2 1 12").is_err());
+    }
+}
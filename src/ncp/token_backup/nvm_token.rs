@@ -0,0 +1,46 @@
+/// An NVM3 token read from the NCP.
+///
+/// Non-indexed tokens hold a single element. Indexed tokens hold one element
+/// per array entry, each [`size`](Self::size) bytes long.
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub struct NvmToken {
+    key: u32,
+    size: u8,
+    elements: Vec<Box<[u8]>>,
+}
+
+impl NvmToken {
+    /// Creates a token with the given NVM3 key, element size and elements.
+    #[must_use]
+    pub const fn new(key: u32, size: u8, elements: Vec<Box<[u8]>>) -> Self {
+        Self {
+            key,
+            size,
+            elements,
+        }
+    }
+
+    /// Returns the NVM3 key of the token.
+    #[must_use]
+    pub const fn key(&self) -> u32 {
+        self.key
+    }
+
+    /// Returns the size of an element in bytes.
+    #[must_use]
+    pub const fn size(&self) -> u8 {
+        self.size
+    }
+
+    /// Returns the number of elements.
+    #[must_use]
+    pub const fn array_size(&self) -> usize {
+        self.elements.len()
+    }
+
+    /// Returns the data of the elements.
+    #[must_use]
+    pub fn elements(&self) -> &[Box<[u8]>] {
+        &self.elements
+    }
+}
@@ -0,0 +1,41 @@
+use std::collections::BTreeSet;
+
+/// Selection of the tokens written by [`Ncp::restore_tokens`](crate::Ncp::restore_tokens).
+///
+/// Counter tokens, such as the boot and nonce counters, must never move
+/// backwards and are skipped unless [`with_counters`](Self::with_counters)
+/// is set. Further tokens are skipped by their NVM3 key with
+/// [`with_excluded`](Self::with_excluded).
+#[derive(Clone, Debug, Default, Eq, Hash, PartialEq)]
+pub struct TokenRestore {
+    excluded: BTreeSet<u32>,
+    counters: bool,
+}
+
+impl TokenRestore {
+    /// Skips the token with the given NVM3 key.
+    #[must_use]
+    pub fn with_excluded(mut self, key: u32) -> Self {
+        self.excluded.insert(key);
+        self
+    }
+
+    /// Sets whether counter tokens are restored.
+    #[must_use]
+    pub const fn with_counters(mut self, counters: bool) -> Self {
+        self.counters = counters;
+        self
+    }
+
+    /// Returns the NVM3 keys of the skipped tokens.
+    #[must_use]
+    pub const fn excluded(&self) -> &BTreeSet<u32> {
+        &self.excluded
+    }
+
+    /// Returns whether counter tokens are restored.
+    #[must_use]
+    pub const fn counters(&self) -> bool {
+        self.counters
+    }
+}
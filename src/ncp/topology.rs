@@ -0,0 +1,301 @@
+//! Network topology crawler.
+
+use std::collections::{BTreeSet, VecDeque};
+use std::time::Duration;
+
+use le_stream::FromLeStream;
+use log::{debug, trace, warn};
+
+pub use self::graph::Topology;
+pub use self::link::TopologyLink;
+pub use self::node::TopologyNode;
+pub use self::route::TopologyRoute;
+use crate::ember::route::Status as EntryStatus;
+use crate::ember::{Eui64, NodeId};
+use crate::ncp::Ncp;
+use crate::zdp::{
+    self, DeviceType, MgmtLqiRequest, MgmtLqiResponse, MgmtRtgRequest, MgmtRtgResponse,
+    Relationship, RouteStatus,
+};
+use crate::{Error, Networking, TablesExt, Utilities};
+
+mod graph;
+mod link;
+mod node;
+mod route;
+
+impl Ncp {
+    /// Crawls the network and returns its topology.
+    ///
+    /// The crawler starts from the NCP's own neighbor, child, and route
+    /// tables. It then walks all discovered routers breadth-first and reads
+    /// their neighbor and routing tables with Mgmt LQI and Mgmt Routing
+    /// requests. Each request waits at most `timeout` for its response; routers
+    /// that do not answer are marked as unresponsive and the crawl continues.
+    ///
+    /// Neighbors reporting a different extended PAN ID are ignored.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`Error`] if the NCP's tables cannot be read.
+    pub async fn crawl_topology(&self, timeout: Duration) -> Result<Topology, Error> {
+        let mut crawler = Crawler {
+            ncp: self.clone(),
+            timeout,
+            topology: Topology::default(),
+            queue: VecDeque::new(),
+            visited: BTreeSet::new(),
+        };
+        let extended_pan_id = crawler.read_local_tables().await?;
+
+        while let Some(node_id) = crawler.queue.pop_front() {
+            crawler.crawl(node_id, extended_pan_id).await;
+        }
+
+        Ok(crawler.topology)
+    }
+}
+
+struct Crawler {
+    ncp: Ncp,
+    timeout: Duration,
+    topology: Topology,
+    queue: VecDeque<NodeId>,
+    visited: BTreeSet<NodeId>,
+}
+
+impl Crawler {
+    async fn read_local_tables(&mut self) -> Result<Eui64, Error> {
+        let node_id = self.ncp.connection.get_node_id().await?;
+        let ieee_address = self.ncp.connection.get_eui64().await?;
+        let (node_type, parameters) = self.ncp.connection.get_network_parameters().await?;
+        let device_type = DeviceType::from(node_type);
+        let depth = (device_type == DeviceType::Coordinator).then_some(0);
+        let local = self.topology.node_mut(node_id);
+        local.update(Some(ieee_address), device_type, depth);
+        local.set_responsive(true);
+        self.visited.insert(node_id);
+
+        for neighbor in self
+            .ncp
+            .connection
+            .neighbor_table()
+            .await?
+            .collect()
+            .await?
+            .into_values()
+        {
+            self.topology.node_mut(neighbor.short_id()).update(
+                Some(neighbor.long_id()),
+                DeviceType::Router,
+                None,
+            );
+            self.topology.insert_link(TopologyLink::new(
+                node_id,
+                neighbor.short_id(),
+                Some(neighbor.average_lqi()),
+                None,
+                None,
+            ));
+            self.enqueue(neighbor.short_id());
+        }
+
+        for child in self
+            .ncp
+            .connection
+            .child_table()
+            .await?
+            .collect()
+            .await?
+            .into_values()
+        {
+            let child_type = child.typ().map_or(DeviceType::Unknown, DeviceType::from);
+            self.topology.node_mut(child.id()).update(
+                Some(child.eui64()),
+                child_type,
+                depth.map(|depth| depth + 1),
+            );
+            self.topology.insert_link(TopologyLink::new(
+                node_id,
+                child.id(),
+                None,
+                None,
+                Some(Relationship::Child),
+            ));
+
+            if child_type.is_router() {
+                self.enqueue(child.id());
+            }
+        }
+
+        for entry in self
+            .ncp
+            .connection
+            .route_table()
+            .await?
+            .collect()
+            .await?
+            .into_values()
+        {
+            let (Some(destination), Ok(status)) = (entry.destination(), entry.status()) else {
+                continue;
+            };
+            let status = match status {
+                EntryStatus::Active => RouteStatus::Active,
+                EntryStatus::Discovered => RouteStatus::DiscoveryUnderway,
+                EntryStatus::Validating => RouteStatus::ValidationUnderway,
+                EntryStatus::Unused => continue,
+            };
+            self.topology.insert_route(TopologyRoute::new(
+                node_id,
+                destination,
+                entry.next_hop(),
+                status,
+            ));
+        }
+
+        Ok(parameters.extended_pan_id())
+    }
+
+    async fn crawl(&mut self, node_id: NodeId, extended_pan_id: Eui64) {
+        debug!("Crawling neighbor and routing tables of {node_id:#06X}");
+        let responsive =
+            self.read_neighbors(node_id, extended_pan_id).await && self.read_routes(node_id).await;
+        self.topology.node_mut(node_id).set_responsive(responsive);
+    }
+
+    async fn read_neighbors(&mut self, node_id: NodeId, extended_pan_id: Eui64) -> bool {
+        let mut start_index: u8 = 0;
+
+        loop {
+            let Some(response) = self
+                .request::<_, MgmtLqiResponse>(node_id, MgmtLqiRequest::new(start_index))
+                .await
+            else {
+                return false;
+            };
+
+            if response.status() != Ok(zdp::Status::Success) {
+                debug!(
+                    "Neighbor table of {node_id:#06X} unavailable: {:?}",
+                    response.status()
+                );
+                return true;
+            }
+
+            for record in response.records() {
+                if record.extended_pan_id() != extended_pan_id {
+                    trace!("Ignoring neighbor of {node_id:#06X} on a foreign network.");
+                    continue;
+                }
+
+                let device_type = record.device_type().unwrap_or(DeviceType::Unknown);
+                self.topology.node_mut(record.node_id()).update(
+                    Some(record.ieee_address()),
+                    device_type,
+                    Some(record.depth()),
+                );
+                self.topology.insert_link(TopologyLink::new(
+                    node_id,
+                    record.node_id(),
+                    Some(record.lqi()),
+                    Some(record.depth()),
+                    record.relationship().ok(),
+                ));
+
+                if device_type.is_router() {
+                    self.enqueue(record.node_id());
+                }
+            }
+
+            match next_index(
+                response.start_index(),
+                response.records().len(),
+                response.neighbor_table_entries(),
+            ) {
+                Some(next) => start_index = next,
+                None => return true,
+            }
+        }
+    }
+
+    async fn read_routes(&mut self, node_id: NodeId) -> bool {
+        let mut start_index: u8 = 0;
+
+        loop {
+            let Some(response) = self
+                .request::<_, MgmtRtgResponse>(node_id, MgmtRtgRequest::new(start_index))
+                .await
+            else {
+                return false;
+            };
+
+            if response.status() != Ok(zdp::Status::Success) {
+                debug!(
+                    "Routing table of {node_id:#06X} unavailable: {:?}",
+                    response.status()
+                );
+                return true;
+            }
+
+            for record in response.records() {
+                if let Ok(status) = record.status() {
+                    self.topology.insert_route(TopologyRoute::new(
+                        node_id,
+                        record.destination(),
+                        record.next_hop(),
+                        status,
+                    ));
+                }
+            }
+
+            match next_index(
+                response.start_index(),
+                response.records().len(),
+                response.routing_table_entries(),
+            ) {
+                Some(next) => start_index = next,
+                None => return true,
+            }
+        }
+    }
+
+    async fn request<T, R>(&mut self, node_id: NodeId, request: T) -> Option<R>
+    where
+        T: zdp::Command + le_stream::ToLeStream,
+        R: zdp::Command + FromLeStream,
+    {
+        match self
+            .ncp
+            .zdp_request_response(node_id, request, self.timeout)
+            .await
+        {
+            Ok(Some(response)) => Some(response),
+            Ok(None) => {
+                warn!("No response from {node_id:#06X} to ZDP request.");
+                None
+            }
+            Err(error) => {
+                warn!("Failed to send ZDP request to {node_id:#06X}: {error}");
+                None
+            }
+        }
+    }
+
+    fn enqueue(&mut self, node_id: NodeId) {
+        if self.visited.insert(node_id) {
+            self.queue.push_back(node_id);
+        }
+    }
+}
+
+/// Returns the start index of the next table page, if any.
+fn next_index(start_index: u8, records: usize, entries: u8) -> Option<u8> {
+    let next = usize::from(start_index) + records;
+
+    if records == 0 || next >= usize::from(entries) {
+        None
+    } else {
+        u8::try_from(next).ok()
+    }
+}
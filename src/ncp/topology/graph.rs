@@ -0,0 +1,286 @@
+use std::collections::BTreeMap;
+use std::fmt::{self, Display, Formatter};
+
+use crate::ember::NodeId;
+use crate::ncp::json::{Optional, Quoted, separator};
+use crate::ncp::topology::{TopologyLink, TopologyNode, TopologyRoute};
+use crate::zdp::DeviceType;
+
+/// A snapshot of the network topology gathered by [`Ncp::crawl_topology`](crate::Ncp::crawl_topology).
+///
+/// The graph consists of the discovered devices, the links each device
+/// reported to its neighbors, and the routes of the queried routers. It can be
+/// exported as Graphviz DOT with [`to_dot`](Self::to_dot) and as JSON with
+/// [`to_json`](Self::to_json).
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct Topology {
+    nodes: BTreeMap<NodeId, TopologyNode>,
+    links: BTreeMap<(NodeId, NodeId), TopologyLink>,
+    routes: BTreeMap<(NodeId, NodeId), TopologyRoute>,
+}
+
+impl Topology {
+    /// Returns the device with the given network address.
+    #[must_use]
+    pub fn node(&self, node_id: NodeId) -> Option<&TopologyNode> {
+        self.nodes.get(&node_id)
+    }
+
+    /// Returns the devices ordered by network address.
+    pub fn nodes(&self) -> impl Iterator<Item = &TopologyNode> {
+        self.nodes.values()
+    }
+
+    /// Returns the links ordered by reporting device and neighbor.
+    pub fn links(&self) -> impl Iterator<Item = &TopologyLink> {
+        self.links.values()
+    }
+
+    /// Returns the routes ordered by reporting device and destination.
+    pub fn routes(&self) -> impl Iterator<Item = &TopologyRoute> {
+        self.routes.values()
+    }
+
+    /// Renders the devices and links as a Graphviz DOT digraph.
+    ///
+    /// Edges point from the reporting device to its neighbor and are labeled
+    /// with the link quality. Devices that did not answer are drawn dashed.
+    #[must_use]
+    pub fn to_dot(&self) -> String {
+        Dot(self).to_string()
+    }
+
+    /// Renders the devices, links, and routes as a JSON object.
+    #[must_use]
+    pub fn to_json(&self) -> String {
+        Json(self).to_string()
+    }
+
+    pub(super) fn node_mut(&mut self, node_id: NodeId) -> &mut TopologyNode {
+        self.nodes
+            .entry(node_id)
+            .or_insert_with(|| TopologyNode::new(node_id))
+    }
+
+    /// Inserts a link, keeping the link quality of a known link if the new one has none.
+    pub(super) fn insert_link(&mut self, link: TopologyLink) {
+        let key = (link.source(), link.target());
+        let lqi = link
+            .lqi()
+            .or_else(|| self.links.get(&key).and_then(TopologyLink::lqi));
+        self.links.insert(
+            key,
+            TopologyLink::new(
+                link.source(),
+                link.target(),
+                lqi,
+                link.depth(),
+                link.relationship(),
+            ),
+        );
+    }
+
+    pub(super) fn insert_route(&mut self, route: TopologyRoute) {
+        self.routes
+            .insert((route.source(), route.destination()), route);
+    }
+}
+
+struct Dot<'a>(&'a Topology);
+
+impl Display for Dot<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        writeln!(f, "digraph topology {{")?;
+
+        for node in self.0.nodes() {
+            write!(
+                f,
+                "    \"{:#06X}\" [label=\"{:#06X}\\n{}",
+                node.node_id(),
+                node.node_id(),
+                node.device_type()
+            )?;
+
+            if let Some(ieee_address) = node.ieee_address() {
+                write!(f, "\\n{ieee_address}")?;
+            }
+
+            write!(f, "\", shape={}", shape(node))?;
+
+            if node.responsive() == Some(false) {
+                write!(f, ", style=dashed")?;
+            }
+
+            writeln!(f, "];")?;
+        }
+
+        for link in self.0.links() {
+            write!(
+                f,
+                "    \"{:#06X}\" -> \"{:#06X}\" [label=\"",
+                link.source(),
+                link.target(),
+            )?;
+
+            match (link.lqi(), link.relationship()) {
+                (Some(lqi), Some(relationship)) => write!(f, "{lqi}\\n{relationship}")?,
+                (Some(lqi), None) => write!(f, "{lqi}")?,
+                (None, Some(relationship)) => write!(f, "{relationship}")?,
+                (None, None) => {}
+            }
+
+            writeln!(f, "\"];")?;
+        }
+
+        writeln!(f, "}}")
+    }
+}
+
+const fn shape(node: &TopologyNode) -> &'static str {
+    match node.device_type() {
+        DeviceType::Coordinator => "doubleoctagon",
+        DeviceType::Router => "box",
+        DeviceType::EndDevice => "ellipse",
+        DeviceType::Unknown => "plaintext",
+    }
+}
+
+struct Json<'a>(&'a Topology);
+
+impl Display for Json<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{{\"nodes\":[")?;
+
+        for (index, node) in self.0.nodes().enumerate() {
+            separator(f, index)?;
+            write!(
+                f,
+                "{{\"node_id\":\"{:#06X}\",\"ieee_address\":{},\"device_type\":\"{}\",\"depth\":{},\"responsive\":{}}}",
+                node.node_id(),
+                Optional(node.ieee_address().map(Quoted)),
+                node.device_type(),
+                Optional(node.depth()),
+                Optional(node.responsive())
+            )?;
+        }
+
+        write!(f, "],\"links\":[")?;
+
+        for (index, link) in self.0.links().enumerate() {
+            separator(f, index)?;
+            write!(
+                f,
+                "{{\"source\":\"{:#06X}\",\"target\":\"{:#06X}\",\"lqi\":{},\"depth\":{},\"relationship\":{}}}",
+                link.source(),
+                link.target(),
+                Optional(link.lqi()),
+                Optional(link.depth()),
+                Optional(link.relationship().map(Quoted))
+            )?;
+        }
+
+        write!(f, "],\"routes\":[")?;
+
+        for (index, route) in self.0.routes().enumerate() {
+            separator(f, index)?;
+            write!(
+                f,
+                "{{\"source\":\"{:#06X}\",\"destination\":\"{:#06X}\",\"next_hop\":\"{:#06X}\",\"status\":\"{:?}\"}}",
+                route.source(),
+                route.destination(),
+                route.next_hop(),
+                route.status()
+            )?;
+        }
+
+        write!(f, "]}}")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Topology;
+    use crate::ember::Eui64;
+    use crate::ncp::topology::{TopologyLink, TopologyRoute};
+    use crate::zdp::{DeviceType, Relationship, RouteStatus};
+
+    fn topology() -> Topology {
+        let mut topology = Topology::default();
+        topology.node_mut(0x0000).update(
+            Some(Eui64::new(0, 1, 2, 3, 4, 5, 6, 7)),
+            DeviceType::Coordinator,
+            Some(0),
+        );
+        topology
+            .node_mut(0x1234)
+            .update(None, DeviceType::Router, Some(1));
+        topology.node_mut(0x1234).set_responsive(false);
+        topology.insert_link(TopologyLink::new(
+            0x0000,
+            0x1234,
+            Some(200),
+            Some(1),
+            Some(Relationship::Child),
+        ));
+        topology
+            .node_mut(0x2345)
+            .update(None, DeviceType::EndDevice, None);
+        topology.insert_link(TopologyLink::new(
+            0x0000,
+            0x2345,
+            None,
+            None,
+            Some(Relationship::Child),
+        ));
+        topology.insert_route(TopologyRoute::new(
+            0x0000,
+            0x1234,
+            0x1234,
+            RouteStatus::Active,
+        ));
+        topology
+    }
+
+    #[test]
+    fn renders_dot() {
+        assert_eq!(
+            topology().to_dot(),
+            "digraph topology {\n    \
+             \"0x0000\" [label=\"0x0000\\nCoordinator\\n00:01:02:03:04:05:06:07\", shape=doubleoctagon];\n    \
+             \"0x1234\" [label=\"0x1234\\nRouter\", shape=box, style=dashed];\n    \
+             \"0x2345\" [label=\"0x2345\\nEnd Device\", shape=ellipse];\n    \
+             \"0x0000\" -> \"0x1234\" [label=\"200\\nChild\"];\n    \
+             \"0x0000\" -> \"0x2345\" [label=\"Child\"];\n\
+             }\n"
+        );
+    }
+
+    #[test]
+    fn keeps_known_link_quality() {
+        let mut topology = topology();
+        topology.insert_link(TopologyLink::new(
+            0x0000,
+            0x1234,
+            None,
+            None,
+            Some(Relationship::Child),
+        ));
+        let link = topology.links().next().expect("link should exist");
+        assert_eq!(link.lqi(), Some(200));
+        assert_eq!(link.depth(), None);
+    }
+
+    #[test]
+    fn renders_json() {
+        assert_eq!(
+            topology().to_json(),
+            "{\"nodes\":[\
+             {\"node_id\":\"0x0000\",\"ieee_address\":\"00:01:02:03:04:05:06:07\",\"device_type\":\"Coordinator\",\"depth\":0,\"responsive\":null},\
+             {\"node_id\":\"0x1234\",\"ieee_address\":null,\"device_type\":\"Router\",\"depth\":1,\"responsive\":false},\
+             {\"node_id\":\"0x2345\",\"ieee_address\":null,\"device_type\":\"End Device\",\"depth\":null,\"responsive\":null}],\
+             \"links\":[{\"source\":\"0x0000\",\"target\":\"0x1234\",\"lqi\":200,\"depth\":1,\"relationship\":\"Child\"},\
+             {\"source\":\"0x0000\",\"target\":\"0x2345\",\"lqi\":null,\"depth\":null,\"relationship\":\"Child\"}],\
+             \"routes\":[{\"source\":\"0x0000\",\"destination\":\"0x1234\",\"next_hop\":\"0x1234\",\"status\":\"Active\"}]}"
+        );
+    }
+}
@@ -0,0 +1,62 @@
+use crate::ember::NodeId;
+use crate::zdp::Relationship;
+
+/// A radio link reported by a device for one of its neighbors.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct TopologyLink {
+    source: NodeId,
+    target: NodeId,
+    lqi: Option<u8>,
+    depth: Option<u8>,
+    relationship: Option<Relationship>,
+}
+
+impl TopologyLink {
+    pub(super) const fn new(
+        source: NodeId,
+        target: NodeId,
+        lqi: Option<u8>,
+        depth: Option<u8>,
+        relationship: Option<Relationship>,
+    ) -> Self {
+        Self {
+            source,
+            target,
+            lqi,
+            depth,
+            relationship,
+        }
+    }
+
+    /// Returns the network address of the reporting device.
+    #[must_use]
+    pub const fn source(&self) -> NodeId {
+        self.source
+    }
+
+    /// Returns the network address of the neighbor.
+    #[must_use]
+    pub const fn target(&self) -> NodeId {
+        self.target
+    }
+
+    /// Returns the link quality the reporting device measured for the neighbor, if known.
+    ///
+    /// Links read from the NCP's child table carry no link quality.
+    #[must_use]
+    pub const fn lqi(&self) -> Option<u8> {
+        self.lqi
+    }
+
+    /// Returns the tree depth of the neighbor, if reported.
+    #[must_use]
+    pub const fn depth(&self) -> Option<u8> {
+        self.depth
+    }
+
+    /// Returns the relationship of the neighbor to the reporting device, if known.
+    #[must_use]
+    pub const fn relationship(&self) -> Option<Relationship> {
+        self.relationship
+    }
+}
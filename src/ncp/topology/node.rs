@@ -0,0 +1,79 @@
+use crate::ember::{Eui64, NodeId};
+use crate::zdp::DeviceType;
+
+/// A device in the network topology.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct TopologyNode {
+    node_id: NodeId,
+    ieee_address: Option<Eui64>,
+    device_type: DeviceType,
+    depth: Option<u8>,
+    responsive: Option<bool>,
+}
+
+impl TopologyNode {
+    pub(super) const fn new(node_id: NodeId) -> Self {
+        Self {
+            node_id,
+            ieee_address: None,
+            device_type: DeviceType::Unknown,
+            depth: None,
+            responsive: None,
+        }
+    }
+
+    /// Returns the network address of the device.
+    #[must_use]
+    pub const fn node_id(&self) -> NodeId {
+        self.node_id
+    }
+
+    /// Returns the IEEE address of the device, if known.
+    #[must_use]
+    pub const fn ieee_address(&self) -> Option<Eui64> {
+        self.ieee_address
+    }
+
+    /// Returns the device type.
+    #[must_use]
+    pub const fn device_type(&self) -> DeviceType {
+        self.device_type
+    }
+
+    /// Returns the tree depth of the device, if reported by a neighbor.
+    #[must_use]
+    pub const fn depth(&self) -> Option<u8> {
+        self.depth
+    }
+
+    /// Returns whether the device answered the crawler's table requests.
+    ///
+    /// Returns [`None`] for devices that were not queried, such as end devices.
+    #[must_use]
+    pub const fn responsive(&self) -> Option<bool> {
+        self.responsive
+    }
+
+    pub(super) const fn update(
+        &mut self,
+        ieee_address: Option<Eui64>,
+        device_type: DeviceType,
+        depth: Option<u8>,
+    ) {
+        if let Some(ieee_address) = ieee_address {
+            self.ieee_address = Some(ieee_address);
+        }
+
+        if !matches!(device_type, DeviceType::Unknown) {
+            self.device_type = device_type;
+        }
+
+        if let Some(depth) = depth {
+            self.depth = Some(depth);
+        }
+    }
+
+    pub(super) const fn set_responsive(&mut self, responsive: bool) {
+        self.responsive = Some(responsive);
+    }
+}
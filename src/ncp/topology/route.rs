@@ -0,0 +1,51 @@
+use crate::ember::NodeId;
+use crate::zdp::RouteStatus;
+
+/// A routing table entry reported by a device.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct TopologyRoute {
+    source: NodeId,
+    destination: NodeId,
+    next_hop: NodeId,
+    status: RouteStatus,
+}
+
+impl TopologyRoute {
+    pub(super) const fn new(
+        source: NodeId,
+        destination: NodeId,
+        next_hop: NodeId,
+        status: RouteStatus,
+    ) -> Self {
+        Self {
+            source,
+            destination,
+            next_hop,
+            status,
+        }
+    }
+
+    /// Returns the network address of the device holding the route.
+    #[must_use]
+    pub const fn source(&self) -> NodeId {
+        self.source
+    }
+
+    /// Returns the network address of the destination.
+    #[must_use]
+    pub const fn destination(&self) -> NodeId {
+        self.destination
+    }
+
+    /// Returns the network address of the next hop.
+    #[must_use]
+    pub const fn next_hop(&self) -> NodeId {
+        self.next_hop
+    }
+
+    /// Returns the status of the route.
+    #[must_use]
+    pub const fn status(&self) -> RouteStatus {
+        self.status
+    }
+}
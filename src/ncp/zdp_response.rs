@@ -0,0 +1,232 @@
+//! ZDP requests awaiting their response.
+
+use std::future::poll_fn;
+use std::pin::Pin;
+use std::task::Poll;
+use std::time::Duration;
+
+use le_stream::{FromLeStream, ToLeStream};
+use log::trace;
+use tokio::sync::mpsc::{Receiver, channel};
+use tokio::time::{Instant, timeout_at};
+
+use crate::ember::NodeId;
+use crate::ember::aps::Options;
+use crate::ncp::service::MESSAGES_CAPACITY;
+use crate::ncp::{Message, Ncp, StackResponse};
+use crate::{DefragmentedMessage, Error, zdp};
+
+impl Ncp {
+    /// Sends a ZDP request to `node_id` and waits up to `timeout` for its response.
+    ///
+    /// Only a response from `node_id` with the request's transaction sequence
+    /// number is accepted. Returns `None` if none arrived in time.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`Error`] if the response subscription cannot be registered
+    /// with the event handler, or the request cannot be sent or is reported as
+    /// failed by its `messageSent` callback.
+    pub(crate) async fn zdp_request_response<T, R>(
+        &mut self,
+        node_id: NodeId,
+        request: T,
+        timeout: Duration,
+    ) -> Result<Option<R>, Error>
+    where
+        T: zdp::Command + ToLeStream,
+        R: zdp::Command + FromLeStream,
+    {
+        let mut responses = self.subscribe_zdp::<R>().await?;
+        let (transaction_sequence, stack_response) =
+            self.zdp_request(node_id, request, Options::NONE).await?;
+        response(
+            &mut responses,
+            stack_response,
+            transaction_sequence,
+            timeout,
+            |sender, _| sender == node_id,
+        )
+        .await
+    }
+
+    /// Broadcasts a ZDP request to `destination` and waits up to `timeout` for a response.
+    ///
+    /// Only a response with the request's transaction sequence number that
+    /// satisfies `accept` is accepted. Returns `None` if none arrived in time.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`Error`] if the response subscription cannot be registered
+    /// with the event handler, or the request cannot be sent or is reported as
+    /// failed by its `messageSent` callback.
+    pub(crate) async fn zdp_broadcast_response<T, R>(
+        &mut self,
+        destination: NodeId,
+        request: T,
+        timeout: Duration,
+        accept: impl Fn(&R) -> bool + Send,
+    ) -> Result<Option<R>, Error>
+    where
+        T: zdp::Command + ToLeStream,
+        R: zdp::Command + FromLeStream,
+    {
+        let mut responses = self.subscribe_zdp::<R>().await?;
+        let (transaction_sequence, stack_response) = self
+            .zdp_broadcast(destination, 0, request, Options::NONE)
+            .await?;
+        response(
+            &mut responses,
+            stack_response,
+            transaction_sequence,
+            timeout,
+            move |_, response| accept(response),
+        )
+        .await
+    }
+
+    /// Subscribes to the ZDP messages of the cluster of `R`.
+    ///
+    /// The subscription ends when the returned receiver is dropped.
+    async fn subscribe_zdp<R>(&self) -> Result<Receiver<DefragmentedMessage>, Error>
+    where
+        R: zdp::Command,
+    {
+        let (sender, responses) = channel(MESSAGES_CAPACITY);
+        self.event_handler_handle
+            .send(Message::Zdp {
+                cluster_id: R::CLUSTER_ID,
+                sender,
+            })
+            .await?;
+        Ok(responses)
+    }
+}
+
+/// Waits up to `timeout` for the accepted response with the given transaction sequence number.
+///
+/// The stack response of the request is awaited alongside, so that a failed
+/// send is reported immediately instead of running into the timeout.
+async fn response<R>(
+    responses: &mut Receiver<DefragmentedMessage>,
+    stack_response: StackResponse,
+    transaction_sequence: u8,
+    timeout: Duration,
+    accept: impl Fn(NodeId, &R) -> bool + Send,
+) -> Result<Option<R>, Error>
+where
+    R: zdp::Command + FromLeStream,
+{
+    let deadline = Instant::now() + timeout;
+    let mut stack_response = Some(stack_response);
+
+    loop {
+        match timeout_at(deadline, next_event(&mut stack_response, responses)).await {
+            Ok(Event::Sent(result)) => result?,
+            Ok(Event::Received(Some(message))) => {
+                if let Some(response) = matching_response(&message, transaction_sequence, &accept) {
+                    if let Some(stack_response) = stack_response {
+                        // The response may overtake the `messageSent` callback.
+                        if let Ok(Err(error)) = timeout_at(deadline, stack_response).await {
+                            trace!("ZDP request answered despite failed send: {error}");
+                        }
+                    }
+
+                    return Ok(Some(response));
+                }
+            }
+            Ok(Event::Received(None)) | Err(_) => return Ok(None),
+        }
+    }
+}
+
+/// An event while waiting for a ZDP response.
+enum Event {
+    /// The stack reported the outcome of sending the request.
+    Sent(Result<(), Error>),
+    /// A message of the response cluster was received.
+    Received(Option<DefragmentedMessage>),
+}
+
+/// Waits for the stack response, if still pending, or the next received message.
+async fn next_event(
+    stack_response: &mut Option<StackResponse>,
+    responses: &mut Receiver<DefragmentedMessage>,
+) -> Event {
+    poll_fn(|context| {
+        if let Some(pending) = stack_response.as_mut()
+            && let Poll::Ready(result) = Pin::new(pending).poll(context)
+        {
+            *stack_response = None;
+            return Poll::Ready(Event::Sent(result));
+        }
+
+        responses.poll_recv(context).map(Event::Received)
+    })
+    .await
+}
+
+/// Returns the response carried by `message` if it answers the request and is accepted.
+fn matching_response<R>(
+    message: &DefragmentedMessage,
+    transaction_sequence: u8,
+    accept: impl Fn(NodeId, &R) -> bool,
+) -> Option<R>
+where
+    R: zdp::Command + FromLeStream,
+{
+    let frame = zdp::Frame::<R>::parse(message.aps_frame().cluster_id(), message.message())?;
+
+    (frame.transaction_sequence() == transaction_sequence
+        && accept(message.sender(), frame.command()))
+    .then(|| frame.into_command())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::task::{Context, Waker};
+
+    use tokio::sync::oneshot;
+
+    use super::*;
+    use crate::ember::Status;
+
+    fn poll_next_event(
+        stack_response: &mut Option<StackResponse>,
+        responses: &mut Receiver<DefragmentedMessage>,
+    ) -> Poll<Event> {
+        let mut context = Context::from_waker(Waker::noop());
+        Box::pin(next_event(stack_response, responses))
+            .as_mut()
+            .poll(&mut context)
+    }
+
+    #[test]
+    fn reports_failed_send() {
+        let (sender, receiver) = oneshot::channel();
+        let (_messages, mut responses) = channel(1);
+        let mut stack_response = Some(StackResponse::from(receiver));
+        sender
+            .send(Ok(Status::DeliveryFailed))
+            .expect("receiver is open");
+
+        assert!(matches!(
+            poll_next_event(&mut stack_response, &mut responses),
+            Poll::Ready(Event::Sent(Err(_)))
+        ));
+        assert!(stack_response.is_none());
+    }
+
+    #[test]
+    fn waits_while_send_is_pending() {
+        let (_sender, receiver) = oneshot::channel();
+        let (_messages, mut responses) = channel(1);
+        let mut stack_response = Some(StackResponse::from(receiver));
+
+        assert!(matches!(
+            poll_next_event(&mut stack_response, &mut responses),
+            Poll::Pending
+        ));
+        assert!(stack_response.is_some());
+    }
+}
@@ -0,0 +1,33 @@
+//! Host-side Zigbee Cluster Library (ZCL) frames.
+//!
+//! EZSP transports application payloads opaquely: an incoming APS message or an
+//! outgoing unicast carries the complete ZCL frame as bytes. This module models
+//! the ZCL frame header and the cluster payloads used by the high-level
+//! [`Ncp`](crate::Ncp) services, so those services can parse requests and
+//! build responses without an additional ZCL dependency.
+//!
+//! Only the commands needed by the services in this crate are modeled.
+//! Payloads of other commands remain available as raw bytes through
+//! [`Frame::payload`].
+
+pub use self::command::Command;
+pub use self::data_type::DataType;
+pub use self::frame::Frame;
+pub use self::header::{Direction, FrameType, Header};
+pub use self::status::Status;
+pub use self::value::Value;
+
+pub mod basic;
+mod command;
+mod data_type;
+mod frame;
+pub mod general;
+pub mod groups;
+mod header;
+pub mod ias_zone;
+pub mod ota;
+mod status;
+mod value;
+
+/// The Zigbee Home Automation profile ID used by ZCL application endpoints.
+pub const HOME_AUTOMATION_PROFILE_ID: u16 = 0x0104;
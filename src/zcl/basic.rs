@@ -0,0 +1,13 @@
+//! Basic cluster (`0x0000`).
+//!
+//! Every device implements the Basic cluster server on at least one endpoint.
+//! Its attributes identify the device's manufacturer and model.
+
+/// The Basic cluster ID.
+pub const CLUSTER_ID: u16 = 0x0000;
+
+/// The attribute ID of the manufacturer name.
+pub const MANUFACTURER_NAME: u16 = 0x0004;
+
+/// The attribute ID of the model identifier.
+pub const MODEL_IDENTIFIER: u16 = 0x0005;
@@ -0,0 +1,16 @@
+use crate::zcl::{Direction, FrameType};
+
+/// A typed ZCL command payload.
+///
+/// The associated constants identify the command in the ZCL frame header, so
+/// [`Frame::from_command`](crate::zcl::Frame::from_command) and
+/// [`Frame::command`](crate::zcl::Frame::command) can build and recognize frames
+/// without repeating the command metadata at every call site.
+pub trait Command {
+    /// The frame type of the command.
+    const FRAME_TYPE: FrameType;
+    /// The direction in which the command is sent.
+    const DIRECTION: Direction;
+    /// The command identifier.
+    const ID: u8;
+}
@@ -0,0 +1,256 @@
+use std::fmt::Display;
+
+use num_derive::FromPrimitive;
+use num_traits::FromPrimitive;
+
+/// ZCL attribute data types.
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd, FromPrimitive)]
+#[repr(u8)]
+pub enum DataType {
+    /// No data.
+    NoData = 0x00,
+    /// 8-bit data.
+    Data8 = 0x08,
+    /// 16-bit data.
+    Data16 = 0x09,
+    /// 24-bit data.
+    Data24 = 0x0A,
+    /// 32-bit data.
+    Data32 = 0x0B,
+    /// 40-bit data.
+    Data40 = 0x0C,
+    /// 48-bit data.
+    Data48 = 0x0D,
+    /// 56-bit data.
+    Data56 = 0x0E,
+    /// 64-bit data.
+    Data64 = 0x0F,
+    /// Boolean.
+    Boolean = 0x10,
+    /// 8-bit bitmap.
+    Bitmap8 = 0x18,
+    /// 16-bit bitmap.
+    Bitmap16 = 0x19,
+    /// 24-bit bitmap.
+    Bitmap24 = 0x1A,
+    /// 32-bit bitmap.
+    Bitmap32 = 0x1B,
+    /// 40-bit bitmap.
+    Bitmap40 = 0x1C,
+    /// 48-bit bitmap.
+    Bitmap48 = 0x1D,
+    /// 56-bit bitmap.
+    Bitmap56 = 0x1E,
+    /// 64-bit bitmap.
+    Bitmap64 = 0x1F,
+    /// Unsigned 8-bit integer.
+    Uint8 = 0x20,
+    /// Unsigned 16-bit integer.
+    Uint16 = 0x21,
+    /// Unsigned 24-bit integer.
+    Uint24 = 0x22,
+    /// Unsigned 32-bit integer.
+    Uint32 = 0x23,
+    /// Unsigned 40-bit integer.
+    Uint40 = 0x24,
+    /// Unsigned 48-bit integer.
+    Uint48 = 0x25,
+    /// Unsigned 56-bit integer.
+    Uint56 = 0x26,
+    /// Unsigned 64-bit integer.
+    Uint64 = 0x27,
+    /// Signed 8-bit integer.
+    Int8 = 0x28,
+    /// Signed 16-bit integer.
+    Int16 = 0x29,
+    /// Signed 24-bit integer.
+    Int24 = 0x2A,
+    /// Signed 32-bit integer.
+    Int32 = 0x2B,
+    /// Signed 40-bit integer.
+    Int40 = 0x2C,
+    /// Signed 48-bit integer.
+    Int48 = 0x2D,
+    /// Signed 56-bit integer.
+    Int56 = 0x2E,
+    /// Signed 64-bit integer.
+    Int64 = 0x2F,
+    /// 8-bit enumeration.
+    Enum8 = 0x30,
+    /// 16-bit enumeration.
+    Enum16 = 0x31,
+    /// Semi-precision floating point number.
+    SemiPrecision = 0x38,
+    /// Single-precision floating point number.
+    SinglePrecision = 0x39,
+    /// Double-precision floating point number.
+    DoublePrecision = 0x3A,
+    /// Octet string with a one-byte length prefix.
+    OctetString = 0x41,
+    /// Character string with a one-byte length prefix.
+    CharacterString = 0x42,
+    /// Octet string with a two-byte length prefix.
+    LongOctetString = 0x43,
+    /// Character string with a two-byte length prefix.
+    LongCharacterString = 0x44,
+    /// Array.
+    Array = 0x48,
+    /// Structure.
+    Structure = 0x4C,
+    /// Set.
+    Set = 0x50,
+    /// Bag.
+    Bag = 0x51,
+    /// Time of day.
+    TimeOfDay = 0xE0,
+    /// Date.
+    Date = 0xE1,
+    /// UTC time.
+    UtcTime = 0xE2,
+    /// Cluster ID.
+    ClusterId = 0xE8,
+    /// Attribute ID.
+    AttributeId = 0xE9,
+    /// `BACnet` object identifier.
+    BacnetOid = 0xEA,
+    /// IEEE address.
+    IeeeAddress = 0xF0,
+    /// 128-bit security key.
+    SecurityKey = 0xF1,
+    /// Unknown data type.
+    Unknown = 0xFF,
+}
+
+impl DataType {
+    /// Returns the encoded size in bytes of a fixed-size data type.
+    ///
+    /// Returns [`None`] for strings, collections, structures, and
+    /// [`DataType::Unknown`].
+    #[must_use]
+    pub const fn size(self) -> Option<usize> {
+        let id = self as u8;
+
+        match self {
+            Self::NoData => Some(0),
+            Self::Data8
+            | Self::Data16
+            | Self::Data24
+            | Self::Data32
+            | Self::Data40
+            | Self::Data48
+            | Self::Data56
+            | Self::Data64 => Some((id - 0x07) as usize),
+            Self::Boolean | Self::Enum8 => Some(1),
+            Self::Bitmap8
+            | Self::Bitmap16
+            | Self::Bitmap24
+            | Self::Bitmap32
+            | Self::Bitmap40
+            | Self::Bitmap48
+            | Self::Bitmap56
+            | Self::Bitmap64 => Some((id - 0x17) as usize),
+            Self::Uint8
+            | Self::Uint16
+            | Self::Uint24
+            | Self::Uint32
+            | Self::Uint40
+            | Self::Uint48
+            | Self::Uint56
+            | Self::Uint64 => Some((id - 0x1F) as usize),
+            Self::Int8
+            | Self::Int16
+            | Self::Int24
+            | Self::Int32
+            | Self::Int40
+            | Self::Int48
+            | Self::Int56
+            | Self::Int64 => Some((id - 0x27) as usize),
+            Self::Enum16 | Self::SemiPrecision | Self::ClusterId | Self::AttributeId => Some(2),
+            Self::SinglePrecision
+            | Self::TimeOfDay
+            | Self::Date
+            | Self::UtcTime
+            | Self::BacnetOid => Some(4),
+            Self::DoublePrecision | Self::IeeeAddress => Some(8),
+            Self::SecurityKey => Some(16),
+            Self::OctetString
+            | Self::CharacterString
+            | Self::LongOctetString
+            | Self::LongCharacterString
+            | Self::Array
+            | Self::Structure
+            | Self::Set
+            | Self::Bag
+            | Self::Unknown => None,
+        }
+    }
+
+    /// Returns whether the data type is general data of 8 to 64 bits.
+    #[must_use]
+    pub const fn is_data(self) -> bool {
+        matches!(self as u8, 0x08..=0x0F)
+    }
+
+    /// Returns whether the data type is a bitmap.
+    #[must_use]
+    pub const fn is_bitmap(self) -> bool {
+        matches!(self as u8, 0x18..=0x1F)
+    }
+
+    /// Returns whether the data type is an unsigned integer.
+    #[must_use]
+    pub const fn is_unsigned(self) -> bool {
+        matches!(self as u8, 0x20..=0x27)
+    }
+
+    /// Returns whether the data type is a signed integer.
+    #[must_use]
+    pub const fn is_signed(self) -> bool {
+        matches!(self as u8, 0x28..=0x2F)
+    }
+
+    /// Returns whether the data type is an array, set, or bag.
+    #[must_use]
+    pub const fn is_collection(self) -> bool {
+        matches!(self, Self::Array | Self::Set | Self::Bag)
+    }
+
+    /// Returns whether the data type is analog.
+    ///
+    /// Reporting configurations of analog attributes carry a reportable change
+    /// of the attribute's data type.
+    #[must_use]
+    pub const fn is_analog(self) -> bool {
+        self.is_unsigned()
+            || self.is_signed()
+            || matches!(
+                self,
+                Self::SemiPrecision
+                    | Self::SinglePrecision
+                    | Self::DoublePrecision
+                    | Self::TimeOfDay
+                    | Self::Date
+                    | Self::UtcTime
+            )
+    }
+}
+
+impl Display for DataType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{self:?}")
+    }
+}
+
+impl From<DataType> for u8 {
+    fn from(data_type: DataType) -> Self {
+        data_type as Self
+    }
+}
+
+impl TryFrom<u8> for DataType {
+    type Error = u8;
+
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
+        Self::from_u8(value).ok_or(value)
+    }
+}
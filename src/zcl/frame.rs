@@ -0,0 +1,120 @@
+use le_stream::{FromLeStream, ToLeStream};
+
+use crate::zcl::{Command, Header};
+
+/// A complete ZCL frame consisting of a header and a command payload.
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub struct Frame {
+    header: Header,
+    payload: Box<[u8]>,
+}
+
+impl Frame {
+    /// Creates a frame from a header and a serializable command payload.
+    #[must_use]
+    pub fn new<T>(header: Header, payload: T) -> Self
+    where
+        T: ToLeStream,
+    {
+        Self {
+            header,
+            payload: payload.to_le_stream().collect(),
+        }
+    }
+
+    /// Creates a frame carrying a typed command.
+    ///
+    /// The frame type, direction, and command ID are taken from `T`.
+    #[must_use]
+    pub fn from_command<T>(
+        transaction_sequence: u8,
+        disable_default_response: bool,
+        command: T,
+    ) -> Self
+    where
+        T: Command + ToLeStream,
+    {
+        Self::new(
+            Header::new(
+                T::FRAME_TYPE,
+                T::DIRECTION,
+                disable_default_response,
+                transaction_sequence,
+                T::ID,
+            ),
+            command,
+        )
+    }
+
+    /// Parses a frame from a complete APS payload.
+    ///
+    /// Returns [`None`] if the payload is too short to contain a ZCL header.
+    #[must_use]
+    pub fn parse(bytes: &[u8]) -> Option<Self> {
+        Self::from_le_stream(bytes.iter().copied())
+    }
+
+    /// Returns the frame header.
+    #[must_use]
+    pub const fn header(&self) -> &Header {
+        &self.header
+    }
+
+    /// Returns the raw command payload.
+    #[must_use]
+    pub const fn payload(&self) -> &[u8] {
+        &self.payload
+    }
+
+    /// Parses the complete command payload as `T`.
+    ///
+    /// Returns [`None`] if the payload is too short for `T`. Trailing bytes are
+    /// ignored, because later ZCL revisions may append fields to a command.
+    #[must_use]
+    pub fn parse_payload<T>(&self) -> Option<T>
+    where
+        T: FromLeStream,
+    {
+        T::from_le_stream(self.payload.iter().copied())
+    }
+
+    /// Parses the payload as the typed command `T` if the header identifies it.
+    ///
+    /// Returns [`None`] if the frame type, direction, or command ID differ from
+    /// those of `T`, or if the payload is too short.
+    #[must_use]
+    pub fn command<T>(&self) -> Option<T>
+    where
+        T: Command + FromLeStream,
+    {
+        if self.header.frame_type() == Ok(T::FRAME_TYPE)
+            && self.header.direction() == T::DIRECTION
+            && self.header.command_id() == T::ID
+        {
+            self.parse_payload()
+        } else {
+            None
+        }
+    }
+
+    /// Serializes the frame into the bytes of an APS payload.
+    #[must_use]
+    pub fn to_bytes(&self) -> Vec<u8> {
+        self.header
+            .to_le_stream()
+            .chain(self.payload.iter().copied())
+            .collect()
+    }
+}
+
+impl FromLeStream for Frame {
+    fn from_le_stream<T>(mut bytes: T) -> Option<Self>
+    where
+        T: Iterator<Item = u8>,
+    {
+        Some(Self {
+            header: Header::from_le_stream(&mut bytes)?,
+            payload: bytes.collect(),
+        })
+    }
+}
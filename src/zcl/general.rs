@@ -0,0 +1,25 @@
+//! Profile-wide (global) ZCL commands.
+//!
+//! Global commands share one command ID space across all clusters. Their
+//! direction depends on the role of the sender, so frames are built with an
+//! explicit [`Direction`](crate::zcl::Direction) where necessary.
+
+pub use self::attribute_status::AttributeStatus;
+pub use self::configure_reporting::{ConfigureReporting, ReportingConfiguration};
+pub use self::configure_reporting_response::ConfigureReportingResponse;
+pub use self::default_response::DefaultResponse;
+pub use self::read_attributes::ReadAttributes;
+pub use self::read_attributes_response::{ReadAttributeStatus, ReadAttributesResponse};
+pub use self::report_attributes::{AttributeReport, ReportAttributes};
+pub use self::write_attributes::WriteAttributes;
+pub use self::write_attributes_response::WriteAttributesResponse;
+
+mod attribute_status;
+mod configure_reporting;
+mod configure_reporting_response;
+mod default_response;
+mod read_attributes;
+mod read_attributes_response;
+mod report_attributes;
+mod write_attributes;
+mod write_attributes_response;
@@ -0,0 +1,60 @@
+use le_stream::FromLeStream;
+
+use crate::zcl::Status;
+
+/// Status of a single attribute in a write or configure reporting response.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct AttributeStatus {
+    status: u8,
+    attribute_id: Option<u16>,
+}
+
+impl AttributeStatus {
+    /// Returns the status.
+    ///
+    /// # Errors
+    ///
+    /// Returns the raw status if it is not a known ZCL status.
+    pub fn status(&self) -> Result<Status, u8> {
+        Status::try_from(self.status)
+    }
+
+    /// Returns the attribute ID.
+    ///
+    /// The attribute ID is absent in a response reporting overall success.
+    #[must_use]
+    pub const fn attribute_id(&self) -> Option<u16> {
+        self.attribute_id
+    }
+
+    /// Reads the status records of a response.
+    ///
+    /// A response reporting success for all attributes consists of a single
+    /// status. Otherwise, each record holds a status, an optional direction,
+    /// and an attribute ID.
+    pub(super) fn read_records<T>(bytes: &mut T, with_direction: bool) -> Option<Box<[Self]>>
+    where
+        T: Iterator<Item = u8>,
+    {
+        let mut records = Vec::new();
+
+        while let Some(status) = u8::from_le_stream(&mut *bytes) {
+            let attribute_id = if with_direction {
+                u8::from_le_stream(&mut *bytes).and_then(|_| u16::from_le_stream(&mut *bytes))
+            } else {
+                u16::from_le_stream(&mut *bytes)
+            };
+
+            if attribute_id.is_none() && !records.is_empty() {
+                return None;
+            }
+
+            records.push(Self {
+                status,
+                attribute_id,
+            });
+        }
+
+        (!records.is_empty()).then(|| records.into_boxed_slice())
+    }
+}
@@ -0,0 +1,135 @@
+use le_stream::ToLeStream;
+
+use crate::zcl::{DataType, Value};
+
+/// Direction field requesting the server to send reports.
+const REPORTED: u8 = 0x00;
+
+/// Configure Reporting command asking a server to report attributes.
+///
+/// The payload is encoded when the command is created, because analog
+/// attributes carry a reportable change of the attribute's own data type.
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub struct ConfigureReporting {
+    payload: Box<[u8]>,
+}
+
+impl ConfigureReporting {
+    /// The global command ID of Configure Reporting.
+    pub const ID: u8 = 0x06;
+
+    /// Creates a request applying the given reporting configurations.
+    ///
+    /// Analog attributes without a reportable change report every change.
+    ///
+    /// # Errors
+    ///
+    /// Returns the ID of the first attribute whose reportable change cannot be
+    /// encoded as the attribute's data type.
+    pub fn new(configurations: &[ReportingConfiguration]) -> Result<Self, u16> {
+        let mut payload = Vec::new();
+
+        for configuration in configurations {
+            payload.push(REPORTED);
+            payload.extend(configuration.attribute_id.to_le_stream());
+            payload.push(configuration.data_type.into());
+            payload.extend(configuration.minimum_interval.to_le_stream());
+            payload.extend(configuration.maximum_interval.to_le_stream());
+
+            if configuration.data_type.is_analog() {
+                match &configuration.reportable_change {
+                    Some(change) => payload.extend(
+                        change
+                            .to_bytes(configuration.data_type)
+                            .ok_or(configuration.attribute_id)?,
+                    ),
+                    None => payload.extend(std::iter::repeat_n(
+                        0,
+                        configuration.data_type.size().unwrap_or_default(),
+                    )),
+                }
+            }
+        }
+
+        Ok(Self {
+            payload: payload.into_boxed_slice(),
+        })
+    }
+}
+
+impl ToLeStream for ConfigureReporting {
+    type Iter = std::vec::IntoIter<u8>;
+
+    fn to_le_stream(self) -> Self::Iter {
+        self.payload.into_vec().into_iter()
+    }
+}
+
+/// Reporting configuration of a single attribute.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ReportingConfiguration {
+    attribute_id: u16,
+    data_type: DataType,
+    minimum_interval: u16,
+    maximum_interval: u16,
+    reportable_change: Option<Value>,
+}
+
+impl ReportingConfiguration {
+    /// Creates a configuration reporting an attribute within the given intervals in seconds.
+    ///
+    /// A maximum interval of `0x0000` disables periodic reports, and a maximum
+    /// interval of `0xFFFF` disables reporting altogether.
+    #[must_use]
+    pub const fn new(
+        attribute_id: u16,
+        data_type: DataType,
+        minimum_interval: u16,
+        maximum_interval: u16,
+    ) -> Self {
+        Self {
+            attribute_id,
+            data_type,
+            minimum_interval,
+            maximum_interval,
+            reportable_change: None,
+        }
+    }
+
+    /// Sets the minimum change of an analog attribute that triggers a report.
+    #[must_use]
+    pub fn with_reportable_change(mut self, reportable_change: Value) -> Self {
+        self.reportable_change = Some(reportable_change);
+        self
+    }
+
+    /// Returns the attribute ID.
+    #[must_use]
+    pub const fn attribute_id(&self) -> u16 {
+        self.attribute_id
+    }
+
+    /// Returns the attribute's data type.
+    #[must_use]
+    pub const fn data_type(&self) -> DataType {
+        self.data_type
+    }
+
+    /// Returns the minimum reporting interval in seconds.
+    #[must_use]
+    pub const fn minimum_interval(&self) -> u16 {
+        self.minimum_interval
+    }
+
+    /// Returns the maximum reporting interval in seconds.
+    #[must_use]
+    pub const fn maximum_interval(&self) -> u16 {
+        self.maximum_interval
+    }
+
+    /// Returns the reportable change of an analog attribute.
+    #[must_use]
+    pub const fn reportable_change(&self) -> Option<&Value> {
+        self.reportable_change.as_ref()
+    }
+}
@@ -0,0 +1,48 @@
+use le_stream::FromLeStream;
+
+use crate::zcl::Status;
+use crate::zcl::general::AttributeStatus;
+
+/// Configure Reporting Response command.
+///
+/// A response reporting success for all attributes carries a single success
+/// status without an attribute ID. Otherwise, it carries one record per
+/// attribute whose reporting could not be configured.
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub struct ConfigureReportingResponse {
+    records: Box<[AttributeStatus]>,
+}
+
+impl ConfigureReportingResponse {
+    /// The global command ID of Configure Reporting Response.
+    pub const ID: u8 = 0x07;
+
+    /// Returns the status of the given attribute.
+    ///
+    /// Attributes not listed in a response are configured successfully.
+    ///
+    /// # Errors
+    ///
+    /// Returns the raw status if it is not a known ZCL status.
+    pub fn status_of(&self, attribute_id: u16) -> Result<Status, u8> {
+        self.records
+            .iter()
+            .find(|record| record.attribute_id() == Some(attribute_id))
+            .map_or(Ok(Status::Success), AttributeStatus::status)
+    }
+
+    /// Returns the status records.
+    #[must_use]
+    pub fn records(&self) -> &[AttributeStatus] {
+        &self.records
+    }
+}
+
+impl FromLeStream for ConfigureReportingResponse {
+    fn from_le_stream<T>(mut bytes: T) -> Option<Self>
+    where
+        T: Iterator<Item = u8>,
+    {
+        AttributeStatus::read_records(&mut bytes, true).map(|records| Self { records })
+    }
+}
@@ -0,0 +1,68 @@
+use le_stream::{FromLeStream, ToLeStream};
+
+use crate::zcl::{Direction, Frame, FrameType, Header, Status};
+
+/// Default Response command.
+///
+/// A default response reports the status of a received command that has no
+/// specific response, unless the sender disabled default responses.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq, FromLeStream, ToLeStream)]
+pub struct DefaultResponse {
+    command_id: u8,
+    status: u8,
+}
+
+impl DefaultResponse {
+    /// The global command ID of the default response.
+    pub const ID: u8 = 0x0B;
+
+    /// Creates a default response for the given command.
+    #[must_use]
+    pub fn new(command_id: u8, status: Status) -> Self {
+        Self {
+            command_id,
+            status: status.into(),
+        }
+    }
+
+    /// Builds the default response frame answering the request with `header`.
+    ///
+    /// The response reverses the request's direction, echoes its transaction
+    /// sequence number and manufacturer code, and disables a default response to
+    /// itself.
+    #[must_use]
+    pub fn reply_to(header: &Header, status: Status) -> Frame {
+        let direction = match header.direction() {
+            Direction::ClientToServer => Direction::ServerToClient,
+            Direction::ServerToClient => Direction::ClientToServer,
+        };
+        let mut response_header = Header::new(
+            FrameType::Global,
+            direction,
+            true,
+            header.transaction_sequence(),
+            Self::ID,
+        );
+
+        if let Some(manufacturer_code) = header.manufacturer_code() {
+            response_header = response_header.with_manufacturer_code(manufacturer_code);
+        }
+
+        Frame::new(response_header, Self::new(header.command_id(), status))
+    }
+
+    /// Returns the ID of the command this response refers to.
+    #[must_use]
+    pub const fn command_id(&self) -> u8 {
+        self.command_id
+    }
+
+    /// Returns the reported status.
+    ///
+    /// # Errors
+    ///
+    /// Returns the raw status if it is not a known ZCL status.
+    pub fn status(&self) -> Result<Status, u8> {
+        Status::try_from(self.status)
+    }
+}
@@ -0,0 +1,38 @@
+use le_stream::ToLeStream;
+
+/// Read Attributes command requesting attribute values from a server.
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub struct ReadAttributes {
+    attribute_ids: Box<[u16]>,
+}
+
+impl ReadAttributes {
+    /// The global command ID of Read Attributes.
+    pub const ID: u8 = 0x00;
+
+    /// Creates a request reading the given attributes.
+    #[must_use]
+    pub fn new(attribute_ids: impl Into<Box<[u16]>>) -> Self {
+        Self {
+            attribute_ids: attribute_ids.into(),
+        }
+    }
+
+    /// Returns the requested attribute IDs.
+    #[must_use]
+    pub fn attribute_ids(&self) -> &[u16] {
+        &self.attribute_ids
+    }
+}
+
+impl ToLeStream for ReadAttributes {
+    type Iter = std::vec::IntoIter<u8>;
+
+    fn to_le_stream(self) -> Self::Iter {
+        self.attribute_ids
+            .iter()
+            .flat_map(|attribute_id| attribute_id.to_le_bytes())
+            .collect::<Vec<_>>()
+            .into_iter()
+    }
+}
@@ -0,0 +1,123 @@
+use le_stream::FromLeStream;
+
+use crate::zcl::{DataType, Status, Value};
+
+/// Read Attributes Response command carrying the requested attribute values.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ReadAttributesResponse {
+    records: Box<[ReadAttributeStatus]>,
+}
+
+impl ReadAttributesResponse {
+    /// The global command ID of Read Attributes Response.
+    pub const ID: u8 = 0x01;
+
+    /// Returns the value of an attribute if it was read successfully.
+    #[must_use]
+    pub fn value(&self, attribute_id: u16) -> Option<&Value> {
+        self.records
+            .iter()
+            .find(|record| record.attribute_id == attribute_id)
+            .and_then(ReadAttributeStatus::value)
+    }
+
+    /// Returns the records.
+    #[must_use]
+    pub fn records(&self) -> &[ReadAttributeStatus] {
+        &self.records
+    }
+}
+
+impl FromLeStream for ReadAttributesResponse {
+    fn from_le_stream<T>(mut bytes: T) -> Option<Self>
+    where
+        T: Iterator<Item = u8>,
+    {
+        let mut records = Vec::new();
+
+        while let Some(attribute_id) = u16::from_le_stream(&mut bytes) {
+            let status = u8::from_le_stream(&mut bytes)?;
+            let value = if status == u8::from(Status::Success) {
+                let data_type = DataType::try_from(u8::from_le_stream(&mut bytes)?).ok()?;
+                Some((data_type, Value::read(data_type, &mut bytes)?))
+            } else {
+                None
+            };
+
+            records.push(ReadAttributeStatus {
+                attribute_id,
+                status,
+                value,
+            });
+        }
+
+        Some(Self {
+            records: records.into_boxed_slice(),
+        })
+    }
+}
+
+/// The result of reading a single attribute.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ReadAttributeStatus {
+    attribute_id: u16,
+    status: u8,
+    value: Option<(DataType, Value)>,
+}
+
+impl ReadAttributeStatus {
+    /// Returns the attribute ID.
+    #[must_use]
+    pub const fn attribute_id(&self) -> u16 {
+        self.attribute_id
+    }
+
+    /// Returns the status.
+    ///
+    /// # Errors
+    ///
+    /// Returns the raw status if it is not a known ZCL status.
+    pub fn status(&self) -> Result<Status, u8> {
+        Status::try_from(self.status)
+    }
+
+    /// Returns the data type of the value, if the attribute was read.
+    #[must_use]
+    pub fn data_type(&self) -> Option<DataType> {
+        self.value.as_ref().map(|(data_type, _)| *data_type)
+    }
+
+    /// Returns the value, if the attribute was read.
+    #[must_use]
+    pub fn value(&self) -> Option<&Value> {
+        self.value.as_ref().map(|(_, value)| value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use le_stream::FromLeStream;
+
+    use super::ReadAttributesResponse;
+    use crate::zcl::{Status, Value};
+
+    #[test]
+    fn parses_values_and_failures() {
+        let bytes = [
+            0x04, 0x00, 0x00, 0x42, 0x03, b'A', b'c', b'm', // manufacturer name
+            0x05, 0x00, 0x86, // model identifier unsupported
+        ];
+        let response =
+            ReadAttributesResponse::from_le_slice(&bytes).expect("response should parse");
+
+        assert_eq!(
+            response.value(0x0004),
+            Some(&Value::CharacterString("Acm".into()))
+        );
+        assert_eq!(response.value(0x0005), None);
+        assert_eq!(
+            response.records()[1].status(),
+            Ok(Status::UnsupportedAttribute)
+        );
+    }
+}
@@ -0,0 +1,82 @@
+use le_stream::FromLeStream;
+
+use crate::zcl::{DataType, Value};
+
+/// Report Attributes command sent by a server reporting attribute values.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ReportAttributes {
+    reports: Box<[AttributeReport]>,
+}
+
+impl ReportAttributes {
+    /// The global command ID of Report Attributes.
+    pub const ID: u8 = 0x0A;
+
+    /// Returns the reported attributes.
+    #[must_use]
+    pub fn reports(&self) -> &[AttributeReport] {
+        &self.reports
+    }
+
+    /// Consumes the command and returns the reported attributes.
+    #[must_use]
+    pub fn into_reports(self) -> Box<[AttributeReport]> {
+        self.reports
+    }
+}
+
+impl FromLeStream for ReportAttributes {
+    fn from_le_stream<T>(mut bytes: T) -> Option<Self>
+    where
+        T: Iterator<Item = u8>,
+    {
+        let mut reports = Vec::new();
+
+        while let Some(attribute_id) = u16::from_le_stream(&mut bytes) {
+            let data_type = DataType::try_from(u8::from_le_stream(&mut bytes)?).ok()?;
+            reports.push(AttributeReport {
+                attribute_id,
+                data_type,
+                value: Value::read(data_type, &mut bytes)?,
+            });
+        }
+
+        Some(Self {
+            reports: reports.into_boxed_slice(),
+        })
+    }
+}
+
+/// The reported value of a single attribute.
+#[derive(Clone, Debug, PartialEq)]
+pub struct AttributeReport {
+    attribute_id: u16,
+    data_type: DataType,
+    value: Value,
+}
+
+impl AttributeReport {
+    /// Returns the attribute ID.
+    #[must_use]
+    pub const fn attribute_id(&self) -> u16 {
+        self.attribute_id
+    }
+
+    /// Returns the data type of the value.
+    #[must_use]
+    pub const fn data_type(&self) -> DataType {
+        self.data_type
+    }
+
+    /// Returns the reported value.
+    #[must_use]
+    pub const fn value(&self) -> &Value {
+        &self.value
+    }
+
+    /// Consumes the report and returns the reported value.
+    #[must_use]
+    pub fn into_value(self) -> Value {
+        self.value
+    }
+}
@@ -0,0 +1,29 @@
+use le_stream::ToLeStream;
+
+use crate::zcl::DataType;
+
+/// Write Attributes command writing a single attribute.
+///
+/// The value is serialized with its own [`ToLeStream`] representation, which
+/// must match the encoding of `data_type`.
+#[derive(Clone, Debug, Eq, Hash, PartialEq, ToLeStream)]
+pub struct WriteAttributes<T> {
+    attribute_id: u16,
+    data_type: u8,
+    value: T,
+}
+
+impl<T> WriteAttributes<T> {
+    /// The global command ID of Write Attributes.
+    pub const ID: u8 = 0x02;
+
+    /// Creates a request writing `value` to the given attribute.
+    #[must_use]
+    pub fn new(attribute_id: u16, data_type: DataType, value: T) -> Self {
+        Self {
+            attribute_id,
+            data_type: data_type.into(),
+            value,
+        }
+    }
+}
@@ -0,0 +1,57 @@
+use le_stream::FromLeStream;
+
+use crate::zcl::Status;
+use crate::zcl::general::AttributeStatus;
+
+/// Write Attributes Response command.
+///
+/// A response reporting success for all attributes carries a single success
+/// status without an attribute ID. Otherwise, it carries one record per
+/// attribute that could not be written.
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub struct WriteAttributesResponse {
+    records: Box<[AttributeStatus]>,
+}
+
+impl WriteAttributesResponse {
+    /// The global command ID of Write Attributes Response.
+    pub const ID: u8 = 0x04;
+
+    /// Returns whether all attributes were written.
+    #[must_use]
+    pub fn is_success(&self) -> bool {
+        self.records
+            .iter()
+            .all(|record| record.status() == Ok(Status::Success))
+    }
+
+    /// Returns the overall status.
+    ///
+    /// This is [`Status::Success`] if all attributes were written, or the
+    /// status of the first attribute that could not be written.
+    ///
+    /// # Errors
+    ///
+    /// Returns the raw status if it is not a known ZCL status.
+    pub fn status(&self) -> Result<Status, u8> {
+        self.records
+            .iter()
+            .find(|record| record.status() != Ok(Status::Success))
+            .map_or(Ok(Status::Success), AttributeStatus::status)
+    }
+
+    /// Returns the status records.
+    #[must_use]
+    pub fn records(&self) -> &[AttributeStatus] {
+        &self.records
+    }
+}
+
+impl FromLeStream for WriteAttributesResponse {
+    fn from_le_stream<T>(mut bytes: T) -> Option<Self>
+    where
+        T: Iterator<Item = u8>,
+    {
+        AttributeStatus::read_records(&mut bytes, false).map(|records| Self { records })
+    }
+}
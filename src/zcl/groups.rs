@@ -0,0 +1,14 @@
+//! Groups cluster (`0x0004`).
+//!
+//! Devices implement the server side of the cluster and keep their group
+//! memberships per endpoint. The coordinator adds and removes memberships on
+//! the client side.
+
+pub use self::add_group::AddGroup;
+pub use self::remove_group::RemoveGroup;
+
+mod add_group;
+mod remove_group;
+
+/// The Groups cluster ID.
+pub const CLUSTER_ID: u16 = 0x0004;
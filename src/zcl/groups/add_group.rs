@@ -0,0 +1,44 @@
+use le_stream::ToLeStream;
+
+use crate::types::ByteSizedVec;
+use crate::zcl::{Command, Direction, FrameType};
+
+/// Add Group command adding an endpoint to a group.
+#[derive(Clone, Debug, Eq, Hash, PartialEq, ToLeStream)]
+pub struct AddGroup {
+    group_id: u16,
+    group_name: ByteSizedVec<u8>,
+}
+
+impl AddGroup {
+    /// Creates a request adding the receiving endpoint to the given group.
+    ///
+    /// The group name is left empty, since devices are not required to
+    /// support names.
+    #[must_use]
+    pub const fn new(group_id: u16) -> Self {
+        Self {
+            group_id,
+            group_name: ByteSizedVec::new(),
+        }
+    }
+}
+
+impl Command for AddGroup {
+    const FRAME_TYPE: FrameType = FrameType::ClusterSpecific;
+    const DIRECTION: Direction = Direction::ClientToServer;
+    const ID: u8 = 0x00;
+}
+
+#[cfg(test)]
+mod tests {
+    use le_stream::ToLeStream;
+
+    use super::AddGroup;
+
+    #[test]
+    fn group_name_is_empty_string() {
+        let payload: Vec<u8> = AddGroup::new(0x1234).to_le_stream().collect();
+        assert_eq!(payload, [0x34, 0x12, 0x00]);
+    }
+}
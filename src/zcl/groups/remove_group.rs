@@ -0,0 +1,23 @@
+use le_stream::ToLeStream;
+
+use crate::zcl::{Command, Direction, FrameType};
+
+/// Remove Group command removing an endpoint from a group.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq, ToLeStream)]
+pub struct RemoveGroup {
+    group_id: u16,
+}
+
+impl RemoveGroup {
+    /// Creates a request removing the receiving endpoint from the given group.
+    #[must_use]
+    pub const fn new(group_id: u16) -> Self {
+        Self { group_id }
+    }
+}
+
+impl Command for RemoveGroup {
+    const FRAME_TYPE: FrameType = FrameType::ClusterSpecific;
+    const DIRECTION: Direction = Direction::ClientToServer;
+    const ID: u8 = 0x03;
+}
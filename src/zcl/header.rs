@@ -0,0 +1,146 @@
+use le_stream::{FromLeStream, ToLeStream};
+
+const FRAME_TYPE_MASK: u8 = 0b0000_0011;
+const MANUFACTURER_SPECIFIC: u8 = 0b0000_0100;
+const SERVER_TO_CLIENT: u8 = 0b0000_1000;
+const DISABLE_DEFAULT_RESPONSE: u8 = 0b0001_0000;
+
+/// ZCL frame type.
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub enum FrameType {
+    /// A profile-wide (global) command, such as reading an attribute.
+    Global,
+    /// A command specific to the addressed cluster.
+    ClusterSpecific,
+}
+
+/// Direction of a ZCL command relative to the cluster roles.
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub enum Direction {
+    /// The command is sent from the client side of a cluster to its server side.
+    ClientToServer,
+    /// The command is sent from the server side of a cluster to its client side.
+    ServerToClient,
+}
+
+/// A ZCL frame header.
+///
+/// The header carries the frame control field, the optional manufacturer code,
+/// the transaction sequence number, and the command identifier. The
+/// manufacturer code is present on the wire only for manufacturer-specific
+/// frames.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq, ToLeStream)]
+pub struct Header {
+    frame_control: u8,
+    manufacturer_code: Option<u16>,
+    transaction_sequence: u8,
+    command_id: u8,
+}
+
+impl Header {
+    /// Creates a header for a command that is not manufacturer-specific.
+    #[must_use]
+    pub const fn new(
+        frame_type: FrameType,
+        direction: Direction,
+        disable_default_response: bool,
+        transaction_sequence: u8,
+        command_id: u8,
+    ) -> Self {
+        let mut frame_control = match frame_type {
+            FrameType::Global => 0x00,
+            FrameType::ClusterSpecific => 0x01,
+        };
+
+        if matches!(direction, Direction::ServerToClient) {
+            frame_control |= SERVER_TO_CLIENT;
+        }
+
+        if disable_default_response {
+            frame_control |= DISABLE_DEFAULT_RESPONSE;
+        }
+
+        Self {
+            frame_control,
+            manufacturer_code: None,
+            transaction_sequence,
+            command_id,
+        }
+    }
+
+    /// Marks the command as manufacturer-specific for the given manufacturer.
+    #[must_use]
+    pub const fn with_manufacturer_code(mut self, manufacturer_code: u16) -> Self {
+        self.frame_control |= MANUFACTURER_SPECIFIC;
+        self.manufacturer_code = Some(manufacturer_code);
+        self
+    }
+
+    /// Returns the frame type.
+    ///
+    /// # Errors
+    ///
+    /// Returns the raw two-bit frame type if it is reserved.
+    pub const fn frame_type(&self) -> Result<FrameType, u8> {
+        match self.frame_control & FRAME_TYPE_MASK {
+            0x00 => Ok(FrameType::Global),
+            0x01 => Ok(FrameType::ClusterSpecific),
+            other => Err(other),
+        }
+    }
+
+    /// Returns the command direction.
+    #[must_use]
+    pub const fn direction(&self) -> Direction {
+        if self.frame_control & SERVER_TO_CLIENT == 0 {
+            Direction::ClientToServer
+        } else {
+            Direction::ServerToClient
+        }
+    }
+
+    /// Returns whether the sender disabled the default response.
+    #[must_use]
+    pub const fn disable_default_response(&self) -> bool {
+        self.frame_control & DISABLE_DEFAULT_RESPONSE != 0
+    }
+
+    /// Returns the manufacturer code of a manufacturer-specific frame.
+    #[must_use]
+    pub const fn manufacturer_code(&self) -> Option<u16> {
+        self.manufacturer_code
+    }
+
+    /// Returns the transaction sequence number.
+    #[must_use]
+    pub const fn transaction_sequence(&self) -> u8 {
+        self.transaction_sequence
+    }
+
+    /// Returns the command identifier.
+    #[must_use]
+    pub const fn command_id(&self) -> u8 {
+        self.command_id
+    }
+}
+
+impl FromLeStream for Header {
+    fn from_le_stream<T>(mut bytes: T) -> Option<Self>
+    where
+        T: Iterator<Item = u8>,
+    {
+        let frame_control = u8::from_le_stream(&mut bytes)?;
+        let manufacturer_code = if frame_control & MANUFACTURER_SPECIFIC == 0 {
+            None
+        } else {
+            Some(u16::from_le_stream(&mut bytes)?)
+        };
+
+        Some(Self {
+            frame_control,
+            manufacturer_code,
+            transaction_sequence: u8::from_le_stream(&mut bytes)?,
+            command_id: u8::from_le_stream(&mut bytes)?,
+        })
+    }
+}
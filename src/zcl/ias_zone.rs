@@ -0,0 +1,28 @@
+//! IAS Zone cluster (`0x0500`).
+//!
+//! IAS zone devices, such as motion or contact sensors, implement the server
+//! side of the cluster. The coordinator acts as IAS Control and Indicating
+//! Equipment (CIE) on the client side: it writes its IEEE address to the
+//! zone's [`CIE_ADDRESS`] attribute, answers the zone's enrollment request, and
+//! receives zone status change notifications.
+
+pub use self::enroll_response_code::EnrollResponseCode;
+pub use self::zone_enroll_request::ZoneEnrollRequest;
+pub use self::zone_enroll_response::ZoneEnrollResponse;
+pub use self::zone_status::ZoneStatus;
+pub use self::zone_status_change_notification::ZoneStatusChangeNotification;
+
+mod enroll_response_code;
+mod zone_enroll_request;
+mod zone_enroll_response;
+mod zone_status;
+mod zone_status_change_notification;
+
+/// The IAS Zone cluster ID.
+pub const CLUSTER_ID: u16 = 0x0500;
+
+/// The attribute ID of the IAS CIE address.
+pub const CIE_ADDRESS: u16 = 0x0010;
+
+/// The zone ID indicating that a zone is not enrolled.
+pub const UNENROLLED_ZONE_ID: u8 = 0xFF;
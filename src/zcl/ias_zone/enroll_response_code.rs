@@ -0,0 +1,30 @@
+use num_derive::FromPrimitive;
+use num_traits::FromPrimitive;
+
+/// Result of a zone enrollment sent in a [`ZoneEnrollResponse`](super::ZoneEnrollResponse).
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd, FromPrimitive)]
+#[repr(u8)]
+pub enum EnrollResponseCode {
+    /// The zone was enrolled.
+    Success = 0x00,
+    /// The zone type is not supported by the CIE.
+    NotSupported = 0x01,
+    /// The CIE does not permit new zones to enroll.
+    NoEnrollPermit = 0x02,
+    /// The CIE has reached its limit of enrolled zones.
+    TooManyZones = 0x03,
+}
+
+impl From<EnrollResponseCode> for u8 {
+    fn from(code: EnrollResponseCode) -> Self {
+        code as Self
+    }
+}
+
+impl TryFrom<u8> for EnrollResponseCode {
+    type Error = u8;
+
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
+        Self::from_u8(value).ok_or(value)
+    }
+}
@@ -0,0 +1,30 @@
+use le_stream::FromLeStream;
+
+use crate::zcl::{Command, Direction, FrameType};
+
+/// Zone Enroll Request command sent by an IAS zone.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq, FromLeStream)]
+pub struct ZoneEnrollRequest {
+    zone_type: u16,
+    manufacturer_code: u16,
+}
+
+impl ZoneEnrollRequest {
+    /// Returns the zone type, such as motion sensor or contact switch.
+    #[must_use]
+    pub const fn zone_type(&self) -> u16 {
+        self.zone_type
+    }
+
+    /// Returns the zone's manufacturer code.
+    #[must_use]
+    pub const fn manufacturer_code(&self) -> u16 {
+        self.manufacturer_code
+    }
+}
+
+impl Command for ZoneEnrollRequest {
+    const FRAME_TYPE: FrameType = FrameType::ClusterSpecific;
+    const DIRECTION: Direction = Direction::ServerToClient;
+    const ID: u8 = 0x01;
+}
@@ -0,0 +1,28 @@
+use le_stream::ToLeStream;
+
+use crate::zcl::ias_zone::EnrollResponseCode;
+use crate::zcl::{Command, Direction, FrameType};
+
+/// Zone Enroll Response command sent by the CIE.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq, ToLeStream)]
+pub struct ZoneEnrollResponse {
+    response_code: u8,
+    zone_id: u8,
+}
+
+impl ZoneEnrollResponse {
+    /// Creates a response with the given result and zone ID.
+    #[must_use]
+    pub fn new(response_code: EnrollResponseCode, zone_id: u8) -> Self {
+        Self {
+            response_code: response_code.into(),
+            zone_id,
+        }
+    }
+}
+
+impl Command for ZoneEnrollResponse {
+    const FRAME_TYPE: FrameType = FrameType::ClusterSpecific;
+    const DIRECTION: Direction = Direction::ClientToServer;
+    const ID: u8 = 0x00;
+}
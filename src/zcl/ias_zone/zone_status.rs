@@ -0,0 +1,31 @@
+use bitflags::bitflags;
+use le_stream::{FromLeStream, ToLeStream};
+
+/// The zone status bitmap of an IAS zone.
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq, FromLeStream, ToLeStream)]
+pub struct ZoneStatus(u16);
+
+bitflags! {
+    impl ZoneStatus: u16 {
+        /// Alarm 1 is active, for example the primary sensor has tripped.
+        const ALARM_1 = 0x0001;
+        /// Alarm 2 is active.
+        const ALARM_2 = 0x0002;
+        /// The device has been tampered with.
+        const TAMPER = 0x0004;
+        /// The battery is low.
+        const BATTERY = 0x0008;
+        /// The zone sends periodic supervision reports.
+        const SUPERVISION_REPORTS = 0x0010;
+        /// The zone reports when an alarm is restored.
+        const RESTORE_REPORTS = 0x0020;
+        /// The device reports a trouble or failure condition.
+        const TROUBLE = 0x0040;
+        /// Mains power has failed.
+        const AC_MAINS = 0x0080;
+        /// The zone is in test mode.
+        const TEST = 0x0100;
+        /// The battery is defective.
+        const BATTERY_DEFECT = 0x0200;
+    }
+}
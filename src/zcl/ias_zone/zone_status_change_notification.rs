@@ -0,0 +1,45 @@
+use le_stream::FromLeStream;
+
+use crate::zcl::ias_zone::ZoneStatus;
+use crate::zcl::{Command, Direction, FrameType};
+
+/// Zone Status Change Notification command sent by an IAS zone.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq, FromLeStream)]
+pub struct ZoneStatusChangeNotification {
+    zone_status: ZoneStatus,
+    extended_status: u8,
+    zone_id: u8,
+    delay: u16,
+}
+
+impl ZoneStatusChangeNotification {
+    /// Returns the new zone status.
+    #[must_use]
+    pub const fn zone_status(&self) -> ZoneStatus {
+        self.zone_status
+    }
+
+    /// Returns the extended status, which is reserved and usually zero.
+    #[must_use]
+    pub const fn extended_status(&self) -> u8 {
+        self.extended_status
+    }
+
+    /// Returns the zone ID assigned at enrollment.
+    #[must_use]
+    pub const fn zone_id(&self) -> u8 {
+        self.zone_id
+    }
+
+    /// Returns the time in quarter seconds between the status change and its notification.
+    #[must_use]
+    pub const fn delay(&self) -> u16 {
+        self.delay
+    }
+}
+
+impl Command for ZoneStatusChangeNotification {
+    const FRAME_TYPE: FrameType = FrameType::ClusterSpecific;
+    const DIRECTION: Direction = Direction::ServerToClient;
+    const ID: u8 = 0x00;
+}
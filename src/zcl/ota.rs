@@ -0,0 +1,38 @@
+//! OTA Upgrade cluster (`0x0019`).
+//!
+//! The cluster lets a client device query an upgrade server for a newer image
+//! and download it block by block. This module models the commands exchanged
+//! by the server side and the Zigbee OTA upgrade file format in [`Image`].
+
+pub use self::image::{
+    FILE_IDENTIFIER, Image, ImageHeader, InvalidImage, Signature, SigningCertificate, SubElement,
+    Tag,
+};
+pub use self::image_block_request::ImageBlockRequest;
+pub use self::image_block_response::ImageBlockResponse;
+pub use self::image_page_request::ImagePageRequest;
+pub use self::query_next_image_request::QueryNextImageRequest;
+pub use self::query_next_image_response::QueryNextImageResponse;
+pub use self::upgrade_end_request::UpgradeEndRequest;
+pub use self::upgrade_end_response::UpgradeEndResponse;
+
+mod image;
+mod image_block_request;
+mod image_block_response;
+mod image_page_request;
+mod query_next_image_request;
+mod query_next_image_response;
+mod upgrade_end_request;
+mod upgrade_end_response;
+
+/// The OTA Upgrade cluster ID.
+pub const CLUSTER_ID: u16 = 0x0019;
+
+/// The manufacturer code wildcard accepted in image queries.
+pub const MANUFACTURER_CODE_WILDCARD: u16 = 0xFFFF;
+
+/// The image type wildcard accepted in image queries.
+pub const IMAGE_TYPE_WILDCARD: u16 = 0xFFFF;
+
+/// The file version wildcard accepted in image queries.
+pub const FILE_VERSION_WILDCARD: u32 = 0xFFFF_FFFF;
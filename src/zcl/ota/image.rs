@@ -0,0 +1,397 @@
+//! Zigbee OTA upgrade file format.
+
+use core::ops::Range;
+use std::io::{self, ErrorKind};
+
+use le_stream::FromLeStream;
+
+pub use self::header::ImageHeader;
+pub use self::signature::{Signature, SigningCertificate};
+pub use self::tag::Tag;
+use crate::ember::Eui64;
+
+mod header;
+mod signature;
+mod tag;
+
+/// The magic number at the start of every Zigbee OTA upgrade file.
+pub const FILE_IDENTIFIER: u32 = 0x0BEE_F11E;
+
+const SUB_ELEMENT_HEADER_LENGTH: usize = 6;
+
+type SubElements = Box<[(Tag, Range<usize>)]>;
+
+/// A parsed and validated Zigbee OTA upgrade file.
+///
+/// The complete file is kept in memory, because image block responses serve
+/// byte ranges of the whole file, including its header. Parsing validates the
+/// header, requires the total image size to match the file length, and
+/// requires the sub-elements to fill the remainder of the file exactly.
+///
+/// Signature-related sub-elements are checked structurally: they must have
+/// the length defined by their crypto suite, the signature must be the last
+/// sub-element, and the signer must match the subject of an included signing
+/// certificate. The ECDSA signature itself is not verified.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Image {
+    header: ImageHeader,
+    sub_elements: SubElements,
+    data: Box<[u8]>,
+}
+
+impl Image {
+    /// Parses and validates an OTA upgrade file.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`InvalidImage`] describing the first violated constraint.
+    pub fn parse(data: impl Into<Box<[u8]>>) -> Result<Self, InvalidImage> {
+        let data = data.into();
+        let header =
+            ImageHeader::from_le_stream(data.iter().copied()).ok_or(InvalidImage::Truncated)?;
+        header.validate()?;
+
+        if usize::try_from(header.total_image_size()).ok() != Some(data.len()) {
+            return Err(InvalidImage::TotalImageSize {
+                header: header.total_image_size(),
+                actual: data.len(),
+            });
+        }
+
+        let sub_elements = parse_sub_elements(&data, usize::from(header.header_length()))?;
+        let image = Self {
+            header,
+            sub_elements,
+            data,
+        };
+        image.validate_signature()?;
+        Ok(image)
+    }
+
+    /// Returns the image header.
+    #[must_use]
+    pub const fn header(&self) -> &ImageHeader {
+        &self.header
+    }
+
+    /// Returns the complete file contents, including the header.
+    #[must_use]
+    pub const fn data(&self) -> &[u8] {
+        &self.data
+    }
+
+    /// Returns the size of the complete file in bytes.
+    #[must_use]
+    pub const fn size(&self) -> usize {
+        self.data.len()
+    }
+
+    /// Returns the sub-elements in file order.
+    pub fn sub_elements(&self) -> impl Iterator<Item = SubElement<'_>> {
+        self.sub_elements.iter().map(|(tag, range)| SubElement {
+            tag: *tag,
+            data: &self.data[range.clone()],
+        })
+    }
+
+    /// Returns the first sub-element with the given tag.
+    #[must_use]
+    pub fn sub_element(&self, tag: Tag) -> Option<SubElement<'_>> {
+        self.sub_elements()
+            .find(|sub_element| sub_element.tag == tag)
+    }
+
+    /// Returns the ECDSA signature sub-element, if the image is signed.
+    #[must_use]
+    pub fn signature(&self) -> Option<Signature<'_>> {
+        self.sub_elements()
+            .find_map(|sub_element| Signature::try_from(sub_element).ok())
+    }
+
+    /// Returns the ECDSA signing certificate sub-element, if included.
+    #[must_use]
+    pub fn signing_certificate(&self) -> Option<SigningCertificate<'_>> {
+        self.sub_elements()
+            .find_map(|sub_element| SigningCertificate::try_from(sub_element).ok())
+    }
+
+    /// Returns the image data between `offset` and at most `offset + length`.
+    ///
+    /// Returns an empty slice if the offset is at or beyond the end of the file.
+    #[must_use]
+    pub fn block(&self, offset: usize, length: usize) -> &[u8] {
+        let start = offset.min(self.data.len());
+        let end = offset.saturating_add(length).min(self.data.len());
+        &self.data[start..end]
+    }
+
+    fn validate_signature(&self) -> Result<(), InvalidImage> {
+        for (index, sub_element) in self.sub_elements().enumerate() {
+            if let Some(expected) = sub_element.tag.expected_length()
+                && sub_element.data.len() != expected
+            {
+                return Err(InvalidImage::SubElementLength {
+                    tag: sub_element.tag.into(),
+                    length: sub_element.data.len(),
+                });
+            }
+
+            if sub_element.tag.is_signature() && index + 1 != self.sub_elements.len() {
+                return Err(InvalidImage::SignatureNotLast);
+            }
+        }
+
+        if let (Some(signature), Some(certificate)) = (self.signature(), self.signing_certificate())
+            && signature.signer() != certificate.subject()
+        {
+            return Err(InvalidImage::SignerMismatch {
+                signer: signature.signer(),
+                subject: certificate.subject(),
+            });
+        }
+
+        Ok(())
+    }
+}
+
+impl TryFrom<Vec<u8>> for Image {
+    type Error = InvalidImage;
+
+    fn try_from(data: Vec<u8>) -> Result<Self, Self::Error> {
+        Self::parse(data)
+    }
+}
+
+/// A tagged sub-element of an OTA upgrade file.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct SubElement<'data> {
+    tag: Tag,
+    data: &'data [u8],
+}
+
+impl<'data> SubElement<'data> {
+    /// Returns the sub-element tag.
+    #[must_use]
+    pub const fn tag(&self) -> Tag {
+        self.tag
+    }
+
+    /// Returns the sub-element payload without its tag and length fields.
+    #[must_use]
+    pub const fn data(&self) -> &'data [u8] {
+        self.data
+    }
+}
+
+/// Reasons why an OTA upgrade file is rejected.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, thiserror::Error)]
+pub enum InvalidImage {
+    /// The file ends inside its header.
+    #[error("OTA file is truncated")]
+    Truncated,
+
+    /// The file does not start with the OTA file identifier.
+    #[error("Invalid OTA file identifier: {0:#010X}")]
+    FileIdentifier(u32),
+
+    /// The header version is not supported.
+    #[error("Unsupported OTA header version: {0:#06X}")]
+    HeaderVersion(u16),
+
+    /// The header length does not cover the fields announced by the header.
+    #[error("Invalid OTA header length: {0}")]
+    HeaderLength(u16),
+
+    /// The total image size in the header does not match the file length.
+    #[error("OTA total image size {header} does not match file length {actual}")]
+    TotalImageSize {
+        /// The size stated in the header.
+        header: u32,
+        /// The actual file length.
+        actual: usize,
+    },
+
+    /// A sub-element exceeds the file or has an invalid length for its tag.
+    #[error("Invalid length {length} of OTA sub-element {tag:#06X}")]
+    SubElementLength {
+        /// The raw sub-element tag.
+        tag: u16,
+        /// The sub-element length.
+        length: usize,
+    },
+
+    /// The signature is followed by further sub-elements.
+    #[error("OTA signature is not the last sub-element")]
+    SignatureNotLast,
+
+    /// The signer of the image does not match the subject of the signing certificate.
+    #[error("OTA signer {signer} does not match certificate subject {subject}")]
+    SignerMismatch {
+        /// The signer IEEE address from the signature sub-element.
+        signer: Eui64,
+        /// The subject IEEE address from the signing certificate.
+        subject: Eui64,
+    },
+}
+
+impl From<InvalidImage> for io::Error {
+    fn from(error: InvalidImage) -> Self {
+        Self::new(ErrorKind::InvalidData, error)
+    }
+}
+
+fn parse_sub_elements(data: &[u8], mut offset: usize) -> Result<SubElements, InvalidImage> {
+    let mut sub_elements = Vec::new();
+
+    while offset < data.len() {
+        let mut bytes = data
+            .get(offset..offset + SUB_ELEMENT_HEADER_LENGTH)
+            .ok_or(InvalidImage::Truncated)?
+            .iter()
+            .copied();
+        let tag = u16::from_le_stream(&mut bytes).ok_or(InvalidImage::Truncated)?;
+        let length = u32::from_le_stream(&mut bytes).ok_or(InvalidImage::Truncated)?;
+        let start = offset + SUB_ELEMENT_HEADER_LENGTH;
+        let end = usize::try_from(length)
+            .ok()
+            .and_then(|length| start.checked_add(length))
+            .filter(|end| *end <= data.len())
+            .ok_or_else(|| InvalidImage::SubElementLength {
+                tag,
+                length: usize::try_from(length).unwrap_or(usize::MAX),
+            })?;
+        sub_elements.push((Tag::from(tag), start..end));
+        offset = end;
+    }
+
+    Ok(sub_elements.into_boxed_slice())
+}
+
+#[cfg(test)]
+mod tests {
+    use le_stream::ToLeStream;
+
+    use super::*;
+
+    const MANUFACTURER_CODE: u16 = 0x10F2;
+    const IMAGE_TYPE: u16 = 0x0042;
+    const FILE_VERSION: u32 = 0x0102_0304;
+    const PAYLOAD: &[u8] = b"firmware";
+    const SIGNER: [u8; 8] = [0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07, 0x08];
+
+    fn sub_element(tag: u16, data: &[u8]) -> Vec<u8> {
+        let length = u32::try_from(data.len()).expect("test data is small");
+        tag.to_le_stream()
+            .chain(length.to_le_stream())
+            .chain(data.iter().copied())
+            .collect()
+    }
+
+    fn file(sub_elements: &[Vec<u8>]) -> Vec<u8> {
+        let body: Vec<u8> = sub_elements.concat();
+        let total = u32::try_from(56 + body.len()).expect("test data is small");
+        let mut data: Vec<u8> = FILE_IDENTIFIER
+            .to_le_stream()
+            .chain(0x0100_u16.to_le_stream())
+            .chain(56_u16.to_le_stream())
+            .chain(0_u16.to_le_stream())
+            .chain(MANUFACTURER_CODE.to_le_stream())
+            .chain(IMAGE_TYPE.to_le_stream())
+            .chain(FILE_VERSION.to_le_stream())
+            .chain(0x0002_u16.to_le_stream())
+            .chain([0; 32])
+            .chain(total.to_le_stream())
+            .collect();
+        data.extend(body);
+        data
+    }
+
+    fn signature() -> Vec<u8> {
+        let mut data = SIGNER.to_vec();
+        data.extend([0xAA; 42]);
+        sub_element(0x0001, &data)
+    }
+
+    fn certificate(subject: [u8; 8]) -> Vec<u8> {
+        let mut data = vec![0x55; 22];
+        data.extend(subject.iter().rev());
+        data.extend([0x66; 18]);
+        sub_element(0x0002, &data)
+    }
+
+    #[test]
+    fn parses_valid_image() {
+        let image = Image::parse(file(&[sub_element(0x0000, PAYLOAD)])).expect("image is valid");
+
+        assert_eq!(image.header().manufacturer_code(), MANUFACTURER_CODE);
+        assert_eq!(image.header().image_type(), IMAGE_TYPE);
+        assert_eq!(image.header().file_version(), FILE_VERSION);
+        assert_eq!(
+            image
+                .sub_element(Tag::UpgradeImage)
+                .map(|element| element.data()),
+            Some(PAYLOAD)
+        );
+        assert_eq!(image.block(62, 4), b"firm");
+    }
+
+    #[test]
+    fn rejects_invalid_file_identifier() {
+        let mut data = file(&[sub_element(0x0000, PAYLOAD)]);
+        data[0] = 0x00;
+
+        assert_eq!(
+            Image::parse(data),
+            Err(InvalidImage::FileIdentifier(0x0BEE_F100))
+        );
+    }
+
+    #[test]
+    fn rejects_total_image_size_mismatch() {
+        let mut data = file(&[sub_element(0x0000, PAYLOAD)]);
+        data.push(0x00);
+
+        assert!(matches!(
+            Image::parse(data),
+            Err(InvalidImage::TotalImageSize { .. })
+        ));
+    }
+
+    #[test]
+    fn rejects_overlong_sub_element() {
+        let mut element = sub_element(0x0000, PAYLOAD);
+        element[2] = 0xFF;
+
+        assert!(matches!(
+            Image::parse(file(&[element])),
+            Err(InvalidImage::SubElementLength { tag: 0x0000, .. })
+        ));
+    }
+
+    #[test]
+    fn validates_signature_sub_elements() {
+        let image = Image::parse(file(&[
+            certificate(SIGNER),
+            sub_element(0x0000, PAYLOAD),
+            signature(),
+        ]))
+        .expect("image is valid");
+
+        assert_eq!(
+            image.signature().map(|signature| signature.signer()),
+            Eui64::from_le_stream(SIGNER.into_iter())
+        );
+        assert_eq!(
+            Image::parse(file(&[signature(), sub_element(0x0000, PAYLOAD)])),
+            Err(InvalidImage::SignatureNotLast)
+        );
+        assert!(matches!(
+            Image::parse(file(&[certificate([0; 8]), signature()])),
+            Err(InvalidImage::SignerMismatch { .. })
+        ));
+        assert!(matches!(
+            Image::parse(file(&[sub_element(0x0001, &SIGNER)])),
+            Err(InvalidImage::SubElementLength { tag: 0x0001, .. })
+        ));
+    }
+}
@@ -0,0 +1,191 @@
+use le_stream::FromLeStream;
+
+use super::{FILE_IDENTIFIER, InvalidImage};
+use crate::ember::Eui64;
+
+const HEADER_VERSION: u16 = 0x0100;
+const FIXED_HEADER_LENGTH: u16 = 56;
+const SECURITY_CREDENTIAL_VERSION_PRESENT: u16 = 0x0001;
+const DEVICE_SPECIFIC_FILE: u16 = 0x0002;
+const HARDWARE_VERSIONS_PRESENT: u16 = 0x0004;
+const SECURITY_CREDENTIAL_VERSION_LENGTH: u16 = 1;
+const UPGRADE_FILE_DESTINATION_LENGTH: u16 = 8;
+const HARDWARE_VERSIONS_LENGTH: u16 = 4;
+
+/// The header of a Zigbee OTA upgrade file.
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub struct ImageHeader {
+    file_identifier: u32,
+    header_version: u16,
+    header_length: u16,
+    field_control: u16,
+    manufacturer_code: u16,
+    image_type: u16,
+    file_version: u32,
+    zigbee_stack_version: u16,
+    header_string: [u8; 32],
+    total_image_size: u32,
+    security_credential_version: Option<u8>,
+    upgrade_file_destination: Option<Eui64>,
+    hardware_versions: Option<(u16, u16)>,
+}
+
+impl ImageHeader {
+    /// Returns the header version.
+    #[must_use]
+    pub const fn header_version(&self) -> u16 {
+        self.header_version
+    }
+
+    /// Returns the length of the header in bytes, including optional fields.
+    #[must_use]
+    pub const fn header_length(&self) -> u16 {
+        self.header_length
+    }
+
+    /// Returns the manufacturer code of the image.
+    #[must_use]
+    pub const fn manufacturer_code(&self) -> u16 {
+        self.manufacturer_code
+    }
+
+    /// Returns the manufacturer-specific image type.
+    #[must_use]
+    pub const fn image_type(&self) -> u16 {
+        self.image_type
+    }
+
+    /// Returns the file version of the image.
+    #[must_use]
+    pub const fn file_version(&self) -> u32 {
+        self.file_version
+    }
+
+    /// Returns the Zigbee stack version the image was built for.
+    #[must_use]
+    pub const fn zigbee_stack_version(&self) -> u16 {
+        self.zigbee_stack_version
+    }
+
+    /// Returns the human-readable header string with trailing NUL bytes removed.
+    #[must_use]
+    pub fn header_string(&self) -> String {
+        String::from_utf8_lossy(&self.header_string)
+            .trim_end_matches('\0')
+            .to_owned()
+    }
+
+    /// Returns the total image size in bytes, including the header.
+    #[must_use]
+    pub const fn total_image_size(&self) -> u32 {
+        self.total_image_size
+    }
+
+    /// Returns the security credential version, if present.
+    #[must_use]
+    pub const fn security_credential_version(&self) -> Option<u8> {
+        self.security_credential_version
+    }
+
+    /// Returns the IEEE address of the only device allowed to use the image, if any.
+    #[must_use]
+    pub const fn upgrade_file_destination(&self) -> Option<Eui64> {
+        self.upgrade_file_destination
+    }
+
+    /// Returns the minimum and maximum hardware versions supported by the image, if present.
+    #[must_use]
+    pub const fn hardware_versions(&self) -> Option<(u16, u16)> {
+        self.hardware_versions
+    }
+
+    /// Returns whether the image may be installed on the given hardware version.
+    ///
+    /// Images without hardware version limits, and clients that do not report
+    /// their hardware version, are always considered compatible.
+    #[must_use]
+    pub fn supports_hardware_version(&self, hardware_version: Option<u16>) -> bool {
+        match (self.hardware_versions, hardware_version) {
+            (Some((minimum, maximum)), Some(version)) => (minimum..=maximum).contains(&version),
+            _ => true,
+        }
+    }
+
+    pub(super) const fn validate(&self) -> Result<(), InvalidImage> {
+        if self.file_identifier != FILE_IDENTIFIER {
+            return Err(InvalidImage::FileIdentifier(self.file_identifier));
+        }
+
+        if self.header_version != HEADER_VERSION {
+            return Err(InvalidImage::HeaderVersion(self.header_version));
+        }
+
+        if self.header_length < self.minimum_header_length() {
+            return Err(InvalidImage::HeaderLength(self.header_length));
+        }
+
+        Ok(())
+    }
+
+    const fn minimum_header_length(&self) -> u16 {
+        let mut length = FIXED_HEADER_LENGTH;
+
+        if self.field_control & SECURITY_CREDENTIAL_VERSION_PRESENT != 0 {
+            length += SECURITY_CREDENTIAL_VERSION_LENGTH;
+        }
+
+        if self.field_control & DEVICE_SPECIFIC_FILE != 0 {
+            length += UPGRADE_FILE_DESTINATION_LENGTH;
+        }
+
+        if self.field_control & HARDWARE_VERSIONS_PRESENT != 0 {
+            length += HARDWARE_VERSIONS_LENGTH;
+        }
+
+        length
+    }
+}
+
+impl FromLeStream for ImageHeader {
+    fn from_le_stream<T>(mut bytes: T) -> Option<Self>
+    where
+        T: Iterator<Item = u8>,
+    {
+        let file_identifier = u32::from_le_stream(&mut bytes)?;
+        let header_version = u16::from_le_stream(&mut bytes)?;
+        let header_length = u16::from_le_stream(&mut bytes)?;
+        let field_control = u16::from_le_stream(&mut bytes)?;
+
+        Some(Self {
+            file_identifier,
+            header_version,
+            header_length,
+            field_control,
+            manufacturer_code: u16::from_le_stream(&mut bytes)?,
+            image_type: u16::from_le_stream(&mut bytes)?,
+            file_version: u32::from_le_stream(&mut bytes)?,
+            zigbee_stack_version: u16::from_le_stream(&mut bytes)?,
+            header_string: <[u8; 32]>::from_le_stream(&mut bytes)?,
+            total_image_size: u32::from_le_stream(&mut bytes)?,
+            security_credential_version: if field_control & SECURITY_CREDENTIAL_VERSION_PRESENT == 0
+            {
+                None
+            } else {
+                Some(u8::from_le_stream(&mut bytes)?)
+            },
+            upgrade_file_destination: if field_control & DEVICE_SPECIFIC_FILE == 0 {
+                None
+            } else {
+                Some(Eui64::from_le_stream(&mut bytes)?)
+            },
+            hardware_versions: if field_control & HARDWARE_VERSIONS_PRESENT == 0 {
+                None
+            } else {
+                Some((
+                    u16::from_le_stream(&mut bytes)?,
+                    u16::from_le_stream(&mut bytes)?,
+                ))
+            },
+        })
+    }
+}
@@ -0,0 +1,94 @@
+use le_stream::FromLeStream;
+
+use super::{SubElement, Tag};
+use crate::ember::Eui64;
+
+const SIGNER_LENGTH: usize = 8;
+const CRYPTO_SUITE_1_SUBJECT: usize = 22;
+const CRYPTO_SUITE_2_SUBJECT: usize = 28;
+
+/// An ECDSA signature sub-element.
+///
+/// The signature covers all preceding bytes of the file. It is exposed for
+/// verification by the application; this crate does not verify it.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct Signature<'data> {
+    signer: Eui64,
+    signature: &'data [u8],
+}
+
+impl<'data> Signature<'data> {
+    /// Returns the IEEE address of the signer.
+    #[must_use]
+    pub const fn signer(&self) -> Eui64 {
+        self.signer
+    }
+
+    /// Returns the raw ECDSA signature data.
+    #[must_use]
+    pub const fn signature(&self) -> &'data [u8] {
+        self.signature
+    }
+}
+
+impl<'data> TryFrom<SubElement<'data>> for Signature<'data> {
+    type Error = SubElement<'data>;
+
+    fn try_from(sub_element: SubElement<'data>) -> Result<Self, Self::Error> {
+        if !sub_element.tag().is_signature() || sub_element.data().len() < SIGNER_LENGTH {
+            return Err(sub_element);
+        }
+
+        let (signer, signature) = sub_element.data().split_at(SIGNER_LENGTH);
+        Ok(Self {
+            signer: Eui64::from_le_stream(signer.iter().copied()).ok_or(sub_element)?,
+            signature,
+        })
+    }
+}
+
+/// An ECDSA signing certificate sub-element.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct SigningCertificate<'data> {
+    subject: Eui64,
+    certificate: &'data [u8],
+}
+
+impl<'data> SigningCertificate<'data> {
+    /// Returns the IEEE address of the certificate subject.
+    ///
+    /// Certificates store the subject most significant byte first, unlike
+    /// the little-endian addresses used elsewhere in ZCL.
+    #[must_use]
+    pub const fn subject(&self) -> Eui64 {
+        self.subject
+    }
+
+    /// Returns the raw certificate data.
+    #[must_use]
+    pub const fn certificate(&self) -> &'data [u8] {
+        self.certificate
+    }
+}
+
+impl<'data> TryFrom<SubElement<'data>> for SigningCertificate<'data> {
+    type Error = SubElement<'data>;
+
+    fn try_from(sub_element: SubElement<'data>) -> Result<Self, Self::Error> {
+        let offset = match sub_element.tag() {
+            Tag::EcdsaSigningCertificateCryptoSuite1 => CRYPTO_SUITE_1_SUBJECT,
+            Tag::EcdsaSigningCertificateCryptoSuite2 => CRYPTO_SUITE_2_SUBJECT,
+            _ => return Err(sub_element),
+        };
+        let subject: [u8; 8] = sub_element
+            .data()
+            .get(offset..offset + SIGNER_LENGTH)
+            .and_then(|subject| subject.try_into().ok())
+            .ok_or(sub_element)?;
+
+        Ok(Self {
+            subject: Eui64::from(subject),
+            certificate: sub_element.data(),
+        })
+    }
+}
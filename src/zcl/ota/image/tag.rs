@@ -0,0 +1,77 @@
+/// Tag identifying the contents of an OTA file sub-element.
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub enum Tag {
+    /// The upgrade image itself.
+    UpgradeImage,
+    /// ECDSA signature using crypto suite 1 (`sect163k1`).
+    EcdsaSignatureCryptoSuite1,
+    /// ECDSA signing certificate using crypto suite 1 (`sect163k1`).
+    EcdsaSigningCertificateCryptoSuite1,
+    /// AES-MMO hash over the preceding file contents.
+    ImageIntegrityCode,
+    /// Picture data.
+    PictureData,
+    /// ECDSA signature using crypto suite 2 (`sect283k1`).
+    EcdsaSignatureCryptoSuite2,
+    /// ECDSA signing certificate using crypto suite 2 (`sect283k1`).
+    EcdsaSigningCertificateCryptoSuite2,
+    /// A manufacturer-specific tag in the range `0xF000..=0xFFFF`.
+    ManufacturerSpecific(u16),
+    /// A reserved tag.
+    Reserved(u16),
+}
+
+impl Tag {
+    /// Returns whether the tag identifies an ECDSA signature.
+    #[must_use]
+    pub const fn is_signature(self) -> bool {
+        matches!(
+            self,
+            Self::EcdsaSignatureCryptoSuite1 | Self::EcdsaSignatureCryptoSuite2
+        )
+    }
+
+    /// Returns the fixed payload length mandated for this tag, if any.
+    #[must_use]
+    pub const fn expected_length(self) -> Option<usize> {
+        match self {
+            Self::EcdsaSignatureCryptoSuite1 => Some(50),
+            Self::EcdsaSigningCertificateCryptoSuite1 => Some(48),
+            Self::ImageIntegrityCode => Some(16),
+            Self::EcdsaSignatureCryptoSuite2 => Some(80),
+            Self::EcdsaSigningCertificateCryptoSuite2 => Some(74),
+            _ => None,
+        }
+    }
+}
+
+impl From<u16> for Tag {
+    fn from(tag: u16) -> Self {
+        match tag {
+            0x0000 => Self::UpgradeImage,
+            0x0001 => Self::EcdsaSignatureCryptoSuite1,
+            0x0002 => Self::EcdsaSigningCertificateCryptoSuite1,
+            0x0003 => Self::ImageIntegrityCode,
+            0x0004 => Self::PictureData,
+            0x0005 => Self::EcdsaSignatureCryptoSuite2,
+            0x0006 => Self::EcdsaSigningCertificateCryptoSuite2,
+            0xF000..=0xFFFF => Self::ManufacturerSpecific(tag),
+            other => Self::Reserved(other),
+        }
+    }
+}
+
+impl From<Tag> for u16 {
+    fn from(tag: Tag) -> Self {
+        match tag {
+            Tag::UpgradeImage => 0x0000,
+            Tag::EcdsaSignatureCryptoSuite1 => 0x0001,
+            Tag::EcdsaSigningCertificateCryptoSuite1 => 0x0002,
+            Tag::ImageIntegrityCode => 0x0003,
+            Tag::PictureData => 0x0004,
+            Tag::EcdsaSignatureCryptoSuite2 => 0x0005,
+            Tag::EcdsaSigningCertificateCryptoSuite2 => 0x0006,
+            Tag::ManufacturerSpecific(tag) | Tag::Reserved(tag) => tag,
+        }
+    }
+}
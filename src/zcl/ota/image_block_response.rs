@@ -0,0 +1,92 @@
+use le_stream::ToLeStream;
+
+use crate::types::ByteSizedVec;
+use crate::zcl::{Command, Direction, FrameType, Status};
+
+/// Image Block Response command sent by the OTA server.
+///
+/// Depending on its status, the response carries a block of image data, asks
+/// the client to retry later, or aborts the download.
+#[derive(Clone, Debug, Eq, Hash, PartialEq, ToLeStream)]
+pub struct ImageBlockResponse {
+    status: u8,
+    block: Option<Block>,
+    wait_for_data: Option<WaitForData>,
+}
+
+#[derive(Clone, Debug, Eq, Hash, PartialEq, ToLeStream)]
+pub struct Block {
+    manufacturer_code: u16,
+    image_type: u16,
+    file_version: u32,
+    file_offset: u32,
+    data: ByteSizedVec<u8>,
+}
+
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq, ToLeStream)]
+pub struct WaitForData {
+    current_time: u32,
+    request_time: u32,
+    minimum_block_period: u16,
+}
+
+impl ImageBlockResponse {
+    /// Creates a response carrying a block of image data.
+    #[must_use]
+    pub const fn success(
+        manufacturer_code: u16,
+        image_type: u16,
+        file_version: u32,
+        file_offset: u32,
+        data: ByteSizedVec<u8>,
+    ) -> Self {
+        Self {
+            status: Status::Success as u8,
+            block: Some(Block {
+                manufacturer_code,
+                image_type,
+                file_version,
+                file_offset,
+                data,
+            }),
+            wait_for_data: None,
+        }
+    }
+
+    /// Creates a response asking the client to retry the request later.
+    ///
+    /// Times are UTC seconds; a current time of zero makes `request_time`
+    /// relative. The minimum block period is given in milliseconds.
+    #[must_use]
+    pub const fn wait_for_data(
+        current_time: u32,
+        request_time: u32,
+        minimum_block_period: u16,
+    ) -> Self {
+        Self {
+            status: Status::WaitForData as u8,
+            block: None,
+            wait_for_data: Some(WaitForData {
+                current_time,
+                request_time,
+                minimum_block_period,
+            }),
+        }
+    }
+
+    /// Creates a response aborting the client's download.
+    #[must_use]
+    pub const fn abort() -> Self {
+        Self {
+            status: Status::Abort as u8,
+            block: None,
+            wait_for_data: None,
+        }
+    }
+}
+
+impl Command for ImageBlockResponse {
+    const FRAME_TYPE: FrameType = FrameType::ClusterSpecific;
+    const DIRECTION: Direction = Direction::ServerToClient;
+    const ID: u8 = 0x05;
+}
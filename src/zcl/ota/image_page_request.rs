@@ -0,0 +1,104 @@
+use le_stream::FromLeStream;
+
+use crate::ember::Eui64;
+use crate::zcl::{Command, Direction, FrameType};
+
+const REQUEST_NODE_ADDRESS_PRESENT: u8 = 0b0000_0001;
+
+/// Image Page Request command sent by an OTA client.
+///
+/// The server answers a page request with a series of image block responses
+/// covering `page_size` bytes, spaced by `response_spacing` milliseconds.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct ImagePageRequest {
+    field_control: u8,
+    manufacturer_code: u16,
+    image_type: u16,
+    file_version: u32,
+    file_offset: u32,
+    maximum_data_size: u8,
+    page_size: u16,
+    response_spacing: u16,
+    request_node_address: Option<Eui64>,
+}
+
+impl ImagePageRequest {
+    /// Returns the manufacturer code of the requested image.
+    #[must_use]
+    pub const fn manufacturer_code(&self) -> u16 {
+        self.manufacturer_code
+    }
+
+    /// Returns the image type of the requested image.
+    #[must_use]
+    pub const fn image_type(&self) -> u16 {
+        self.image_type
+    }
+
+    /// Returns the file version of the requested image.
+    #[must_use]
+    pub const fn file_version(&self) -> u32 {
+        self.file_version
+    }
+
+    /// Returns the offset of the requested page within the image file.
+    #[must_use]
+    pub const fn file_offset(&self) -> u32 {
+        self.file_offset
+    }
+
+    /// Returns the largest block the client accepts.
+    #[must_use]
+    pub const fn maximum_data_size(&self) -> u8 {
+        self.maximum_data_size
+    }
+
+    /// Returns the number of bytes requested for the page.
+    #[must_use]
+    pub const fn page_size(&self) -> u16 {
+        self.page_size
+    }
+
+    /// Returns the requested delay between two block responses in milliseconds.
+    #[must_use]
+    pub const fn response_spacing(&self) -> u16 {
+        self.response_spacing
+    }
+
+    /// Returns the client's IEEE address, if it was included.
+    #[must_use]
+    pub const fn request_node_address(&self) -> Option<Eui64> {
+        self.request_node_address
+    }
+}
+
+impl Command for ImagePageRequest {
+    const FRAME_TYPE: FrameType = FrameType::ClusterSpecific;
+    const DIRECTION: Direction = Direction::ClientToServer;
+    const ID: u8 = 0x04;
+}
+
+impl FromLeStream for ImagePageRequest {
+    fn from_le_stream<T>(mut bytes: T) -> Option<Self>
+    where
+        T: Iterator<Item = u8>,
+    {
+        let field_control = u8::from_le_stream(&mut bytes)?;
+
+        Some(Self {
+            field_control,
+            manufacturer_code: u16::from_le_stream(&mut bytes)?,
+            image_type: u16::from_le_stream(&mut bytes)?,
+            file_version: u32::from_le_stream(&mut bytes)?,
+            file_offset: u32::from_le_stream(&mut bytes)?,
+            maximum_data_size: u8::from_le_stream(&mut bytes)?,
+            page_size: u16::from_le_stream(&mut bytes)?,
+            response_spacing: u16::from_le_stream(&mut bytes)?,
+            request_node_address: if field_control & REQUEST_NODE_ADDRESS_PRESENT == 0 {
+                None
+            } else {
+                Some(Eui64::from_le_stream(&mut bytes)?)
+            },
+        })
+    }
+}
@@ -0,0 +1,68 @@
+use le_stream::FromLeStream;
+
+use crate::zcl::{Command, Direction, FrameType};
+
+const HARDWARE_VERSION_PRESENT: u8 = 0b0000_0001;
+
+/// Query Next Image Request command sent by an OTA client.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct QueryNextImageRequest {
+    field_control: u8,
+    manufacturer_code: u16,
+    image_type: u16,
+    current_file_version: u32,
+    hardware_version: Option<u16>,
+}
+
+impl QueryNextImageRequest {
+    /// Returns the client's manufacturer code.
+    #[must_use]
+    pub const fn manufacturer_code(&self) -> u16 {
+        self.manufacturer_code
+    }
+
+    /// Returns the client's image type.
+    #[must_use]
+    pub const fn image_type(&self) -> u16 {
+        self.image_type
+    }
+
+    /// Returns the file version currently running on the client.
+    #[must_use]
+    pub const fn current_file_version(&self) -> u32 {
+        self.current_file_version
+    }
+
+    /// Returns the client's hardware version, if it was reported.
+    #[must_use]
+    pub const fn hardware_version(&self) -> Option<u16> {
+        self.hardware_version
+    }
+}
+
+impl Command for QueryNextImageRequest {
+    const FRAME_TYPE: FrameType = FrameType::ClusterSpecific;
+    const DIRECTION: Direction = Direction::ClientToServer;
+    const ID: u8 = 0x01;
+}
+
+impl FromLeStream for QueryNextImageRequest {
+    fn from_le_stream<T>(mut bytes: T) -> Option<Self>
+    where
+        T: Iterator<Item = u8>,
+    {
+        let field_control = u8::from_le_stream(&mut bytes)?;
+
+        Some(Self {
+            field_control,
+            manufacturer_code: u16::from_le_stream(&mut bytes)?,
+            image_type: u16::from_le_stream(&mut bytes)?,
+            current_file_version: u32::from_le_stream(&mut bytes)?,
+            hardware_version: if field_control & HARDWARE_VERSION_PRESENT == 0 {
+                None
+            } else {
+                Some(u16::from_le_stream(&mut bytes)?)
+            },
+        })
+    }
+}
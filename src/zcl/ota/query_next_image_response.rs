@@ -0,0 +1,57 @@
+use le_stream::ToLeStream;
+
+use crate::zcl::{Command, Direction, FrameType, Status};
+
+/// Query Next Image Response command sent by the OTA server.
+///
+/// The image fields are only present on the wire if the status is
+/// [`Status::Success`].
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq, ToLeStream)]
+pub struct QueryNextImageResponse {
+    status: u8,
+    image: Option<NextImage>,
+}
+
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq, ToLeStream)]
+pub struct NextImage {
+    manufacturer_code: u16,
+    image_type: u16,
+    file_version: u32,
+    image_size: u32,
+}
+
+impl QueryNextImageResponse {
+    /// Creates a response offering an image to the client.
+    #[must_use]
+    pub const fn available(
+        manufacturer_code: u16,
+        image_type: u16,
+        file_version: u32,
+        image_size: u32,
+    ) -> Self {
+        Self {
+            status: Status::Success as u8,
+            image: Some(NextImage {
+                manufacturer_code,
+                image_type,
+                file_version,
+                image_size,
+            }),
+        }
+    }
+
+    /// Creates a response telling the client that no image is available.
+    #[must_use]
+    pub const fn no_image_available() -> Self {
+        Self {
+            status: Status::NoImageAvailable as u8,
+            image: None,
+        }
+    }
+}
+
+impl Command for QueryNextImageResponse {
+    const FRAME_TYPE: FrameType = FrameType::ClusterSpecific;
+    const DIRECTION: Direction = Direction::ServerToClient;
+    const ID: u8 = 0x02;
+}
@@ -0,0 +1,47 @@
+use le_stream::FromLeStream;
+
+use crate::zcl::{Command, Direction, FrameType, Status};
+
+/// Upgrade End Request command sent by an OTA client after a download.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq, FromLeStream)]
+pub struct UpgradeEndRequest {
+    status: u8,
+    manufacturer_code: u16,
+    image_type: u16,
+    file_version: u32,
+}
+
+impl UpgradeEndRequest {
+    /// Returns the download status reported by the client.
+    ///
+    /// # Errors
+    ///
+    /// Returns the raw status if it is not a known ZCL status.
+    pub fn status(&self) -> Result<Status, u8> {
+        Status::try_from(self.status)
+    }
+
+    /// Returns the manufacturer code of the downloaded image.
+    #[must_use]
+    pub const fn manufacturer_code(&self) -> u16 {
+        self.manufacturer_code
+    }
+
+    /// Returns the image type of the downloaded image.
+    #[must_use]
+    pub const fn image_type(&self) -> u16 {
+        self.image_type
+    }
+
+    /// Returns the file version of the downloaded image.
+    #[must_use]
+    pub const fn file_version(&self) -> u32 {
+        self.file_version
+    }
+}
+
+impl Command for UpgradeEndRequest {
+    const FRAME_TYPE: FrameType = FrameType::ClusterSpecific;
+    const DIRECTION: Direction = Direction::ClientToServer;
+    const ID: u8 = 0x06;
+}
@@ -0,0 +1,42 @@
+use le_stream::ToLeStream;
+
+use crate::zcl::{Command, Direction, FrameType};
+
+/// Upgrade End Response command telling a client when to apply its new image.
+///
+/// Times are UTC seconds. A current time of zero makes the upgrade time
+/// relative, i.e. the client applies the image after `upgrade_time` seconds.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq, ToLeStream)]
+pub struct UpgradeEndResponse {
+    manufacturer_code: u16,
+    image_type: u16,
+    file_version: u32,
+    current_time: u32,
+    upgrade_time: u32,
+}
+
+impl UpgradeEndResponse {
+    /// Creates an upgrade end response.
+    #[must_use]
+    pub const fn new(
+        manufacturer_code: u16,
+        image_type: u16,
+        file_version: u32,
+        current_time: u32,
+        upgrade_time: u32,
+    ) -> Self {
+        Self {
+            manufacturer_code,
+            image_type,
+            file_version,
+            current_time,
+            upgrade_time,
+        }
+    }
+}
+
+impl Command for UpgradeEndResponse {
+    const FRAME_TYPE: FrameType = FrameType::ClusterSpecific;
+    const DIRECTION: Direction = Direction::ServerToClient;
+    const ID: u8 = 0x07;
+}
@@ -0,0 +1,86 @@
+use std::fmt::Display;
+
+use num_derive::FromPrimitive;
+use num_traits::FromPrimitive;
+
+/// ZCL status codes.
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd, FromPrimitive)]
+#[repr(u8)]
+pub enum Status {
+    /// Operation was successful.
+    Success = 0x00,
+    /// Operation was not successful.
+    Failure = 0x01,
+    /// The sender is not authorized to carry out this command.
+    NotAuthorized = 0x7E,
+    /// The command appears to contain the wrong fields.
+    MalformedCommand = 0x80,
+    /// The specified cluster command is not supported on the device.
+    UnsupportedClusterCommand = 0x81,
+    /// The specified general ZCL command is not supported on the device.
+    UnsupportedGeneralCommand = 0x82,
+    /// A manufacturer-specific cluster command is not supported on the device.
+    UnsupportedManufacturerClusterCommand = 0x83,
+    /// A manufacturer-specific general ZCL command is not supported on the device.
+    UnsupportedManufacturerGeneralCommand = 0x84,
+    /// At least one field of the command contains an incorrect value.
+    InvalidField = 0x85,
+    /// The specified attribute does not exist on the device.
+    UnsupportedAttribute = 0x86,
+    /// Out of range error or set to a reserved value.
+    InvalidValue = 0x87,
+    /// Attempt to write a read-only attribute.
+    ReadOnly = 0x88,
+    /// An operation failed due to an insufficient amount of free space available.
+    InsufficientSpace = 0x89,
+    /// The requested information could not be found.
+    NotFound = 0x8B,
+    /// Periodic reports cannot be issued for this attribute.
+    UnreportableAttribute = 0x8C,
+    /// The data type given for an attribute is incorrect.
+    InvalidDataType = 0x8D,
+    /// The selector for an attribute is incorrect.
+    InvalidSelector = 0x8E,
+    /// The operation timed out.
+    Timeout = 0x94,
+    /// Failed case when a client or a server decides to abort the upgrade process.
+    Abort = 0x95,
+    /// Invalid OTA upgrade image.
+    InvalidImage = 0x96,
+    /// The server does not have data block available yet.
+    WaitForData = 0x97,
+    /// No OTA upgrade image available for the client.
+    NoImageAvailable = 0x98,
+    /// The client still requires more OTA upgrade image files to upgrade.
+    RequireMoreImage = 0x99,
+    /// The command has been received and is being processed.
+    NotificationPending = 0x9A,
+    /// An operation was unsuccessful due to a hardware failure.
+    HardwareFailure = 0xC0,
+    /// An operation was unsuccessful due to a software failure.
+    SoftwareFailure = 0xC1,
+    /// The cluster is not supported.
+    UnsupportedCluster = 0xC3,
+    /// The request could not be processed because a limit was reached.
+    LimitReached = 0xC4,
+}
+
+impl Display for Status {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{self:?}")
+    }
+}
+
+impl From<Status> for u8 {
+    fn from(status: Status) -> Self {
+        status as Self
+    }
+}
+
+impl TryFrom<u8> for Status {
+    type Error = u8;
+
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
+        Self::from_u8(value).ok_or(value)
+    }
+}
@@ -0,0 +1,366 @@
+use le_stream::{FromLeStream, ToLeStream};
+
+use crate::ember::Eui64;
+use crate::zcl::DataType;
+
+const INVALID_SHORT_LENGTH: u8 = 0xFF;
+const INVALID_LONG_LENGTH: u16 = 0xFFFF;
+
+/// A ZCL attribute value.
+///
+/// Integer, bitmap, and general data values of all widths are widened to 64
+/// bits, so the [`DataType`] the value was read with is needed to encode it
+/// again. Invalid string and collection lengths are read as empty values.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Value {
+    /// No data.
+    NoData,
+    /// General data of 8 to 64 bits.
+    Data(u64),
+    /// A Boolean, where `0xFF` marks an invalid value.
+    Boolean(u8),
+    /// A bitmap of 8 to 64 bits.
+    Bitmap(u64),
+    /// An unsigned integer of 8 to 64 bits.
+    Unsigned(u64),
+    /// A signed integer of 8 to 64 bits.
+    Signed(i64),
+    /// An 8-bit or 16-bit enumeration.
+    Enum(u16),
+    /// A semi-precision floating point number in its raw representation.
+    SemiPrecision(u16),
+    /// A single-precision floating point number.
+    SinglePrecision(f32),
+    /// A double-precision floating point number.
+    DoublePrecision(f64),
+    /// An octet string.
+    OctetString(Box<[u8]>),
+    /// A character string.
+    CharacterString(String),
+    /// An array, set, or bag of elements of a single data type.
+    Collection(DataType, Box<[Self]>),
+    /// A structure of elements with individual data types.
+    Structure(Box<[(DataType, Self)]>),
+    /// A time of day as hours, minutes, seconds, and hundredths.
+    TimeOfDay([u8; 4]),
+    /// A date as year since 1900, month, day of month, and day of week.
+    Date([u8; 4]),
+    /// UTC time in seconds since 2000-01-01.
+    UtcTime(u32),
+    /// A cluster ID.
+    ClusterId(u16),
+    /// An attribute ID.
+    AttributeId(u16),
+    /// A `BACnet` object identifier.
+    BacnetOid(u32),
+    /// An IEEE address.
+    IeeeAddress(Eui64),
+    /// A 128-bit security key.
+    SecurityKey([u8; 16]),
+}
+
+impl Value {
+    /// Reads a value of the given data type from a little-endian byte stream.
+    ///
+    /// Returns [`None`] if the stream ends early or the data type has no
+    /// defined encoding.
+    pub fn read<T>(data_type: DataType, bytes: &mut T) -> Option<Self>
+    where
+        T: Iterator<Item = u8>,
+    {
+        let id = u8::from(data_type);
+
+        Some(match data_type {
+            DataType::NoData => Self::NoData,
+            DataType::Data8
+            | DataType::Data16
+            | DataType::Data24
+            | DataType::Data32
+            | DataType::Data40
+            | DataType::Data48
+            | DataType::Data56
+            | DataType::Data64 => Self::Data(read_unsigned(bytes, id - 0x07)?),
+            DataType::Boolean => Self::Boolean(u8::from_le_stream(bytes)?),
+            DataType::Bitmap8
+            | DataType::Bitmap16
+            | DataType::Bitmap24
+            | DataType::Bitmap32
+            | DataType::Bitmap40
+            | DataType::Bitmap48
+            | DataType::Bitmap56
+            | DataType::Bitmap64 => Self::Bitmap(read_unsigned(bytes, id - 0x17)?),
+            DataType::Uint8
+            | DataType::Uint16
+            | DataType::Uint24
+            | DataType::Uint32
+            | DataType::Uint40
+            | DataType::Uint48
+            | DataType::Uint56
+            | DataType::Uint64 => Self::Unsigned(read_unsigned(bytes, id - 0x1F)?),
+            DataType::Int8
+            | DataType::Int16
+            | DataType::Int24
+            | DataType::Int32
+            | DataType::Int40
+            | DataType::Int48
+            | DataType::Int56
+            | DataType::Int64 => Self::Signed(read_signed(bytes, id - 0x27)?),
+            DataType::Enum8 => Self::Enum(u8::from_le_stream(bytes)?.into()),
+            DataType::Enum16 => Self::Enum(u16::from_le_stream(bytes)?),
+            DataType::SemiPrecision => Self::SemiPrecision(u16::from_le_stream(bytes)?),
+            DataType::SinglePrecision => Self::SinglePrecision(f32::from_le_stream(bytes)?),
+            DataType::DoublePrecision => Self::DoublePrecision(f64::from_le_stream(bytes)?),
+            DataType::OctetString => {
+                let length = short_length(u8::from_le_stream(&mut *bytes)?);
+                Self::OctetString(read_bytes(bytes, length)?)
+            }
+            DataType::CharacterString => {
+                let length = short_length(u8::from_le_stream(&mut *bytes)?);
+                Self::CharacterString(String::from_utf8_lossy(&read_bytes(bytes, length)?).into())
+            }
+            DataType::LongOctetString => {
+                let length = long_length(u16::from_le_stream(&mut *bytes)?);
+                Self::OctetString(read_bytes(bytes, length)?)
+            }
+            DataType::LongCharacterString => {
+                let length = long_length(u16::from_le_stream(&mut *bytes)?);
+                Self::CharacterString(String::from_utf8_lossy(&read_bytes(bytes, length)?).into())
+            }
+            DataType::Array | DataType::Set | DataType::Bag => {
+                let element_type = DataType::try_from(u8::from_le_stream(&mut *bytes)?).ok()?;
+                let count = long_length(u16::from_le_stream(&mut *bytes)?);
+                let elements = (0..count)
+                    .map(|_| Self::read(element_type, bytes))
+                    .collect::<Option<_>>()?;
+                Self::Collection(element_type, elements)
+            }
+            DataType::Structure => {
+                let count = long_length(u16::from_le_stream(&mut *bytes)?);
+                let elements = (0..count)
+                    .map(|_| {
+                        let element_type =
+                            DataType::try_from(u8::from_le_stream(&mut *bytes)?).ok()?;
+                        Some((element_type, Self::read(element_type, bytes)?))
+                    })
+                    .collect::<Option<_>>()?;
+                Self::Structure(elements)
+            }
+            DataType::TimeOfDay => Self::TimeOfDay(<[u8; 4]>::from_le_stream(bytes)?),
+            DataType::Date => Self::Date(<[u8; 4]>::from_le_stream(bytes)?),
+            DataType::UtcTime => Self::UtcTime(u32::from_le_stream(bytes)?),
+            DataType::ClusterId => Self::ClusterId(u16::from_le_stream(bytes)?),
+            DataType::AttributeId => Self::AttributeId(u16::from_le_stream(bytes)?),
+            DataType::BacnetOid => Self::BacnetOid(u32::from_le_stream(bytes)?),
+            DataType::IeeeAddress => Self::IeeeAddress(Eui64::from_le_stream(bytes)?),
+            DataType::SecurityKey => Self::SecurityKey(<[u8; 16]>::from_le_stream(bytes)?),
+            DataType::Unknown => return None,
+        })
+    }
+
+    /// Encodes the value as the given data type.
+    ///
+    /// Integer values are truncated to the width of `data_type`. Returns
+    /// [`None`] if the value does not match the data type or a string or
+    /// collection is too long for it.
+    #[must_use]
+    pub fn to_bytes(&self, data_type: DataType) -> Option<Vec<u8>> {
+        let mut bytes = Vec::new();
+        self.write(data_type, &mut bytes)?;
+        Some(bytes)
+    }
+
+    fn write(&self, data_type: DataType, bytes: &mut Vec<u8>) -> Option<()> {
+        let width = data_type.size();
+
+        match (self, data_type) {
+            (Self::NoData, DataType::NoData) => {}
+            (Self::Boolean(value), DataType::Boolean) => bytes.push(*value),
+            (Self::Data(value), _) if data_type.is_data() => {
+                bytes.extend(value.to_le_stream().take(width?));
+            }
+            (Self::Bitmap(value), _) if data_type.is_bitmap() => {
+                bytes.extend(value.to_le_stream().take(width?));
+            }
+            (Self::Unsigned(value), _) if data_type.is_unsigned() => {
+                bytes.extend(value.to_le_stream().take(width?));
+            }
+            (Self::Signed(value), _) if data_type.is_signed() => {
+                bytes.extend(value.to_le_stream().take(width?));
+            }
+            (Self::Enum(value), DataType::Enum8) => bytes.push(u8::try_from(*value).ok()?),
+            (Self::Enum(value), DataType::Enum16) => bytes.extend(value.to_le_stream()),
+            (Self::SemiPrecision(value), DataType::SemiPrecision)
+            | (Self::ClusterId(value), DataType::ClusterId)
+            | (Self::AttributeId(value), DataType::AttributeId) => {
+                bytes.extend(value.to_le_stream());
+            }
+            (Self::SinglePrecision(value), DataType::SinglePrecision) => {
+                bytes.extend(value.to_le_stream());
+            }
+            (Self::DoublePrecision(value), DataType::DoublePrecision) => {
+                bytes.extend(value.to_le_stream());
+            }
+            (Self::OctetString(value), DataType::OctetString) => {
+                write_short(bytes, value)?;
+            }
+            (Self::CharacterString(value), DataType::CharacterString) => {
+                write_short(bytes, value.as_bytes())?;
+            }
+            (Self::OctetString(value), DataType::LongOctetString) => {
+                write_long(bytes, value)?;
+            }
+            (Self::CharacterString(value), DataType::LongCharacterString) => {
+                write_long(bytes, value.as_bytes())?;
+            }
+            (Self::Collection(element_type, elements), _) if data_type.is_collection() => {
+                bytes.push((*element_type).into());
+                bytes.extend(u16::try_from(elements.len()).ok()?.to_le_stream());
+
+                for element in elements {
+                    element.write(*element_type, bytes)?;
+                }
+            }
+            (Self::Structure(elements), DataType::Structure) => {
+                bytes.extend(u16::try_from(elements.len()).ok()?.to_le_stream());
+
+                for (element_type, element) in elements {
+                    bytes.push((*element_type).into());
+                    element.write(*element_type, bytes)?;
+                }
+            }
+            (Self::TimeOfDay(value), DataType::TimeOfDay) | (Self::Date(value), DataType::Date) => {
+                bytes.extend(value);
+            }
+            (Self::UtcTime(value), DataType::UtcTime)
+            | (Self::BacnetOid(value), DataType::BacnetOid) => {
+                bytes.extend(value.to_le_stream());
+            }
+            (Self::IeeeAddress(value), DataType::IeeeAddress) => {
+                bytes.extend(value.to_le_stream());
+            }
+            (Self::SecurityKey(value), DataType::SecurityKey) => bytes.extend(value),
+            _ => return None,
+        }
+
+        Some(())
+    }
+}
+
+fn read_unsigned<T>(bytes: &mut T, width: u8) -> Option<u64>
+where
+    T: Iterator<Item = u8>,
+{
+    let mut buffer = [0; 8];
+
+    for byte in buffer.iter_mut().take(width.into()) {
+        *byte = bytes.next()?;
+    }
+
+    Some(u64::from_le_bytes(buffer))
+}
+
+fn read_signed<T>(bytes: &mut T, width: u8) -> Option<i64>
+where
+    T: Iterator<Item = u8>,
+{
+    let unused_bits = 64 - u32::from(width) * 8;
+    let value = read_unsigned(bytes, width)?.cast_signed();
+    Some((value << unused_bits) >> unused_bits)
+}
+
+fn read_bytes<T>(bytes: &mut T, length: usize) -> Option<Box<[u8]>>
+where
+    T: Iterator<Item = u8>,
+{
+    let data: Box<[u8]> = bytes.take(length).collect();
+    (data.len() == length).then_some(data)
+}
+
+fn short_length(length: u8) -> usize {
+    if length == INVALID_SHORT_LENGTH {
+        0
+    } else {
+        length.into()
+    }
+}
+
+fn long_length(length: u16) -> usize {
+    if length == INVALID_LONG_LENGTH {
+        0
+    } else {
+        length.into()
+    }
+}
+
+fn write_short(bytes: &mut Vec<u8>, data: &[u8]) -> Option<()> {
+    let length = u8::try_from(data.len())
+        .ok()
+        .filter(|length| *length != INVALID_SHORT_LENGTH)?;
+    bytes.push(length);
+    bytes.extend_from_slice(data);
+    Some(())
+}
+
+fn write_long(bytes: &mut Vec<u8>, data: &[u8]) -> Option<()> {
+    let length = u16::try_from(data.len())
+        .ok()
+        .filter(|length| *length != INVALID_LONG_LENGTH)?;
+    bytes.extend(length.to_le_stream());
+    bytes.extend_from_slice(data);
+    Some(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn round_trip(data_type: DataType, bytes: &[u8]) -> Value {
+        let value = Value::read(data_type, &mut bytes.iter().copied()).expect("value is valid");
+        assert_eq!(value.to_bytes(data_type).as_deref(), Some(bytes));
+        value
+    }
+
+    #[test]
+    fn reads_integers_of_all_widths() {
+        assert_eq!(
+            round_trip(DataType::Uint24, &[0x01, 0x02, 0x03]),
+            Value::Unsigned(0x03_0201)
+        );
+        assert_eq!(
+            round_trip(DataType::Int16, &[0xFE, 0xFF]),
+            Value::Signed(-2)
+        );
+        assert_eq!(
+            round_trip(DataType::Int24, &[0x00, 0x00, 0x80]),
+            Value::Signed(-0x80_0000)
+        );
+    }
+
+    #[test]
+    fn reads_strings_and_collections() {
+        assert_eq!(
+            round_trip(DataType::CharacterString, &[0x03, b'a', b'b', b'c']),
+            Value::CharacterString("abc".into())
+        );
+        assert_eq!(
+            round_trip(DataType::Array, &[0x20, 0x02, 0x00, 0x05, 0x06]),
+            Value::Collection(
+                DataType::Uint8,
+                [Value::Unsigned(5), Value::Unsigned(6)].into()
+            )
+        );
+    }
+
+    #[test]
+    fn reads_invalid_string_as_empty() {
+        assert_eq!(
+            Value::read(DataType::OctetString, &mut std::iter::once(0xFF)),
+            Some(Value::OctetString(Box::default()))
+        );
+    }
+
+    #[test]
+    fn rejects_mismatched_data_type() {
+        assert_eq!(Value::Signed(1).to_bytes(DataType::Uint8), None);
+    }
+}
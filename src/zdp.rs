@@ -0,0 +1,73 @@
+//! Host-side Zigbee Device Profile (ZDP) frames.
+//!
+//! ZDP messages use profile `0x0000` and endpoint zero. Their APS payload
+//! starts with a transaction sequence number followed by the command-specific
+//! fields. [`Frame`] models that layout, while the command types model the ZDP
+//! requests and responses used by the high-level [`Ncp`](crate::Ncp) services.
+//! Responses use the cluster ID of their request with the high bit set.
+
+pub use self::active_endpoints_request::ActiveEndpointsRequest;
+pub use self::active_endpoints_response::ActiveEndpointsResponse;
+pub use self::bind_request::BindRequest;
+pub use self::bind_response::BindResponse;
+pub use self::command::Command;
+pub use self::device_announce::DeviceAnnounce;
+pub use self::device_type::DeviceType;
+pub use self::frame::Frame;
+pub use self::match_descriptor_request::MatchDescriptorRequest;
+pub use self::match_descriptor_response::MatchDescriptorResponse;
+pub use self::mgmt_lqi_request::MgmtLqiRequest;
+pub use self::mgmt_lqi_response::{MgmtLqiResponse, NeighborRecord};
+pub use self::mgmt_nwk_update_notify::MgmtNwkUpdateNotify;
+pub use self::mgmt_nwk_update_request::MgmtNwkUpdateRequest;
+pub use self::mgmt_permit_joining_request::MgmtPermitJoiningRequest;
+pub use self::mgmt_rtg_request::MgmtRtgRequest;
+pub use self::mgmt_rtg_response::{MgmtRtgResponse, RouteStatus, RoutingRecord};
+pub use self::node_descriptor::NodeDescriptor;
+pub use self::node_descriptor_request::NodeDescriptorRequest;
+pub use self::node_descriptor_response::NodeDescriptorResponse;
+pub use self::nwk_addr_request::NwkAddrRequest;
+pub use self::nwk_addr_response::NwkAddrResponse;
+pub use self::relationship::Relationship;
+pub use self::simple_descriptor::SimpleDescriptor;
+pub use self::simple_descriptor_request::SimpleDescriptorRequest;
+pub use self::simple_descriptor_response::SimpleDescriptorResponse;
+pub use self::status::Status;
+pub use self::unbind_request::UnbindRequest;
+pub use self::unbind_response::UnbindResponse;
+
+mod active_endpoints_request;
+mod active_endpoints_response;
+mod bind_request;
+mod bind_response;
+mod command;
+mod device_announce;
+mod device_type;
+mod frame;
+mod match_descriptor_request;
+mod match_descriptor_response;
+mod mgmt_lqi_request;
+mod mgmt_lqi_response;
+mod mgmt_nwk_update_notify;
+mod mgmt_nwk_update_request;
+mod mgmt_permit_joining_request;
+mod mgmt_rtg_request;
+mod mgmt_rtg_response;
+mod node_descriptor;
+mod node_descriptor_request;
+mod node_descriptor_response;
+mod nwk_addr_request;
+mod nwk_addr_response;
+mod relationship;
+mod simple_descriptor;
+mod simple_descriptor_request;
+mod simple_descriptor_response;
+mod status;
+mod unbind_request;
+mod unbind_response;
+
+/// The ZDP profile ID.
+pub const PROFILE_ID: u16 = 0x0000;
+
+/// The bit distinguishing response cluster IDs from request cluster IDs.
+pub const RESPONSE_BIT: u16 = 0x8000;
@@ -0,0 +1,22 @@
+use le_stream::ToLeStream;
+
+use crate::ember::NodeId;
+use crate::zdp::Command;
+
+/// Active Endpoints Request listing a device's application endpoints.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq, ToLeStream)]
+pub struct ActiveEndpointsRequest {
+    node_id: NodeId,
+}
+
+impl ActiveEndpointsRequest {
+    /// Creates a request for the active endpoints of `node_id`.
+    #[must_use]
+    pub const fn new(node_id: NodeId) -> Self {
+        Self { node_id }
+    }
+}
+
+impl Command for ActiveEndpointsRequest {
+    const CLUSTER_ID: u16 = 0x0005;
+}
@@ -0,0 +1,63 @@
+use le_stream::FromLeStream;
+
+use crate::ember::NodeId;
+use crate::types::ByteSizedVec;
+use crate::zdp::{ActiveEndpointsRequest, Command, RESPONSE_BIT, Status};
+
+/// Active Endpoints Response listing a device's application endpoints.
+///
+/// Responses with a status other than [`Status::Success`] carry no endpoints.
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub struct ActiveEndpointsResponse {
+    status: u8,
+    node_id: NodeId,
+    endpoints: ByteSizedVec<u8>,
+}
+
+impl ActiveEndpointsResponse {
+    /// Returns the response status.
+    ///
+    /// # Errors
+    ///
+    /// Returns the raw status if it is not a known ZDP status.
+    pub fn status(&self) -> Result<Status, u8> {
+        Status::try_from(self.status)
+    }
+
+    /// Returns the network address of the queried device.
+    #[must_use]
+    pub const fn node_id(&self) -> NodeId {
+        self.node_id
+    }
+
+    /// Returns the active endpoints.
+    #[must_use]
+    pub fn endpoints(&self) -> &[u8] {
+        &self.endpoints
+    }
+}
+
+impl Command for ActiveEndpointsResponse {
+    const CLUSTER_ID: u16 = ActiveEndpointsRequest::CLUSTER_ID | RESPONSE_BIT;
+}
+
+impl FromLeStream for ActiveEndpointsResponse {
+    fn from_le_stream<T>(mut bytes: T) -> Option<Self>
+    where
+        T: Iterator<Item = u8>,
+    {
+        let status = u8::from_le_stream(&mut bytes)?;
+        let node_id = NodeId::from_le_stream(&mut bytes)?;
+        let endpoints = if status == u8::from(Status::Success) {
+            ByteSizedVec::from_le_stream(&mut bytes)?
+        } else {
+            ByteSizedVec::new()
+        };
+
+        Some(Self {
+            status,
+            node_id,
+            endpoints,
+        })
+    }
+}
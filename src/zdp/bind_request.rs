@@ -0,0 +1,98 @@
+use le_stream::ToLeStream;
+
+use crate::ember::Eui64;
+use crate::ember::binding::{TableEntry, Type};
+use crate::zdp::Command;
+
+/// Destination address mode of a group binding.
+const GROUP_ADDRESS: u8 = 0x01;
+/// Destination address mode of a unicast binding.
+const IEEE_ADDRESS: u8 = 0x03;
+
+/// Bind Request creating a binding on a remote device.
+///
+/// The binding is described by the same [`TableEntry`] as the NCP's own
+/// binding table, with the local endpoint being the endpoint on the device
+/// that holds the binding.
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub struct BindRequest {
+    payload: Box<[u8]>,
+}
+
+impl BindRequest {
+    /// Creates a request adding `entry` to the binding table of the device with the IEEE address `source`.
+    ///
+    /// Returns `None` if the entry is unused or has an invalid type.
+    #[must_use]
+    pub fn new(source: Eui64, entry: &TableEntry) -> Option<Self> {
+        encode(source, entry).map(|payload| Self { payload })
+    }
+}
+
+impl Command for BindRequest {
+    const CLUSTER_ID: u16 = 0x0021;
+}
+
+impl ToLeStream for BindRequest {
+    type Iter = std::vec::IntoIter<u8>;
+
+    fn to_le_stream(self) -> Self::Iter {
+        self.payload.into_vec().into_iter()
+    }
+}
+
+/// Encodes the binding fields shared by Bind and Unbind Requests.
+pub fn encode(source: Eui64, entry: &TableEntry) -> Option<Box<[u8]>> {
+    let mut payload = Vec::new();
+    payload.extend(source.to_le_stream());
+    payload.push(entry.local());
+    payload.extend(entry.cluster_id().to_le_stream());
+
+    match entry.typ() {
+        Ok(Type::Unicast | Type::ManyToOne) => {
+            payload.push(IEEE_ADDRESS);
+            payload.extend(entry.identifier().to_le_stream());
+            payload.push(entry.remote());
+        }
+        Ok(Type::Multicast) => {
+            payload.push(GROUP_ADDRESS);
+            payload.extend(entry.identifier().to_le_stream().take(2));
+        }
+        Ok(Type::Unused) | Err(_) => return None,
+    }
+
+    Some(payload.into_boxed_slice())
+}
+
+#[cfg(test)]
+mod tests {
+    use le_stream::ToLeStream;
+
+    use super::BindRequest;
+    use crate::ember::Eui64;
+    use crate::ember::binding::{TableEntry, Type};
+
+    const SOURCE: Eui64 = Eui64::new(0x00, 0x0B, 0x57, 0xFF, 0xFE, 0x01, 0x02, 0x03);
+
+    #[test]
+    fn group_binding_carries_group_address() {
+        let group = Eui64::new(0, 0, 0, 0, 0, 0, 0x12, 0x34);
+        let entry = TableEntry::new(Type::Multicast, 1, 0x0006, 0, group, 0);
+        let payload: Vec<u8> = BindRequest::new(SOURCE, &entry)
+            .expect("multicast bindings are valid")
+            .to_le_stream()
+            .collect();
+        assert_eq!(
+            payload,
+            [
+                0x03, 0x02, 0x01, 0xFE, 0xFF, 0x57, 0x0B, 0x00, 0x01, 0x06, 0x00, 0x01, 0x34, 0x12
+            ]
+        );
+    }
+
+    #[test]
+    fn unused_bindings_are_rejected() {
+        let entry = TableEntry::new(Type::Unused, 1, 0x0006, 1, SOURCE, 0);
+        assert!(BindRequest::new(SOURCE, &entry).is_none());
+    }
+}
@@ -0,0 +1,24 @@
+use le_stream::FromLeStream;
+
+use crate::zdp::{BindRequest, Command, RESPONSE_BIT, Status};
+
+/// Bind Response reporting the result of a [`BindRequest`].
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq, FromLeStream)]
+pub struct BindResponse {
+    status: u8,
+}
+
+impl BindResponse {
+    /// Returns the response status.
+    ///
+    /// # Errors
+    ///
+    /// Returns the raw status if it is not a known ZDP status.
+    pub fn status(&self) -> Result<Status, u8> {
+        Status::try_from(self.status)
+    }
+}
+
+impl Command for BindResponse {
+    const CLUSTER_ID: u16 = BindRequest::CLUSTER_ID | RESPONSE_BIT;
+}
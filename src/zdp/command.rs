@@ -0,0 +1,9 @@
+/// A typed ZDP command payload.
+///
+/// The associated constant identifies the command's APS cluster ID, so
+/// [`Frame`](crate::zdp::Frame) payloads can be sent and recognized without
+/// repeating the cluster ID at every call site.
+pub trait Command {
+    /// The APS cluster ID of the command.
+    const CLUSTER_ID: u16;
+}
@@ -0,0 +1,36 @@
+use le_stream::{FromLeStream, ToLeStream};
+
+use crate::ember::{Eui64, NodeId};
+use crate::zdp::Command;
+
+/// Device announcement broadcast by a device after joining or rejoining.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq, FromLeStream, ToLeStream)]
+pub struct DeviceAnnounce {
+    node_id: NodeId,
+    ieee_address: Eui64,
+    capability: u8,
+}
+
+impl DeviceAnnounce {
+    /// Returns the network address of the announced device.
+    #[must_use]
+    pub const fn node_id(&self) -> NodeId {
+        self.node_id
+    }
+
+    /// Returns the IEEE address of the announced device.
+    #[must_use]
+    pub const fn ieee_address(&self) -> Eui64 {
+        self.ieee_address
+    }
+
+    /// Returns the MAC capability flags of the announced device.
+    #[must_use]
+    pub const fn capability(&self) -> u8 {
+        self.capability
+    }
+}
+
+impl Command for DeviceAnnounce {
+    const CLUSTER_ID: u16 = 0x0013;
+}
@@ -0,0 +1,64 @@
+use std::fmt::Display;
+
+use num_derive::FromPrimitive;
+use num_traits::FromPrimitive;
+
+use crate::ember::node;
+
+/// Device type of a neighbor reported in a Mgmt LQI response.
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd, FromPrimitive)]
+#[repr(u8)]
+pub enum DeviceType {
+    /// Zigbee coordinator.
+    Coordinator = 0x00,
+    /// Zigbee router.
+    Router = 0x01,
+    /// Zigbee end device.
+    EndDevice = 0x02,
+    /// The device type is unknown.
+    Unknown = 0x03,
+}
+
+impl DeviceType {
+    /// Returns `true` if devices of this type relay messages.
+    #[must_use]
+    pub const fn is_router(self) -> bool {
+        matches!(self, Self::Coordinator | Self::Router)
+    }
+}
+
+impl Display for DeviceType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Coordinator => write!(f, "Coordinator"),
+            Self::Router => write!(f, "Router"),
+            Self::EndDevice => write!(f, "End Device"),
+            Self::Unknown => write!(f, "Unknown"),
+        }
+    }
+}
+
+impl From<node::Type> for DeviceType {
+    fn from(typ: node::Type) -> Self {
+        match typ {
+            node::Type::Coordinator => Self::Coordinator,
+            node::Type::Router => Self::Router,
+            node::Type::EndDevice | node::Type::SleepyEndDevice => Self::EndDevice,
+            node::Type::UnknownDevice => Self::Unknown,
+        }
+    }
+}
+
+impl From<DeviceType> for u8 {
+    fn from(device_type: DeviceType) -> Self {
+        device_type as Self
+    }
+}
+
+impl TryFrom<u8> for DeviceType {
+    type Error = u8;
+
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
+        Self::from_u8(value).ok_or(value)
+    }
+}
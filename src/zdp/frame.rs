@@ -0,0 +1,68 @@
+use le_stream::{FromLeStream, ToLeStream};
+
+use crate::zdp::Command;
+
+/// A ZDP frame consisting of a transaction sequence number and a command.
+#[derive(Clone, Debug, Eq, Hash, PartialEq, FromLeStream, ToLeStream)]
+pub struct Frame<T> {
+    transaction_sequence: u8,
+    command: T,
+}
+
+impl<T> Frame<T> {
+    /// Creates a frame carrying a command.
+    #[must_use]
+    pub const fn new(transaction_sequence: u8, command: T) -> Self {
+        Self {
+            transaction_sequence,
+            command,
+        }
+    }
+
+    /// Returns the transaction sequence number.
+    #[must_use]
+    pub const fn transaction_sequence(&self) -> u8 {
+        self.transaction_sequence
+    }
+
+    /// Returns the command.
+    #[must_use]
+    pub const fn command(&self) -> &T {
+        &self.command
+    }
+
+    /// Consumes the frame and returns the command.
+    #[must_use]
+    pub fn into_command(self) -> T {
+        self.command
+    }
+}
+
+impl<T> Frame<T>
+where
+    T: Command + FromLeStream,
+{
+    /// Parses a frame from the APS payload of a message with the given cluster ID.
+    ///
+    /// Returns [`None`] if the cluster ID is not that of `T` or the payload is
+    /// too short.
+    #[must_use]
+    pub fn parse(cluster_id: u16, bytes: &[u8]) -> Option<Self> {
+        if cluster_id == T::CLUSTER_ID {
+            Self::from_le_stream(bytes.iter().copied())
+        } else {
+            None
+        }
+    }
+}
+
+impl<T> Frame<T>
+where
+    T: ToLeStream,
+{
+    /// Serializes the frame into the bytes of an APS payload.
+    #[must_use]
+    pub fn to_bytes(self) -> Vec<u8> {
+        self.to_le_stream().collect()
+    }
+}
@@ -0,0 +1,37 @@
+use le_stream::ToLeStream;
+
+use crate::ember::NodeId;
+use crate::types::ByteSizedVec;
+use crate::zdp::Command;
+
+/// Match Descriptor Request searching a device for endpoints with given clusters.
+#[derive(Clone, Debug, Eq, Hash, PartialEq, ToLeStream)]
+pub struct MatchDescriptorRequest {
+    node_id: NodeId,
+    profile_id: u16,
+    input_clusters: ByteSizedVec<u16>,
+    output_clusters: ByteSizedVec<u16>,
+}
+
+impl MatchDescriptorRequest {
+    /// Creates a request for endpoints of `node_id` that implement `profile_id`
+    /// and any of the given input or output clusters.
+    #[must_use]
+    pub const fn new(
+        node_id: NodeId,
+        profile_id: u16,
+        input_clusters: ByteSizedVec<u16>,
+        output_clusters: ByteSizedVec<u16>,
+    ) -> Self {
+        Self {
+            node_id,
+            profile_id,
+            input_clusters,
+            output_clusters,
+        }
+    }
+}
+
+impl Command for MatchDescriptorRequest {
+    const CLUSTER_ID: u16 = 0x0006;
+}
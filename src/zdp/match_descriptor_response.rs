@@ -0,0 +1,40 @@
+use le_stream::FromLeStream;
+
+use crate::ember::NodeId;
+use crate::types::ByteSizedVec;
+use crate::zdp::{Command, MatchDescriptorRequest, RESPONSE_BIT, Status};
+
+/// Match Descriptor Response listing the matching endpoints of a device.
+#[derive(Clone, Debug, Eq, Hash, PartialEq, FromLeStream)]
+pub struct MatchDescriptorResponse {
+    status: u8,
+    node_id: NodeId,
+    endpoints: ByteSizedVec<u8>,
+}
+
+impl MatchDescriptorResponse {
+    /// Returns the response status.
+    ///
+    /// # Errors
+    ///
+    /// Returns the raw status if it is not a known ZDP status.
+    pub fn status(&self) -> Result<Status, u8> {
+        Status::try_from(self.status)
+    }
+
+    /// Returns the network address of the queried device.
+    #[must_use]
+    pub const fn node_id(&self) -> NodeId {
+        self.node_id
+    }
+
+    /// Returns the matching endpoints.
+    #[must_use]
+    pub fn endpoints(&self) -> &[u8] {
+        &self.endpoints
+    }
+}
+
+impl Command for MatchDescriptorResponse {
+    const CLUSTER_ID: u16 = MatchDescriptorRequest::CLUSTER_ID | RESPONSE_BIT;
+}
@@ -0,0 +1,21 @@
+use le_stream::ToLeStream;
+
+use crate::zdp::Command;
+
+/// Mgmt LQI Request reading a device's neighbor table.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq, ToLeStream)]
+pub struct MgmtLqiRequest {
+    start_index: u8,
+}
+
+impl MgmtLqiRequest {
+    /// Creates a request for the neighbor table records starting at `start_index`.
+    #[must_use]
+    pub const fn new(start_index: u8) -> Self {
+        Self { start_index }
+    }
+}
+
+impl Command for MgmtLqiRequest {
+    const CLUSTER_ID: u16 = 0x0031;
+}
@@ -0,0 +1,213 @@
+use le_stream::FromLeStream;
+
+use crate::ember::{Eui64, NodeId};
+use crate::types::ByteSizedVec;
+use crate::zdp::{Command, DeviceType, MgmtLqiRequest, RESPONSE_BIT, Relationship, Status};
+
+const DEVICE_TYPE_MASK: u8 = 0b0000_0011;
+const RX_ON_WHEN_IDLE_MASK: u8 = 0b0000_1100;
+const RX_ON_WHEN_IDLE_SHIFT: u8 = 2;
+const RELATIONSHIP_MASK: u8 = 0b0111_0000;
+const RELATIONSHIP_SHIFT: u8 = 4;
+const PERMIT_JOINING_MASK: u8 = 0b0000_0011;
+
+/// Mgmt LQI Response carrying a page of a device's neighbor table.
+///
+/// Responses with a status other than [`Status::Success`] carry no table.
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub struct MgmtLqiResponse {
+    status: u8,
+    neighbor_table_entries: u8,
+    start_index: u8,
+    records: ByteSizedVec<NeighborRecord>,
+}
+
+impl MgmtLqiResponse {
+    /// Returns the response status.
+    ///
+    /// # Errors
+    ///
+    /// Returns the raw status if it is not a known ZDP status.
+    pub fn status(&self) -> Result<Status, u8> {
+        Status::try_from(self.status)
+    }
+
+    /// Returns the total number of entries in the device's neighbor table.
+    #[must_use]
+    pub const fn neighbor_table_entries(&self) -> u8 {
+        self.neighbor_table_entries
+    }
+
+    /// Returns the index of the first record in the response.
+    #[must_use]
+    pub const fn start_index(&self) -> u8 {
+        self.start_index
+    }
+
+    /// Returns the neighbor table records.
+    #[must_use]
+    pub fn records(&self) -> &[NeighborRecord] {
+        &self.records
+    }
+}
+
+impl Command for MgmtLqiResponse {
+    const CLUSTER_ID: u16 = MgmtLqiRequest::CLUSTER_ID | RESPONSE_BIT;
+}
+
+impl FromLeStream for MgmtLqiResponse {
+    fn from_le_stream<T>(mut bytes: T) -> Option<Self>
+    where
+        T: Iterator<Item = u8>,
+    {
+        let status = u8::from_le_stream(&mut bytes)?;
+
+        if status != u8::from(Status::Success) {
+            return Some(Self {
+                status,
+                neighbor_table_entries: 0,
+                start_index: 0,
+                records: ByteSizedVec::new(),
+            });
+        }
+
+        Some(Self {
+            status,
+            neighbor_table_entries: u8::from_le_stream(&mut bytes)?,
+            start_index: u8::from_le_stream(&mut bytes)?,
+            records: ByteSizedVec::from_le_stream(&mut bytes)?,
+        })
+    }
+}
+
+/// A neighbor table record of a Mgmt LQI response.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq, FromLeStream)]
+pub struct NeighborRecord {
+    extended_pan_id: Eui64,
+    ieee_address: Eui64,
+    node_id: NodeId,
+    flags: u8,
+    permit_joining: u8,
+    depth: u8,
+    lqi: u8,
+}
+
+impl NeighborRecord {
+    /// Returns the extended PAN ID of the neighbor's network.
+    #[must_use]
+    pub const fn extended_pan_id(&self) -> Eui64 {
+        self.extended_pan_id
+    }
+
+    /// Returns the IEEE address of the neighbor.
+    #[must_use]
+    pub const fn ieee_address(&self) -> Eui64 {
+        self.ieee_address
+    }
+
+    /// Returns the network address of the neighbor.
+    #[must_use]
+    pub const fn node_id(&self) -> NodeId {
+        self.node_id
+    }
+
+    /// Returns the device type of the neighbor.
+    ///
+    /// # Errors
+    ///
+    /// Returns the raw device type if it is invalid.
+    pub fn device_type(&self) -> Result<DeviceType, u8> {
+        DeviceType::try_from(self.flags & DEVICE_TYPE_MASK)
+    }
+
+    /// Returns whether the neighbor's receiver is on when idle.
+    ///
+    /// Returns [`None`] if this is unknown.
+    #[must_use]
+    pub const fn rx_on_when_idle(&self) -> Option<bool> {
+        match (self.flags & RX_ON_WHEN_IDLE_MASK) >> RX_ON_WHEN_IDLE_SHIFT {
+            0 => Some(false),
+            1 => Some(true),
+            _ => None,
+        }
+    }
+
+    /// Returns the relationship of the neighbor to the reporting device.
+    ///
+    /// # Errors
+    ///
+    /// Returns the raw relationship if it is invalid.
+    pub fn relationship(&self) -> Result<Relationship, u8> {
+        Relationship::try_from((self.flags & RELATIONSHIP_MASK) >> RELATIONSHIP_SHIFT)
+    }
+
+    /// Returns whether the neighbor accepts join requests.
+    ///
+    /// Returns [`None`] if this is unknown.
+    #[must_use]
+    pub const fn permit_joining(&self) -> Option<bool> {
+        match self.permit_joining & PERMIT_JOINING_MASK {
+            0 => Some(false),
+            1 => Some(true),
+            _ => None,
+        }
+    }
+
+    /// Returns the tree depth of the neighbor.
+    #[must_use]
+    pub const fn depth(&self) -> u8 {
+        self.depth
+    }
+
+    /// Returns the estimated link quality for frames received from the neighbor.
+    #[must_use]
+    pub const fn lqi(&self) -> u8 {
+        self.lqi
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use le_stream::FromLeStream;
+
+    use super::{DeviceType, MgmtLqiResponse, Relationship, Status};
+    use crate::ember::Eui64;
+
+    #[test]
+    fn parses_neighbor_records() {
+        let bytes = [
+            0x00, 0x03, 0x01, 0x01, // status, entries, start index, count
+            0x08, 0x07, 0x06, 0x05, 0x04, 0x03, 0x02, 0x01, // extended PAN ID
+            0x18, 0x17, 0x16, 0x15, 0x14, 0x13, 0x12, 0x11, // IEEE address
+            0x34, 0x12, // node ID
+            0x25, // router, rx on when idle, sibling
+            0x00, 0x02, 0xA0, // permit joining, depth, LQI
+        ];
+        let response = MgmtLqiResponse::from_le_slice(&bytes).expect("response should parse");
+        assert_eq!(response.status(), Ok(Status::Success));
+        assert_eq!(response.neighbor_table_entries(), 3);
+        assert_eq!(response.start_index(), 1);
+
+        let [record] = response.records() else {
+            panic!("expected one record");
+        };
+        assert_eq!(
+            record.ieee_address(),
+            Eui64::new(0x11, 0x12, 0x13, 0x14, 0x15, 0x16, 0x17, 0x18)
+        );
+        assert_eq!(record.node_id(), 0x1234);
+        assert_eq!(record.device_type(), Ok(DeviceType::Router));
+        assert_eq!(record.rx_on_when_idle(), Some(true));
+        assert_eq!(record.relationship(), Ok(Relationship::Sibling));
+        assert_eq!(record.permit_joining(), Some(false));
+        assert_eq!(record.depth(), 2);
+        assert_eq!(record.lqi(), 0xA0);
+    }
+
+    #[test]
+    fn parses_failed_response_without_table() {
+        let response = MgmtLqiResponse::from_le_slice(&[0x84]).expect("response should parse");
+        assert_eq!(response.status(), Ok(Status::NotSupported));
+        assert!(response.records().is_empty());
+    }
+}
@@ -0,0 +1,115 @@
+use le_stream::FromLeStream;
+
+use crate::types::ByteSizedVec;
+use crate::zdp::{Command, MgmtNwkUpdateRequest, RESPONSE_BIT, Status};
+
+/// Mgmt NWK Update Notify reporting the result of a remote energy scan.
+///
+/// Responses with a status other than [`Status::Success`] carry no scan results.
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub struct MgmtNwkUpdateNotify {
+    status: u8,
+    scanned_channels: u32,
+    total_transmissions: u16,
+    transmission_failures: u16,
+    energy_values: ByteSizedVec<u8>,
+}
+
+impl MgmtNwkUpdateNotify {
+    /// Returns the response status.
+    ///
+    /// # Errors
+    ///
+    /// Returns the raw status if it is not a known ZDP status.
+    pub fn status(&self) -> Result<Status, u8> {
+        Status::try_from(self.status)
+    }
+
+    /// Returns the mask of the scanned channels.
+    #[must_use]
+    pub const fn scanned_channels(&self) -> u32 {
+        self.scanned_channels
+    }
+
+    /// Returns the number of transmissions reported by the device.
+    #[must_use]
+    pub const fn total_transmissions(&self) -> u16 {
+        self.total_transmissions
+    }
+
+    /// Returns the number of failed transmissions reported by the device.
+    #[must_use]
+    pub const fn transmission_failures(&self) -> u16 {
+        self.transmission_failures
+    }
+
+    /// Returns the measured energy of each scanned channel in ascending channel order.
+    #[must_use]
+    pub fn energy_values(&self) -> &[u8] {
+        &self.energy_values
+    }
+
+    /// Returns the scanned channels with their measured energy.
+    pub fn channel_energies(&self) -> impl Iterator<Item = (u8, u8)> + '_ {
+        (0..u32::BITS)
+            .filter(|channel| self.scanned_channels & (1 << channel) != 0)
+            .filter_map(|channel| u8::try_from(channel).ok())
+            .zip(self.energy_values.iter().copied())
+    }
+}
+
+impl Command for MgmtNwkUpdateNotify {
+    const CLUSTER_ID: u16 = MgmtNwkUpdateRequest::CLUSTER_ID | RESPONSE_BIT;
+}
+
+impl FromLeStream for MgmtNwkUpdateNotify {
+    fn from_le_stream<T>(mut bytes: T) -> Option<Self>
+    where
+        T: Iterator<Item = u8>,
+    {
+        let status = u8::from_le_stream(&mut bytes)?;
+
+        if status != u8::from(Status::Success) {
+            return Some(Self {
+                status,
+                scanned_channels: 0,
+                total_transmissions: 0,
+                transmission_failures: 0,
+                energy_values: ByteSizedVec::new(),
+            });
+        }
+
+        Some(Self {
+            status,
+            scanned_channels: u32::from_le_stream(&mut bytes)?,
+            total_transmissions: u16::from_le_stream(&mut bytes)?,
+            transmission_failures: u16::from_le_stream(&mut bytes)?,
+            energy_values: ByteSizedVec::from_le_stream(&mut bytes)?,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use le_stream::FromLeStream;
+
+    use super::{MgmtNwkUpdateNotify, Status};
+
+    #[test]
+    fn parses_channel_energies() {
+        let bytes = [
+            0x00, // status
+            0x00, 0x18, 0x10, 0x00, // channels 11, 12 and 20
+            0x10, 0x00, 0x02, 0x00, // transmissions, failures
+            0x03, 0x40, 0x80, 0xC0, // count, energy values
+        ];
+        let notify = MgmtNwkUpdateNotify::from_le_slice(&bytes).expect("notify should parse");
+        assert_eq!(notify.status(), Ok(Status::Success));
+        assert_eq!(notify.total_transmissions(), 16);
+        assert_eq!(notify.transmission_failures(), 2);
+        assert_eq!(
+            notify.channel_energies().collect::<Vec<_>>(),
+            [(11, 0x40), (12, 0x80), (20, 0xC0)]
+        );
+    }
+}
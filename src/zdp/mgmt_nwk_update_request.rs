@@ -0,0 +1,45 @@
+use le_stream::ToLeStream;
+
+use crate::zdp::Command;
+
+/// Scan duration value requesting a channel change.
+const CHANGE_CHANNEL: u8 = 0xFE;
+
+/// Mgmt NWK Update Request moving devices to another channel.
+///
+/// Energy scans, the other use of this request, are sent by the NCP through
+/// [`Networking::energy_scan_request`](crate::Networking::energy_scan_request).
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq, ToLeStream)]
+pub struct MgmtNwkUpdateRequest {
+    scan_channels: u32,
+    scan_duration: u8,
+    nwk_update_id: u8,
+}
+
+impl MgmtNwkUpdateRequest {
+    /// Creates a request to move to `channel` with the given network update ID.
+    #[must_use]
+    pub const fn change_channel(channel: u8, nwk_update_id: u8) -> Self {
+        Self {
+            scan_channels: 1 << channel,
+            scan_duration: CHANGE_CHANNEL,
+            nwk_update_id,
+        }
+    }
+
+    /// Returns the channel mask containing the new channel.
+    #[must_use]
+    pub const fn scan_channels(&self) -> u32 {
+        self.scan_channels
+    }
+
+    /// Returns the network update ID devices adopt with the new channel.
+    #[must_use]
+    pub const fn nwk_update_id(&self) -> u8 {
+        self.nwk_update_id
+    }
+}
+
+impl Command for MgmtNwkUpdateRequest {
+    const CLUSTER_ID: u16 = 0x0038;
+}
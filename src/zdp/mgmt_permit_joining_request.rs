@@ -0,0 +1,36 @@
+use le_stream::ToLeStream;
+
+use crate::zdp::Command;
+
+/// Trust center significance required by Zigbee PRO 2007 and later.
+const TC_SIGNIFICANCE: u8 = 0x01;
+
+/// Mgmt Permit Joining Request opening or closing a router for joining.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq, ToLeStream)]
+pub struct MgmtPermitJoiningRequest {
+    permit_duration: u8,
+    tc_significance: u8,
+}
+
+impl MgmtPermitJoiningRequest {
+    /// Creates a request permitting joins for `permit_duration` seconds.
+    ///
+    /// A duration of zero closes the router and `0xFF` opens it indefinitely.
+    #[must_use]
+    pub const fn new(permit_duration: u8) -> Self {
+        Self {
+            permit_duration,
+            tc_significance: TC_SIGNIFICANCE,
+        }
+    }
+
+    /// Returns the duration in seconds for which joining is permitted.
+    #[must_use]
+    pub const fn permit_duration(&self) -> u8 {
+        self.permit_duration
+    }
+}
+
+impl Command for MgmtPermitJoiningRequest {
+    const CLUSTER_ID: u16 = 0x0036;
+}
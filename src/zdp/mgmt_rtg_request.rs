@@ -0,0 +1,21 @@
+use le_stream::ToLeStream;
+
+use crate::zdp::Command;
+
+/// Mgmt Routing Request reading a device's routing table.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq, ToLeStream)]
+pub struct MgmtRtgRequest {
+    start_index: u8,
+}
+
+impl MgmtRtgRequest {
+    /// Creates a request for the routing table records starting at `start_index`.
+    #[must_use]
+    pub const fn new(start_index: u8) -> Self {
+        Self { start_index }
+    }
+}
+
+impl Command for MgmtRtgRequest {
+    const CLUSTER_ID: u16 = 0x0032;
+}
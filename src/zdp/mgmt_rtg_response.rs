@@ -0,0 +1,160 @@
+use le_stream::FromLeStream;
+use num_derive::FromPrimitive;
+use num_traits::FromPrimitive;
+
+use crate::ember::NodeId;
+use crate::types::ByteSizedVec;
+use crate::zdp::{Command, MgmtRtgRequest, RESPONSE_BIT, Status};
+
+const STATUS_MASK: u8 = 0b0000_0111;
+const MEMORY_CONSTRAINED: u8 = 0b0000_1000;
+const MANY_TO_ONE: u8 = 0b0001_0000;
+const ROUTE_RECORD_REQUIRED: u8 = 0b0010_0000;
+
+/// Mgmt Routing Response carrying a page of a device's routing table.
+///
+/// Responses with a status other than [`Status::Success`] carry no table.
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub struct MgmtRtgResponse {
+    status: u8,
+    routing_table_entries: u8,
+    start_index: u8,
+    records: ByteSizedVec<RoutingRecord>,
+}
+
+impl MgmtRtgResponse {
+    /// Returns the response status.
+    ///
+    /// # Errors
+    ///
+    /// Returns the raw status if it is not a known ZDP status.
+    pub fn status(&self) -> Result<Status, u8> {
+        Status::try_from(self.status)
+    }
+
+    /// Returns the total number of entries in the device's routing table.
+    #[must_use]
+    pub const fn routing_table_entries(&self) -> u8 {
+        self.routing_table_entries
+    }
+
+    /// Returns the index of the first record in the response.
+    #[must_use]
+    pub const fn start_index(&self) -> u8 {
+        self.start_index
+    }
+
+    /// Returns the routing table records.
+    #[must_use]
+    pub fn records(&self) -> &[RoutingRecord] {
+        &self.records
+    }
+}
+
+impl Command for MgmtRtgResponse {
+    const CLUSTER_ID: u16 = MgmtRtgRequest::CLUSTER_ID | RESPONSE_BIT;
+}
+
+impl FromLeStream for MgmtRtgResponse {
+    fn from_le_stream<T>(mut bytes: T) -> Option<Self>
+    where
+        T: Iterator<Item = u8>,
+    {
+        let status = u8::from_le_stream(&mut bytes)?;
+
+        if status != u8::from(Status::Success) {
+            return Some(Self {
+                status,
+                routing_table_entries: 0,
+                start_index: 0,
+                records: ByteSizedVec::new(),
+            });
+        }
+
+        Some(Self {
+            status,
+            routing_table_entries: u8::from_le_stream(&mut bytes)?,
+            start_index: u8::from_le_stream(&mut bytes)?,
+            records: ByteSizedVec::from_le_stream(&mut bytes)?,
+        })
+    }
+}
+
+/// A routing table record of a Mgmt Routing response.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq, FromLeStream)]
+pub struct RoutingRecord {
+    destination: NodeId,
+    flags: u8,
+    next_hop: NodeId,
+}
+
+impl RoutingRecord {
+    /// Returns the network address of the destination.
+    #[must_use]
+    pub const fn destination(&self) -> NodeId {
+        self.destination
+    }
+
+    /// Returns the status of the route.
+    ///
+    /// # Errors
+    ///
+    /// Returns the raw status if it is invalid.
+    pub fn status(&self) -> Result<RouteStatus, u8> {
+        RouteStatus::try_from(self.flags & STATUS_MASK)
+    }
+
+    /// Returns `true` if the destination is memory constrained.
+    #[must_use]
+    pub const fn is_memory_constrained(&self) -> bool {
+        self.flags & MEMORY_CONSTRAINED != 0
+    }
+
+    /// Returns `true` if the destination is a concentrator with a many-to-one route.
+    #[must_use]
+    pub const fn is_many_to_one(&self) -> bool {
+        self.flags & MANY_TO_ONE != 0
+    }
+
+    /// Returns `true` if a route record must be sent before data to the destination.
+    #[must_use]
+    pub const fn is_route_record_required(&self) -> bool {
+        self.flags & ROUTE_RECORD_REQUIRED != 0
+    }
+
+    /// Returns the network address of the next hop.
+    #[must_use]
+    pub const fn next_hop(&self) -> NodeId {
+        self.next_hop
+    }
+}
+
+/// Status of a route in a Mgmt Routing response.
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd, FromPrimitive)]
+#[repr(u8)]
+pub enum RouteStatus {
+    /// The route is active.
+    Active = 0x00,
+    /// Route discovery is underway.
+    DiscoveryUnderway = 0x01,
+    /// Route discovery failed.
+    DiscoveryFailed = 0x02,
+    /// The route is inactive.
+    Inactive = 0x03,
+    /// Route validation is underway.
+    ValidationUnderway = 0x04,
+}
+
+impl From<RouteStatus> for u8 {
+    fn from(status: RouteStatus) -> Self {
+        status as Self
+    }
+}
+
+impl TryFrom<u8> for RouteStatus {
+    type Error = u8;
+
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
+        Self::from_u8(value).ok_or(value)
+    }
+}
@@ -0,0 +1,93 @@
+use le_stream::FromLeStream;
+
+use crate::zdp::DeviceType;
+
+const LOGICAL_TYPE_MASK: u8 = 0b0000_0111;
+const COMPLEX_DESCRIPTOR_AVAILABLE: u8 = 0b0000_1000;
+const USER_DESCRIPTOR_AVAILABLE: u8 = 0b0001_0000;
+const FREQUENCY_BAND_SHIFT: u8 = 3;
+
+/// Node descriptor describing a device's type and capabilities.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq, FromLeStream)]
+pub struct NodeDescriptor {
+    flags: u8,
+    frequency_band: u8,
+    mac_capability: u8,
+    manufacturer_code: u16,
+    maximum_buffer_size: u8,
+    maximum_incoming_transfer_size: u16,
+    server_mask: u16,
+    maximum_outgoing_transfer_size: u16,
+    descriptor_capability: u8,
+}
+
+impl NodeDescriptor {
+    /// Returns the logical device type.
+    ///
+    /// # Errors
+    ///
+    /// Returns the raw logical type if it is invalid.
+    pub fn logical_type(&self) -> Result<DeviceType, u8> {
+        DeviceType::try_from(self.flags & LOGICAL_TYPE_MASK)
+    }
+
+    /// Returns `true` if the device provides a complex descriptor.
+    #[must_use]
+    pub const fn complex_descriptor_available(&self) -> bool {
+        self.flags & COMPLEX_DESCRIPTOR_AVAILABLE != 0
+    }
+
+    /// Returns `true` if the device provides a user descriptor.
+    #[must_use]
+    pub const fn user_descriptor_available(&self) -> bool {
+        self.flags & USER_DESCRIPTOR_AVAILABLE != 0
+    }
+
+    /// Returns the bitmask of supported frequency bands.
+    #[must_use]
+    pub const fn frequency_band(&self) -> u8 {
+        self.frequency_band >> FREQUENCY_BAND_SHIFT
+    }
+
+    /// Returns the MAC capability flags.
+    #[must_use]
+    pub const fn mac_capability(&self) -> u8 {
+        self.mac_capability
+    }
+
+    /// Returns the manufacturer code.
+    #[must_use]
+    pub const fn manufacturer_code(&self) -> u16 {
+        self.manufacturer_code
+    }
+
+    /// Returns the maximum size of a network sub-layer data unit.
+    #[must_use]
+    pub const fn maximum_buffer_size(&self) -> u8 {
+        self.maximum_buffer_size
+    }
+
+    /// Returns the maximum size of an incoming application sub-layer data unit.
+    #[must_use]
+    pub const fn maximum_incoming_transfer_size(&self) -> u16 {
+        self.maximum_incoming_transfer_size
+    }
+
+    /// Returns the server mask.
+    #[must_use]
+    pub const fn server_mask(&self) -> u16 {
+        self.server_mask
+    }
+
+    /// Returns the maximum size of an outgoing application sub-layer data unit.
+    #[must_use]
+    pub const fn maximum_outgoing_transfer_size(&self) -> u16 {
+        self.maximum_outgoing_transfer_size
+    }
+
+    /// Returns the descriptor capability flags.
+    #[must_use]
+    pub const fn descriptor_capability(&self) -> u8 {
+        self.descriptor_capability
+    }
+}
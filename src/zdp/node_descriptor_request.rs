@@ -0,0 +1,22 @@
+use le_stream::ToLeStream;
+
+use crate::ember::NodeId;
+use crate::zdp::Command;
+
+/// Node Descriptor Request reading a device's node descriptor.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq, ToLeStream)]
+pub struct NodeDescriptorRequest {
+    node_id: NodeId,
+}
+
+impl NodeDescriptorRequest {
+    /// Creates a request for the node descriptor of `node_id`.
+    #[must_use]
+    pub const fn new(node_id: NodeId) -> Self {
+        Self { node_id }
+    }
+}
+
+impl Command for NodeDescriptorRequest {
+    const CLUSTER_ID: u16 = 0x0002;
+}
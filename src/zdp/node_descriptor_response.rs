@@ -0,0 +1,62 @@
+use le_stream::FromLeStream;
+
+use crate::ember::NodeId;
+use crate::zdp::{Command, NodeDescriptor, NodeDescriptorRequest, RESPONSE_BIT, Status};
+
+/// Node Descriptor Response carrying a device's node descriptor.
+///
+/// Responses with a status other than [`Status::Success`] carry no descriptor.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct NodeDescriptorResponse {
+    status: u8,
+    node_id: NodeId,
+    descriptor: Option<NodeDescriptor>,
+}
+
+impl NodeDescriptorResponse {
+    /// Returns the response status.
+    ///
+    /// # Errors
+    ///
+    /// Returns the raw status if it is not a known ZDP status.
+    pub fn status(&self) -> Result<Status, u8> {
+        Status::try_from(self.status)
+    }
+
+    /// Returns the network address of the queried device.
+    #[must_use]
+    pub const fn node_id(&self) -> NodeId {
+        self.node_id
+    }
+
+    /// Returns the node descriptor.
+    #[must_use]
+    pub const fn descriptor(&self) -> Option<&NodeDescriptor> {
+        self.descriptor.as_ref()
+    }
+}
+
+impl Command for NodeDescriptorResponse {
+    const CLUSTER_ID: u16 = NodeDescriptorRequest::CLUSTER_ID | RESPONSE_BIT;
+}
+
+impl FromLeStream for NodeDescriptorResponse {
+    fn from_le_stream<T>(mut bytes: T) -> Option<Self>
+    where
+        T: Iterator<Item = u8>,
+    {
+        let status = u8::from_le_stream(&mut bytes)?;
+        let node_id = NodeId::from_le_stream(&mut bytes)?;
+        let descriptor = if status == u8::from(Status::Success) {
+            Some(NodeDescriptor::from_le_stream(&mut bytes)?)
+        } else {
+            None
+        };
+
+        Some(Self {
+            status,
+            node_id,
+            descriptor,
+        })
+    }
+}
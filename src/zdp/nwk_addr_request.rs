@@ -0,0 +1,31 @@
+use le_stream::ToLeStream;
+
+use crate::ember::Eui64;
+use crate::zdp::Command;
+
+/// Request type asking for the network address of the device only.
+const SINGLE_DEVICE_RESPONSE: u8 = 0x00;
+
+/// NWK Address Request resolving a device's network address from its IEEE address.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq, ToLeStream)]
+pub struct NwkAddrRequest {
+    ieee_address: Eui64,
+    request_type: u8,
+    start_index: u8,
+}
+
+impl NwkAddrRequest {
+    /// Creates a request for the network address of the device with `ieee_address`.
+    #[must_use]
+    pub const fn new(ieee_address: Eui64) -> Self {
+        Self {
+            ieee_address,
+            request_type: SINGLE_DEVICE_RESPONSE,
+            start_index: 0,
+        }
+    }
+}
+
+impl Command for NwkAddrRequest {
+    const CLUSTER_ID: u16 = 0x0000;
+}
@@ -0,0 +1,39 @@
+use le_stream::FromLeStream;
+
+use crate::ember::{Eui64, NodeId};
+use crate::zdp::{Command, NwkAddrRequest, RESPONSE_BIT, Status};
+
+/// NWK Address Response reporting the network address of a device.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq, FromLeStream)]
+pub struct NwkAddrResponse {
+    status: u8,
+    ieee_address: Eui64,
+    node_id: NodeId,
+}
+
+impl NwkAddrResponse {
+    /// Returns the response status.
+    ///
+    /// # Errors
+    ///
+    /// Returns the raw status if it is not a known ZDP status.
+    pub fn status(&self) -> Result<Status, u8> {
+        Status::try_from(self.status)
+    }
+
+    /// Returns the IEEE address of the device.
+    #[must_use]
+    pub const fn ieee_address(&self) -> Eui64 {
+        self.ieee_address
+    }
+
+    /// Returns the network address of the device.
+    #[must_use]
+    pub const fn node_id(&self) -> NodeId {
+        self.node_id
+    }
+}
+
+impl Command for NwkAddrResponse {
+    const CLUSTER_ID: u16 = NwkAddrRequest::CLUSTER_ID | RESPONSE_BIT;
+}
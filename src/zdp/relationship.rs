@@ -0,0 +1,46 @@
+use std::fmt::Display;
+
+use num_derive::FromPrimitive;
+use num_traits::FromPrimitive;
+
+/// Relationship of a neighbor to the device reporting it in a Mgmt LQI response.
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd, FromPrimitive)]
+#[repr(u8)]
+pub enum Relationship {
+    /// The neighbor is the parent of the reporting device.
+    Parent = 0x00,
+    /// The neighbor is a child of the reporting device.
+    Child = 0x01,
+    /// The neighbor is a sibling of the reporting device.
+    Sibling = 0x02,
+    /// The neighbor has no relationship with the reporting device.
+    None = 0x03,
+    /// The neighbor is a previous child of the reporting device.
+    PreviousChild = 0x04,
+}
+
+impl Display for Relationship {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Parent => write!(f, "Parent"),
+            Self::Child => write!(f, "Child"),
+            Self::Sibling => write!(f, "Sibling"),
+            Self::None => write!(f, "None"),
+            Self::PreviousChild => write!(f, "Previous Child"),
+        }
+    }
+}
+
+impl From<Relationship> for u8 {
+    fn from(relationship: Relationship) -> Self {
+        relationship as Self
+    }
+}
+
+impl TryFrom<u8> for Relationship {
+    type Error = u8;
+
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
+        Self::from_u8(value).ok_or(value)
+    }
+}
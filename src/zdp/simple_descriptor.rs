@@ -0,0 +1,52 @@
+use le_stream::FromLeStream;
+
+use crate::types::ByteSizedVec;
+
+/// Simple descriptor describing the clusters of an application endpoint.
+#[derive(Clone, Debug, Eq, Hash, PartialEq, FromLeStream)]
+pub struct SimpleDescriptor {
+    endpoint: u8,
+    profile_id: u16,
+    device_id: u16,
+    device_version: u8,
+    input_clusters: ByteSizedVec<u16>,
+    output_clusters: ByteSizedVec<u16>,
+}
+
+impl SimpleDescriptor {
+    /// Returns the endpoint.
+    #[must_use]
+    pub const fn endpoint(&self) -> u8 {
+        self.endpoint
+    }
+
+    /// Returns the application profile ID.
+    #[must_use]
+    pub const fn profile_id(&self) -> u16 {
+        self.profile_id
+    }
+
+    /// Returns the application device ID.
+    #[must_use]
+    pub const fn device_id(&self) -> u16 {
+        self.device_id
+    }
+
+    /// Returns the application device version.
+    #[must_use]
+    pub const fn device_version(&self) -> u8 {
+        self.device_version
+    }
+
+    /// Returns the server clusters of the endpoint.
+    #[must_use]
+    pub fn input_clusters(&self) -> &[u16] {
+        &self.input_clusters
+    }
+
+    /// Returns the client clusters of the endpoint.
+    #[must_use]
+    pub fn output_clusters(&self) -> &[u16] {
+        &self.output_clusters
+    }
+}
@@ -0,0 +1,23 @@
+use le_stream::ToLeStream;
+
+use crate::ember::NodeId;
+use crate::zdp::Command;
+
+/// Simple Descriptor Request reading the simple descriptor of an endpoint.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq, ToLeStream)]
+pub struct SimpleDescriptorRequest {
+    node_id: NodeId,
+    endpoint: u8,
+}
+
+impl SimpleDescriptorRequest {
+    /// Creates a request for the simple descriptor of `endpoint` on `node_id`.
+    #[must_use]
+    pub const fn new(node_id: NodeId, endpoint: u8) -> Self {
+        Self { node_id, endpoint }
+    }
+}
+
+impl Command for SimpleDescriptorRequest {
+    const CLUSTER_ID: u16 = 0x0004;
+}
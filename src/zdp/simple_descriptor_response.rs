@@ -0,0 +1,107 @@
+use le_stream::FromLeStream;
+
+use crate::ember::NodeId;
+use crate::zdp::{Command, RESPONSE_BIT, SimpleDescriptor, SimpleDescriptorRequest, Status};
+
+/// Simple Descriptor Response carrying the simple descriptor of an endpoint.
+///
+/// Responses with a status other than [`Status::Success`] carry no descriptor.
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub struct SimpleDescriptorResponse {
+    status: u8,
+    node_id: NodeId,
+    descriptor: Option<SimpleDescriptor>,
+}
+
+impl SimpleDescriptorResponse {
+    /// Returns the response status.
+    ///
+    /// # Errors
+    ///
+    /// Returns the raw status if it is not a known ZDP status.
+    pub fn status(&self) -> Result<Status, u8> {
+        Status::try_from(self.status)
+    }
+
+    /// Returns the network address of the queried device.
+    #[must_use]
+    pub const fn node_id(&self) -> NodeId {
+        self.node_id
+    }
+
+    /// Returns the simple descriptor.
+    #[must_use]
+    pub const fn descriptor(&self) -> Option<&SimpleDescriptor> {
+        self.descriptor.as_ref()
+    }
+
+    /// Consumes the response and returns the simple descriptor.
+    #[must_use]
+    pub fn into_descriptor(self) -> Option<SimpleDescriptor> {
+        self.descriptor
+    }
+}
+
+impl Command for SimpleDescriptorResponse {
+    const CLUSTER_ID: u16 = SimpleDescriptorRequest::CLUSTER_ID | RESPONSE_BIT;
+}
+
+impl FromLeStream for SimpleDescriptorResponse {
+    fn from_le_stream<T>(mut bytes: T) -> Option<Self>
+    where
+        T: Iterator<Item = u8>,
+    {
+        let status = u8::from_le_stream(&mut bytes)?;
+        let node_id = NodeId::from_le_stream(&mut bytes)?;
+        let length = u8::from_le_stream(&mut bytes).unwrap_or_default();
+        let descriptor = if status == u8::from(Status::Success) && length > 0 {
+            Some(SimpleDescriptor::from_le_stream(&mut bytes)?)
+        } else {
+            None
+        };
+
+        Some(Self {
+            status,
+            node_id,
+            descriptor,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use le_stream::FromLeStream;
+
+    use super::SimpleDescriptorResponse;
+    use crate::zdp::Status;
+
+    #[test]
+    fn parses_descriptor() {
+        let bytes = [
+            0x00, 0x34, 0x12, 0x0E, // status, node ID, length
+            0x01, 0x04, 0x01, 0x02, 0x01, 0x01, // endpoint, profile, device, version
+            0x02, 0x00, 0x00, 0x06, 0x00, // input clusters
+            0x01, 0x19, 0x00, // output clusters
+        ];
+        let response =
+            SimpleDescriptorResponse::from_le_slice(&bytes).expect("response should parse");
+        let descriptor = response.descriptor().expect("descriptor should be present");
+
+        assert_eq!(response.status(), Ok(Status::Success));
+        assert_eq!(response.node_id(), 0x1234);
+        assert_eq!(descriptor.endpoint(), 1);
+        assert_eq!(descriptor.profile_id(), 0x0104);
+        assert_eq!(descriptor.device_id(), 0x0102);
+        assert_eq!(descriptor.input_clusters(), [0x0000, 0x0006]);
+        assert_eq!(descriptor.output_clusters(), [0x0019]);
+    }
+
+    #[test]
+    fn parses_failure_without_descriptor() {
+        let response = SimpleDescriptorResponse::from_le_slice(&[0x83, 0x34, 0x12, 0x00])
+            .expect("response should parse");
+
+        assert_eq!(response.status(), Ok(Status::NotActive));
+        assert!(response.descriptor().is_none());
+    }
+}
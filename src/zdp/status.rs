@@ -0,0 +1,58 @@
+use std::fmt::Display;
+
+use num_derive::FromPrimitive;
+use num_traits::FromPrimitive;
+
+/// ZDP status codes.
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd, FromPrimitive)]
+#[repr(u8)]
+pub enum Status {
+    /// The request succeeded.
+    Success = 0x00,
+    /// The supplied request type was invalid.
+    InvalidRequestType = 0x80,
+    /// The requested device did not exist on a device following a child descriptor request.
+    DeviceNotFound = 0x81,
+    /// The supplied endpoint was equal to zero or greater than 240.
+    InvalidEndpoint = 0x82,
+    /// The requested endpoint is not described by a simple descriptor.
+    NotActive = 0x83,
+    /// The requested optional feature is not supported on the target device.
+    NotSupported = 0x84,
+    /// A timeout has occurred with the requested operation.
+    Timeout = 0x85,
+    /// The end device bind request was unsuccessful due to a failure to match any suitable clusters.
+    NoMatch = 0x86,
+    /// The unbind request was unsuccessful due to the coordinator or source device not having an entry in its binding table to unbind.
+    NoEntry = 0x88,
+    /// A child descriptor was not available following a discovery request to a parent.
+    NoDescriptor = 0x89,
+    /// The device does not have storage space to support the requested operation.
+    InsufficientSpace = 0x8A,
+    /// The device is not in the proper state to support the requested operation.
+    NotPermitted = 0x8B,
+    /// The device does not have table space to support the operation.
+    TableFull = 0x8C,
+    /// The permissions configuration table on the target indicates that the request is not authorized from this device.
+    NotAuthorized = 0x8D,
+}
+
+impl Display for Status {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{self:?}")
+    }
+}
+
+impl From<Status> for u8 {
+    fn from(status: Status) -> Self {
+        status as Self
+    }
+}
+
+impl TryFrom<u8> for Status {
+    type Error = u8;
+
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
+        Self::from_u8(value).ok_or(value)
+    }
+}
@@ -0,0 +1,36 @@
+use le_stream::ToLeStream;
+
+use crate::ember::Eui64;
+use crate::ember::binding::TableEntry;
+use crate::zdp::Command;
+use crate::zdp::bind_request::encode;
+
+/// Unbind Request removing a binding from a remote device.
+///
+/// The binding is described like for a [`BindRequest`](crate::zdp::BindRequest).
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub struct UnbindRequest {
+    payload: Box<[u8]>,
+}
+
+impl UnbindRequest {
+    /// Creates a request removing `entry` from the binding table of the device with the IEEE address `source`.
+    ///
+    /// Returns `None` if the entry is unused or has an invalid type.
+    #[must_use]
+    pub fn new(source: Eui64, entry: &TableEntry) -> Option<Self> {
+        encode(source, entry).map(|payload| Self { payload })
+    }
+}
+
+impl Command for UnbindRequest {
+    const CLUSTER_ID: u16 = 0x0022;
+}
+
+impl ToLeStream for UnbindRequest {
+    type Iter = std::vec::IntoIter<u8>;
+
+    fn to_le_stream(self) -> Self::Iter {
+        self.payload.into_vec().into_iter()
+    }
+}
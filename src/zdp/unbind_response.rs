@@ -0,0 +1,24 @@
+use le_stream::FromLeStream;
+
+use crate::zdp::{Command, RESPONSE_BIT, Status, UnbindRequest};
+
+/// Unbind Response reporting the result of an [`UnbindRequest`].
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq, FromLeStream)]
+pub struct UnbindResponse {
+    status: u8,
+}
+
+impl UnbindResponse {
+    /// Returns the response status.
+    ///
+    /// # Errors
+    ///
+    /// Returns the raw status if it is not a known ZDP status.
+    pub fn status(&self) -> Result<Status, u8> {
+        Status::try_from(self.status)
+    }
+}
+
+impl Command for UnbindResponse {
+    const CLUSTER_ID: u16 = UnbindRequest::CLUSTER_ID | RESPONSE_BIT;
+}